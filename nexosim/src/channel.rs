@@ -9,8 +9,8 @@ use std::error;
 use std::fmt;
 use std::future::Future;
 use std::marker::PhantomData;
-use std::sync::atomic::{self, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use async_event::Event;
 use diatomic_waker::primitives::DiatomicWaker;
@@ -20,6 +20,8 @@ use queue::{PopError, PushError, Queue};
 use recycle_box::coerce_box;
 
 use crate::model::{Context, Model};
+use crate::simulation::OverflowPolicy;
+use crate::util::semaphore::Semaphore;
 
 // Counts the difference between the number of sent and received messages for
 // this thread.
@@ -39,17 +41,34 @@ struct Inner<M> {
     sender_signal: Event,
     /// Current count of live senders.
     sender_count: AtomicUsize,
+    /// Behavior of senders when the queue is full.
+    overflow_policy: OverflowPolicy,
+    /// Lock serializing the pop operations of the receiver and of the senders
+    /// when the latter may discard the oldest message.
+    consumer_lock: Mutex<()>,
+    /// Whether a message was popped by the receiver but awaits a concurrency
+    /// permit before being processed.
+    is_awaiting_permit: AtomicBool,
 }
 
 impl<M: 'static> Inner<M> {
-    fn new(capacity: usize) -> Self {
+    fn new(capacity: usize, overflow_policy: OverflowPolicy) -> Self {
         Self {
             queue: Queue::new(capacity),
             receiver_signal: DiatomicWaker::new(),
             sender_signal: Event::new(),
             sender_count: AtomicUsize::new(0),
+            overflow_policy,
+            consumer_lock: Mutex::new(()),
+            is_awaiting_permit: AtomicBool::new(false),
         }
     }
+
+    /// Acquires the consumer lock if senders may pop messages from the queue.
+    fn lock_consumer(&self) -> Option<MutexGuard<'_, ()>> {
+        (self.overflow_policy == OverflowPolicy::DropOldest)
+            .then(|| self.consumer_lock.lock().unwrap())
+    }
 }
 
 /// A receiver which can asynchronously execute `async` message that take an
@@ -59,6 +78,9 @@ pub(crate) struct Receiver<M> {
     inner: Arc<Inner<M>>,
     /// A recyclable box to temporarily store the `async` closure to be executed.
     future_box: Option<RecycleBox<()>>,
+    /// An optional semaphore shared with other receivers, which caps the
+    /// number of messages being executed concurrently.
+    limiter: Option<Arc<Semaphore>>,
 }
 
 impl<M: Model> Receiver<M> {
@@ -69,14 +91,31 @@ impl<M: Model> Receiver<M> {
     /// The constructor will panic if the requested capacity is 0 or is greater
     /// than `usize::MAX/2 + 1`.
     pub(crate) fn new(capacity: usize) -> Self {
-        let inner = Arc::new(Inner::new(capacity));
+        Self::with_overflow_policy(capacity, OverflowPolicy::Block)
+    }
+
+    /// Creates a new receiver with the specified capacity and behavior of the
+    /// senders when the channel is full.
+    ///
+    /// # Panic
+    ///
+    /// The constructor will panic if the requested capacity is 0 or is greater
+    /// than `usize::MAX/2 + 1`.
+    pub(crate) fn with_overflow_policy(capacity: usize, overflow_policy: OverflowPolicy) -> Self {
+        let inner = Arc::new(Inner::new(capacity, overflow_policy));
 
         Receiver {
             inner,
             future_box: Some(RecycleBox::new(())),
+            limiter: None,
         }
     }
 
+    /// Sets a semaphore that must be acquired before executing each message.
+    pub(crate) fn set_limiter(&mut self, limiter: Arc<Semaphore>) {
+        self.limiter = Some(limiter);
+    }
+
     /// Creates a new sender.
     pub(crate) fn sender(&self) -> Sender<M> {
         // Increase the reference count of senders.
@@ -110,19 +149,19 @@ impl<M: Model> Receiver<M> {
         let msg = unsafe {
             self.inner
                 .receiver_signal
-                .wait_until(|| match self.inner.queue.pop() {
-                    Ok(msg) => Some(Some(msg)),
-                    Err(PopError::Empty) => None,
-                    Err(PopError::Closed) => Some(None),
+                .wait_until(|| {
+                    let consumer_guard = self.inner.lock_consumer();
+                    match self.inner.queue.pop() {
+                        Ok(msg) => Some(Some((msg, consumer_guard))),
+                        Err(PopError::Empty) => None,
+                        Err(PopError::Closed) => Some(None),
+                    }
                 })
                 .await
         };
 
         match msg {
-            Some(mut msg) => {
-                // Decrement the count of in-flight messages.
-                THREAD_MSG_COUNT.set(THREAD_MSG_COUNT.get().wrapping_sub(1));
-
+            Some((mut msg, consumer_guard)) => {
                 // Take the message to obtain a boxed future.
                 let fut = msg.call_once(model, cx, self.future_box.take().unwrap());
 
@@ -130,8 +169,30 @@ impl<M: Model> Receiver<M> {
                 // in the queue and signal to one awaiting sender that a slot is
                 // available for sending.
                 drop(msg);
+                drop(consumer_guard);
                 self.inner.sender_signal.notify_one();
 
+                // Wait for a permit if concurrency is capped. The message is
+                // only considered received once the permit is obtained and
+                // is counted by observers in the meantime, so that a message
+                // stuck waiting for a permit is reported as a deadlock by the
+                // executor.
+                let _permit = match &self.limiter {
+                    Some(limiter) => {
+                        self.inner.is_awaiting_permit.store(true, Ordering::Relaxed);
+                        let permit = limiter.acquire().await;
+                        self.inner
+                            .is_awaiting_permit
+                            .store(false, Ordering::Relaxed);
+
+                        Some(permit)
+                    }
+                    None => None,
+                };
+
+                // Decrement the count of in-flight messages.
+                THREAD_MSG_COUNT.set(THREAD_MSG_COUNT.get().wrapping_sub(1));
+
                 // Await the future provided by the message.
                 let mut fut = RecycleBox::into_pin(fut);
                 fut.as_mut().await;
@@ -203,6 +264,10 @@ pub(crate) struct Sender<M: 'static> {
 impl<M: Model> Sender<M> {
     /// Sends a message, if necessary waiting until enough capacity becomes
     /// available in the channel.
+    ///
+    /// If the channel is full and its overflow policy is not
+    /// [`OverflowPolicy::Block`], either the oldest message or the sent message
+    /// is discarded instead.
     pub(crate) async fn send<F>(&self, msg_fn: F) -> Result<(), SendError>
     where
         F: for<'a> FnOnce(
@@ -219,32 +284,55 @@ impl<M: Model> Sender<M> {
             coerce_box!(RecycleBox::recycle(vacated_box, MessageFnOnce::new(msg_fn)))
         });
 
-        let success = self
+        // The outcome is `Ok(false)` if the message was discarded.
+        let outcome = self
             .inner
             .sender_signal
-            .wait_until(|| {
+            .wait_until(|| loop {
                 match self.inner.queue.push(msg_fn.take().unwrap()) {
-                    Ok(()) => Some(true),
-                    Err(PushError::Full(m)) => {
-                        // Recycle the message.
-                        msg_fn = Some(m);
-
-                        None
-                    }
-                    Err(PushError::Closed) => Some(false),
+                    Ok(()) => return Some(Ok(true)),
+                    Err(PushError::Full(m)) => match self.inner.overflow_policy {
+                        OverflowPolicy::Block => {
+                            // Recycle the message.
+                            msg_fn = Some(m);
+
+                            return None;
+                        }
+                        OverflowPolicy::DropNewest => return Some(Ok(false)),
+                        OverflowPolicy::DropOldest => {
+                            // Recycle the message and try again once a slot was
+                            // freed.
+                            msg_fn = Some(m);
+                            self.discard_oldest();
+                        }
+                    },
+                    Err(PushError::Closed) => return Some(Err(SendError)),
                 }
             })
             .await;
 
-        if success {
+        if outcome? {
             self.inner.receiver_signal.notify();
 
             // Increment the count of in-flight messages.
             THREAD_MSG_COUNT.set(THREAD_MSG_COUNT.get().wrapping_add(1));
+        }
+
+        Ok(())
+    }
+
+    /// Discards the oldest message of the channel, if any.
+    fn discard_oldest(&self) {
+        let _consumer_guard = self.inner.lock_consumer();
+
+        // Safety: with the `DropOldest` policy, the receiver and the senders
+        // only pop messages while holding the consumer lock.
+        if let Ok(msg) = unsafe { self.inner.queue.pop() } {
+            drop(msg);
 
-            Ok(())
-        } else {
-            Err(SendError)
+            // The message will never be received, so it is no longer in
+            // flight.
+            THREAD_MSG_COUNT.set(THREAD_MSG_COUNT.get().wrapping_sub(1));
         }
     }
 
@@ -252,7 +340,6 @@ impl<M: Model> Sender<M> {
     ///
     /// This prevents any further messages from being sent. Messages that were
     /// already sent can still be received.
-    #[allow(unused)]
     pub(crate) fn close(&self) {
         self.inner.queue.close();
 
@@ -266,7 +353,6 @@ impl<M: Model> Sender<M> {
     ///
     /// This can happen either because the [`Receiver`] was dropped or because
     /// one of the [`Sender::close`] or [`Receiver::close`] method was called.
-    #[allow(unused)]
     pub(crate) fn is_closed(&self) -> bool {
         self.inner.queue.is_closed()
     }
@@ -301,7 +387,8 @@ impl<M> Clone for Sender<M> {
 /// A model-independent handle to a channel that can observe the current number
 /// of messages.
 pub(crate) trait ChannelObserver: Send {
-    /// Returns the current number of messages in the channel.
+    /// Returns the current number of messages in the channel, including a
+    /// message taken by the receiver that still awaits a concurrency permit.
     ///
     /// # Warning
     ///
@@ -311,6 +398,15 @@ pub(crate) trait ChannelObserver: Send {
     /// the past state of the channel, and may be greater than the capacity of
     /// the channel.
     fn len(&self) -> usize;
+
+    /// Returns the maximum number of messages in the channel.
+    fn capacity(&self) -> usize;
+
+    /// Returns a unique identifier for the channel.
+    ///
+    /// The identifier is the same as the one returned by
+    /// [`Sender::channel_id`] for senders of the same channel.
+    fn channel_id(&self) -> usize;
 }
 
 /// A handle to a channel that can observe the current number of messages.
@@ -325,7 +421,15 @@ pub(crate) struct Observer<M: 'static> {
 
 impl<M: Model> ChannelObserver for Observer<M> {
     fn len(&self) -> usize {
-        self.inner.queue.len()
+        self.inner.queue.len() + self.inner.is_awaiting_permit.load(Ordering::Relaxed) as usize
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.queue.capacity()
+    }
+
+    fn channel_id(&self) -> usize {
+        Arc::as_ptr(&self.inner) as usize
     }
 }
 
@@ -342,6 +342,11 @@ impl<T: ?Sized> Queue<T> {
         (enqueue_idx + (carry_flag as usize) * self.buffer.len()) - dequeue_idx
     }
 
+    /// Returns the capacity of the queue.
+    pub(super) fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
     /// Increment the queue position, incrementing the sequence count as well if
     /// the index wraps to 0.
     ///
@@ -17,11 +17,12 @@ impl Executor {
     /// The maximum number of threads is set with the `pool_size` parameter.
     pub fn new(pool_size: usize) -> Self {
         let dummy_cx = crate::executor::SimulationContext {
-            #[cfg(feature = "tracing")]
             time_reader: crate::util::sync_cell::SyncCell::new(
                 crate::time::TearableAtomicTime::new(crate::time::MonotonicTime::EPOCH),
             )
             .reader(),
+            #[cfg(feature = "tracing")]
+            time_slice_span: std::sync::Arc::new(std::sync::RwLock::new(tracing::Span::none())),
         };
         Self(executor::Executor::new_multi_threaded(
             pool_size,
@@ -45,6 +46,6 @@ impl Executor {
     /// Let the executor run, blocking until all futures have completed or until
     /// the executor deadlocks.
     pub fn run(&mut self) {
-        self.0.run(Duration::ZERO).unwrap();
+        self.0.run(Duration::ZERO, 0).unwrap();
     }
 }
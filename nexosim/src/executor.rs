@@ -8,13 +8,14 @@ use std::any::Any;
 use std::future::Future;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+#[cfg(feature = "tracing")]
+use std::sync::RwLock;
 use std::time::Duration;
 
 use crossbeam_utils::CachePadded;
 
 use crate::macros::scoped_thread_local::scoped_thread_local;
 use crate::simulation::ModelId;
-#[cfg(feature = "tracing")]
 use crate::time::AtomicTimeReader;
 use task::Promise;
 
@@ -27,6 +28,8 @@ pub(crate) enum ExecutorError {
     UnprocessedMessages(usize),
     /// The simulation has timed out.
     Timeout,
+    /// The maximum number of task polls has been reached.
+    PollBudgetExceeded,
     /// The simulation has panicked.
     Panic(ModelId, Box<dyn Any + Send + 'static>),
 }
@@ -35,8 +38,10 @@ pub(crate) enum ExecutorError {
 #[derive(Clone)]
 pub(crate) struct SimulationContext {
     /// Read-only handle to the simulation time.
-    #[cfg(feature = "tracing")]
     pub(crate) time_reader: AtomicTimeReader,
+    /// Span of the time slice being currently processed, if any.
+    #[cfg(feature = "tracing")]
+    pub(crate) time_slice_span: Arc<RwLock<tracing::Span>>,
 }
 
 scoped_thread_local!(pub(crate) static SIMULATION_CONTEXT: SimulationContext);
@@ -108,11 +113,47 @@ impl Executor {
         }
     }
 
+    /// Returns the number of threads on which futures are executed.
+    pub(crate) fn num_threads(&self) -> usize {
+        match self {
+            Self::StExecutor(_) => 1,
+            Self::MtExecutor(executor) => executor.num_threads(),
+        }
+    }
+
+    /// Returns the number of tasks run during the last call to
+    /// [`run`](Executor::run).
+    pub(crate) fn tasks_run_last(&self) -> usize {
+        match self {
+            Self::StExecutor(executor) => executor.tasks_run_last(),
+            Self::MtExecutor(executor) => executor.tasks_run_last(),
+        }
+    }
+
+    /// Wakes up all worker threads of a multi-threaded executor, if any, to
+    /// make them process trivial tasks.
+    ///
+    /// Processing of the tasks is only guaranteed to complete after the next
+    /// call to [`run`](Executor::run).
+    pub(crate) fn prewarm(&self) {
+        match self {
+            Self::StExecutor(_) => {}
+            Self::MtExecutor(executor) => executor.prewarm(),
+        }
+    }
+
     /// Execute spawned tasks, blocking until all futures have completed or
     /// until the executor reaches a deadlock.
-    pub(crate) fn run(&mut self, timeout: Duration) -> Result<(), ExecutorError> {
+    ///
+    /// A non-zero poll budget limits the number of task polls, but it is only
+    /// enforced by the single-threaded executor.
+    pub(crate) fn run(
+        &mut self,
+        timeout: Duration,
+        poll_budget: usize,
+    ) -> Result<(), ExecutorError> {
         match self {
-            Self::StExecutor(executor) => executor.run(timeout),
+            Self::StExecutor(executor) => executor.run(timeout, poll_budget),
             Self::MtExecutor(executor) => executor.run(timeout),
         }
     }
@@ -151,11 +192,12 @@ mod tests {
 
     fn dummy_simulation_context() -> SimulationContext {
         SimulationContext {
-            #[cfg(feature = "tracing")]
             time_reader: crate::util::sync_cell::SyncCell::new(
                 crate::time::TearableAtomicTime::new(crate::time::MonotonicTime::EPOCH),
             )
             .reader(),
+            #[cfg(feature = "tracing")]
+            time_slice_span: Arc::new(RwLock::new(tracing::Span::none())),
         }
     }
 
@@ -228,7 +270,7 @@ mod tests {
             }
         });
 
-        executor.run(Duration::ZERO).unwrap();
+        executor.run(Duration::ZERO, 0).unwrap();
 
         // Make sure that all tasks are eventually dropped even though each task
         // wakes the others when dropped.
@@ -252,4 +294,20 @@ mod tests {
             Signal::new(),
         ));
     }
+
+    #[test]
+    fn executor_poll_budget_st() {
+        let mut executor = Executor::new_single_threaded(dummy_simulation_context(), Signal::new());
+
+        // A task that keeps re-scheduling itself.
+        executor.spawn_and_forget(std::future::poll_fn(|cx| {
+            cx.waker().wake_by_ref();
+            std::task::Poll::<()>::Pending
+        }));
+
+        assert!(matches!(
+            executor.run(Duration::ZERO, 100),
+            Err(ExecutorError::PollBudgetExceeded)
+        ));
+    }
 }
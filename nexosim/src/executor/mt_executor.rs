@@ -48,7 +48,7 @@ use std::cell::Cell;
 use std::fmt;
 use std::future::Future;
 use std::panic::{self, AssertUnwindSafe};
-use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
@@ -237,9 +237,33 @@ impl Executor {
         self.context.injector.insert_task(runnable);
     }
 
+    /// Returns the number of worker threads.
+    pub(crate) fn num_threads(&self) -> usize {
+        self.worker_handles.len()
+    }
+
+    /// Returns the number of tasks run during the last call to
+    /// [`run`](Executor::run).
+    pub(crate) fn tasks_run_last(&self) -> usize {
+        self.context.task_count.load(Ordering::Relaxed)
+    }
+
+    /// Spawns a no-op task for each worker thread and wakes up all workers.
+    ///
+    /// The woken workers search for tasks until the pool becomes idle again.
+    /// Note that the spawned tasks are not necessarily processed by distinct
+    /// workers.
+    pub(crate) fn prewarm(&self) {
+        for _ in 0..self.worker_handles.len() {
+            self.spawn_and_forget(async {});
+        }
+        self.context.pool_manager.wake_all_workers();
+    }
+
     /// Execute spawned tasks, blocking until all futures have completed or an
     /// error is encountered.
     pub(crate) fn run(&mut self, timeout: Duration) -> Result<(), ExecutorError> {
+        self.context.task_count.store(0, Ordering::Relaxed);
         self.context.pool_manager.activate_worker();
 
         loop {
@@ -336,6 +360,11 @@ struct ExecutorContext {
     /// This counter is only updated by worker threads before they park and is
     /// therefore only consistent once all workers are parked.
     msg_count: AtomicIsize,
+    /// Number of tasks run by all worker threads during the current or last
+    /// call to `run`.
+    ///
+    /// This counter is only consistent once all workers are parked.
+    task_count: AtomicUsize,
 }
 
 impl ExecutorContext {
@@ -359,6 +388,7 @@ impl ExecutorContext {
                 worker_unparkers,
             ),
             msg_count: AtomicIsize::new(0),
+            task_count: AtomicUsize::new(0),
         }
     }
 }
@@ -610,6 +640,10 @@ fn run_local_worker(worker: &Worker, id: usize, parker: Parker, abort_signal: Si
                     if abort_signal.is_set() {
                         return;
                     }
+                    worker
+                        .executor_context
+                        .task_count
+                        .fetch_add(1, Ordering::Relaxed);
                     task.run();
                 }
 
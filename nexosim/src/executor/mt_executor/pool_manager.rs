@@ -217,6 +217,17 @@ impl PoolManager {
         }
     }
 
+    /// Unparks all workers and mark them as active and searching for tasks.
+    ///
+    /// This should only be called when the pool is idle.
+    pub(super) fn wake_all_workers(&self) {
+        self.set_all_workers_active();
+        for unparker in &*self.worker_unparkers {
+            self.begin_worker_search();
+            unparker.unpark();
+        }
+    }
+
     /// Registers a worker panic.
     ///
     /// If a panic was already registered and was not yet processed by the
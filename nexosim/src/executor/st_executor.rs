@@ -1,7 +1,8 @@
 use std::cell::RefCell;
 use std::future::Future;
 use std::panic::AssertUnwindSafe;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use std::{fmt, panic, thread};
 
@@ -29,6 +30,8 @@ pub(crate) struct Executor {
     inner: Option<Box<ExecutorInner>>,
     /// Handle to the forced termination signal.
     abort_signal: Signal,
+    /// Number of tasks run during the last call to `run`.
+    task_count: Arc<AtomicUsize>,
 }
 
 impl Executor {
@@ -44,6 +47,7 @@ impl Executor {
 
         let context = ExecutorContext::new(executor_id);
         let active_tasks = RefCell::new(Slab::new());
+        let task_count = Arc::new(AtomicUsize::new(0));
 
         Self {
             inner: Some(Box::new(ExecutorInner {
@@ -51,8 +55,10 @@ impl Executor {
                 active_tasks,
                 simulation_context,
                 abort_signal: abort_signal.clone(),
+                task_count: task_count.clone(),
             })),
             abort_signal,
+            task_count,
         }
     }
 
@@ -116,11 +122,25 @@ impl Executor {
         queue.push(runnable);
     }
 
+    /// Returns the number of tasks run during the last call to
+    /// [`run`](Executor::run).
+    pub(crate) fn tasks_run_last(&self) -> usize {
+        self.task_count.load(Ordering::Relaxed)
+    }
+
     /// Execute spawned tasks, blocking until all futures have completed or an
     /// error is encountered.
-    pub(crate) fn run(&mut self, timeout: Duration) -> Result<(), ExecutorError> {
+    ///
+    /// If the poll budget is not zero, an error is returned once the specified
+    /// number of task polls is reached, leaving the remaining tasks in the
+    /// queue.
+    pub(crate) fn run(
+        &mut self,
+        timeout: Duration,
+        poll_budget: usize,
+    ) -> Result<(), ExecutorError> {
         if timeout.is_zero() {
-            return self.inner.as_mut().unwrap().run();
+            return self.inner.as_mut().unwrap().run(poll_budget);
         }
 
         // Temporarily move out the inner state so it can be moved to another
@@ -130,7 +150,7 @@ impl Executor {
         let parker = Parker::new();
         let unparker = parker.unparker();
         let th = thread::spawn(move || {
-            let res = inner.run();
+            let res = inner.run(poll_budget);
             unparker.unpark();
 
             (inner, res)
@@ -160,26 +180,40 @@ struct ExecutorInner {
     simulation_context: SimulationContext,
     /// Signal requesting the worker thread to return as soon as possible.
     abort_signal: Signal,
+    /// Number of tasks run during the current or last call to `run`.
+    task_count: Arc<AtomicUsize>,
 }
 
 impl ExecutorInner {
-    fn run(&mut self) -> Result<(), ExecutorError> {
+    fn run(&mut self, poll_budget: usize) -> Result<(), ExecutorError> {
         // In case this executor is nested in another one, reset the counter of in-flight messages.
         let msg_count_stash = channel::THREAD_MSG_COUNT.replace(self.context.msg_count);
+        self.task_count.store(0, Ordering::Relaxed);
 
         let result = SIMULATION_CONTEXT.set(&self.simulation_context, || {
             ACTIVE_TASKS.set(&self.active_tasks, || {
                 EXECUTOR_CONTEXT.set(&self.context, || {
+                    let mut poll_count = 0;
+
+                    // Returns `true` if the poll budget was exhausted.
                     panic::catch_unwind(AssertUnwindSafe(|| loop {
                         let task = match self.context.queue.borrow_mut().pop() {
                             Some(task) => task,
-                            None => break,
+                            None => break false,
                         };
 
+                        if poll_count == poll_budget && poll_budget != 0 {
+                            self.context.queue.borrow_mut().push(task);
+
+                            return true;
+                        }
+                        poll_count += 1;
+                        self.task_count.fetch_add(1, Ordering::Relaxed);
+
                         task.run();
 
                         if self.abort_signal.is_set() {
-                            return;
+                            return false;
                         }
                     }))
                 })
@@ -187,14 +221,21 @@ impl ExecutorInner {
         });
 
         // Return the panic payload, if any.
-        if let Err(payload) = result {
-            let model_id = CURRENT_MODEL_ID.take();
+        let is_budget_exhausted = match result {
+            Ok(is_budget_exhausted) => is_budget_exhausted,
+            Err(payload) => {
+                let model_id = CURRENT_MODEL_ID.take();
+
+                return Err(ExecutorError::Panic(model_id, payload));
+            }
+        };
 
-            return Err(ExecutorError::Panic(model_id, payload));
+        self.context.msg_count = channel::THREAD_MSG_COUNT.replace(msg_count_stash);
+        if is_budget_exhausted {
+            return Err(ExecutorError::PollBudgetExceeded);
         }
 
         // Check for unprocessed messages.
-        self.context.msg_count = channel::THREAD_MSG_COUNT.replace(msg_count_stash);
         if self.context.msg_count != 0 {
             let msg_count: usize = self.context.msg_count.try_into().unwrap();
 
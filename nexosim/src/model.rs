@@ -194,6 +194,7 @@
 //! impl Model for ChildModel {}
 //!
 //! ```
+use std::fmt;
 use std::future::Future;
 
 pub use context::{BuildContext, Context};
@@ -247,6 +248,73 @@ pub trait Model: Sized + Send + 'static {
     fn init(self, _: &mut Context<Self>) -> impl Future<Output = InitializedModel<Self>> + Send {
         async { self.into() }
     }
+
+    /// Restores the model to the state it should have at the beginning of a
+    /// new episode.
+    ///
+    /// This asynchronous method is executed for all models of the simulation
+    /// when the [`Simulation::reset`](crate::simulation::Simulation::reset)
+    /// method is called, after all messages already in the mailbox of the
+    /// model have been processed. It makes it possible to re-run a simulation
+    /// bench without rebuilding its models and connections.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nexosim::model::{Context, Model};
+    ///
+    /// pub struct Counter {
+    ///     count: u64,
+    /// }
+    ///
+    /// impl Model for Counter {
+    ///     async fn reset(&mut self, _: &mut Context<Self>) {
+    ///         self.count = 0;
+    ///     }
+    /// }
+    /// ```
+    fn reset(&mut self, _: &mut Context<Self>) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Releases the resources held by the model before the simulation ends.
+    ///
+    /// This asynchronous method is executed once for each live model when the
+    /// simulation is shut down, either explicitly with
+    /// [`Simulation::shutdown`](crate::simulation::Simulation::shutdown) or
+    /// implicitly when the simulation is dropped, after all messages already in
+    /// the mailbox of the model have been processed. Models are terminated one
+    /// at a time, in the reverse order of their addition to the simulation.
+    ///
+    /// It is also executed when the model is removed from a running simulation
+    /// with
+    /// [`Simulation::remove_model`](crate::simulation::Simulation::remove_model).
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io::Write;
+    ///
+    /// use nexosim::model::{Context, Model};
+    ///
+    /// pub struct Logger {
+    ///     file: File,
+    /// }
+    ///
+    /// impl Model for Logger {
+    ///     async fn terminate(&mut self, _: &mut Context<Self>) {
+    ///         let _ = self.file.flush();
+    ///     }
+    /// }
+    /// ```
+    fn terminate(&mut self, _: &mut Context<Self>) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 }
 
 /// Opaque type containing an initialized model.
@@ -278,6 +346,23 @@ pub trait ProtoModel: Sized {
     /// Type of the model to be built.
     type Model: Model;
 
+    /// Checks the connections of the prototype before the model is built.
+    ///
+    /// This method is invoked just before [`ProtoModel::build`] and makes it
+    /// possible to report assembly mistakes, such as an output port that was
+    /// never connected (see e.g.
+    /// [`Output::is_connected`](crate::ports::Output::is_connected)) or a
+    /// mandatory input port that the model knows to be left dangling. The
+    /// warnings of all models are reported by
+    /// [`SimInit::init`](crate::simulation::SimInit::init) as an
+    /// [`ExecutionError::UnconnectedPorts`](crate::simulation::ExecutionError::UnconnectedPorts)
+    /// error before the simulation is initialized.
+    ///
+    /// The default implementation reports no warnings.
+    fn validate(&self) -> Result<(), Vec<ConnectionWarning>> {
+        Ok(())
+    }
+
     /// Builds the model.
     ///
     /// This method is invoked when the
@@ -294,3 +379,26 @@ impl<M: Model> ProtoModel for M {
         self
     }
 }
+
+/// A connection issue reported by [`ProtoModel::validate`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConnectionWarning {
+    /// The output or requestor port with the specified name has no
+    /// connection.
+    UnconnectedOutput(String),
+    /// The input or replier port with the specified name is mandatory but was
+    /// not connected.
+    UnconnectedInput(String),
+}
+
+impl fmt::Display for ConnectionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnconnectedOutput(port) => write!(f, "port '{}' has no connection", port),
+            Self::UnconnectedInput(port) => {
+                write!(f, "mandatory port '{}' is not connected", port)
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::executor::{Executor, Signal};
 use crate::ports::InputFn;
-use crate::simulation::{self, ActionKey, Address, GlobalScheduler, Mailbox, SchedulingError};
+use crate::simulation::{
+    self, ActionKey, Address, GlobalScheduler, Instrumentation, Mailbox, PendingModel,
+    SchedulingError,
+};
 use crate::time::{Deadline, MonotonicTime};
 
 use super::{Model, ProtoModel};
@@ -15,7 +18,7 @@ use crate::channel::Receiver;
 ///
 /// A `Context` is a handle to the global context associated to a model
 /// instance. It can be used by the model to retrieve the simulation time or
-/// schedule delayed actions on itself.
+/// schedule delayed actions on itself or on other models.
 ///
 /// ### Caveat: self-scheduling `async` methods
 ///
@@ -76,29 +79,46 @@ use crate::channel::Receiver;
 // The self-scheduling caveat seems related to this issue:
 // https://github.com/rust-lang/rust/issues/78649
 pub struct Context<M: Model> {
-    name: String,
+    name: Arc<str>,
     scheduler: GlobalScheduler,
     address: Address<M>,
     origin_id: usize,
+    instrumentation: Instrumentation,
 }
 
 impl<M: Model> Context<M> {
     /// Creates a new local context.
-    pub(crate) fn new(name: String, scheduler: GlobalScheduler, address: Address<M>) -> Self {
-        // The only requirement for the origin ID is that it must be (i)
-        // specific to each model and (ii) different from 0 (which is reserved
-        // for the global scheduler). The channel ID of the model mailbox
-        // fulfills this requirement.
-        let origin_id = address.0.channel_id();
-
+    ///
+    /// The origin ID must be (i) specific to each model and (ii) different from
+    /// 0, which is reserved for the global scheduler.
+    pub(crate) fn new(
+        name: String,
+        scheduler: GlobalScheduler,
+        address: Address<M>,
+        origin_id: usize,
+    ) -> Self {
         Self {
-            name,
+            name: name.into(),
             scheduler,
             address,
             origin_id,
+            instrumentation: Instrumentation::default(),
         }
     }
 
+    /// Sets the recorders of the processed events.
+    pub(crate) fn set_instrumentation(&mut self, instrumentation: Instrumentation) {
+        self.instrumentation = instrumentation;
+    }
+
+    /// Records the processing of an event or query with the type of the
+    /// argument, if event type counting, the event journal or the event log are
+    /// enabled.
+    pub(crate) fn record_event_type<T>(&self, _arg: &T) {
+        self.instrumentation
+            .record(&self.name, std::any::type_name::<T>(), || self.time());
+    }
+
     /// Returns the fully qualified model instance name.
     ///
     /// The fully qualified name is made of the unqualified model name, if
@@ -107,6 +127,13 @@ impl<M: Model> Context<M> {
         &self.name
     }
 
+    /// Returns a shared handle to the fully qualified model instance name.
+    ///
+    /// Unlike `name().to_string()`, this does not allocate.
+    pub(crate) fn shared_name(&self) -> Arc<str> {
+        self.name.clone()
+    }
+
     /// Returns the current simulation time.
     pub fn time(&self) -> MonotonicTime {
         self.scheduler.time()
@@ -151,7 +178,7 @@ impl<M: Model> Context<M> {
     ) -> Result<(), SchedulingError>
     where
         F: for<'a> InputFn<'a, M, T, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
         S: Send + 'static,
     {
         self.scheduler
@@ -208,7 +235,7 @@ impl<M: Model> Context<M> {
     ) -> Result<ActionKey, SchedulingError>
     where
         F: for<'a> InputFn<'a, M, T, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
         S: Send + 'static,
     {
         let event_key = self.scheduler.schedule_keyed_event_from(
@@ -354,6 +381,96 @@ impl<M: Model> Context<M> {
 
         Ok(event_key)
     }
+
+    /// Schedules an event at a future time on another model.
+    ///
+    /// An error is returned if the specified deadline is not in the future of
+    /// the current simulation time or if the mailbox of the target model was
+    /// dropped.
+    ///
+    /// Events scheduled by this model for the same time and targeting the same
+    /// model are guaranteed to be processed according to the scheduling order,
+    /// whether they were scheduled with this method or with
+    /// [`Context::schedule_event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::model::{Context, Model};
+    /// use nexosim::simulation::Address;
+    ///
+    /// // A lamp.
+    /// pub struct Lamp {}
+    ///
+    /// impl Lamp {
+    ///     // Switches the lamp off [input port].
+    ///     pub fn switch_off(&mut self) {
+    ///         println!("Lights out");
+    ///     }
+    /// }
+    ///
+    /// impl Model for Lamp {}
+    ///
+    /// // A timer that switches a lamp off after some delay.
+    /// pub struct LampTimer {
+    ///     lamp: Address<Lamp>,
+    /// }
+    ///
+    /// impl LampTimer {
+    ///     // Starts the timer [input port].
+    ///     pub fn start(&mut self, delay: Duration, cx: &mut Context<Self>) {
+    ///         if cx
+    ///             .schedule_event_on(delay, Lamp::switch_off, (), &self.lamp)
+    ///             .is_err()
+    ///         {
+    ///             println!("The timer delay must be strictly positive");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl Model for LampTimer {}
+    /// ```
+    pub fn schedule_event_on<N, F, T, S>(
+        &self,
+        deadline: impl Deadline,
+        func: F,
+        arg: T,
+        address: impl Into<Address<N>>,
+    ) -> Result<(), SchedulingError>
+    where
+        N: Model,
+        F: for<'a> InputFn<'a, N, T, S>,
+        T: Send + 'static,
+        S: Send + 'static,
+    {
+        self.scheduler
+            .schedule_event_from(deadline, func, arg, address, self.origin_id)
+    }
+
+    /// Schedules a cancellable event at a future time on another model and
+    /// returns an action key.
+    ///
+    /// An error is returned if the specified deadline is not in the future of
+    /// the current simulation time or if the mailbox of the target model was
+    /// dropped.
+    pub fn schedule_keyed_event_on<N, F, T, S>(
+        &self,
+        deadline: impl Deadline,
+        func: F,
+        arg: T,
+        address: impl Into<Address<N>>,
+    ) -> Result<ActionKey, SchedulingError>
+    where
+        N: Model,
+        F: for<'a> InputFn<'a, N, T, S>,
+        T: Send + 'static,
+        S: Send + 'static,
+    {
+        self.scheduler
+            .schedule_keyed_event_from(deadline, func, arg, address, self.origin_id)
+    }
 }
 
 impl<M: Model> fmt::Debug for Context<M> {
@@ -454,10 +571,7 @@ impl<M: Model> fmt::Debug for Context<M> {
 pub struct BuildContext<'a, P: ProtoModel> {
     mailbox: &'a Mailbox<P::Model>,
     name: &'a String,
-    scheduler: &'a GlobalScheduler,
-    executor: &'a Executor,
-    abort_signal: &'a Signal,
-    model_names: &'a mut Vec<String>,
+    pending_models: &'a mut Vec<PendingModel>,
 }
 
 impl<'a, P: ProtoModel> BuildContext<'a, P> {
@@ -465,18 +579,12 @@ impl<'a, P: ProtoModel> BuildContext<'a, P> {
     pub(crate) fn new(
         mailbox: &'a Mailbox<P::Model>,
         name: &'a String,
-        scheduler: &'a GlobalScheduler,
-        executor: &'a Executor,
-        abort_signal: &'a Signal,
-        model_names: &'a mut Vec<String>,
+        pending_models: &'a mut Vec<PendingModel>,
     ) -> Self {
         Self {
             mailbox,
             name,
-            scheduler,
-            executor,
-            abort_signal,
-            model_names,
+            pending_models,
         }
     }
 
@@ -513,15 +621,7 @@ impl<'a, P: ProtoModel> BuildContext<'a, P> {
         };
         submodel_name = self.name.to_string() + "." + &submodel_name;
 
-        simulation::add_model(
-            model,
-            mailbox,
-            submodel_name,
-            self.scheduler.clone(),
-            self.executor,
-            self.abort_signal,
-            self.model_names,
-        );
+        simulation::add_model(model, mailbox, submodel_name, self.pending_models);
     }
 }
 
@@ -534,6 +634,7 @@ impl<M: Model> Context<M> {
             String::new(),
             GlobalScheduler::new_dummy(),
             Address(dummy_address),
+            1,
         )
     }
 }
@@ -234,7 +234,10 @@
 //!
 //! Objects implementing the [`EventSink`] trait, such as [`EventSlot`] and
 //! [`EventBuffer`], are in turn similar to input ports. They can be connected
-//! to model outputs and collect events sent by such models.
+//! to model outputs and collect events sent by such models. Wrapping a sink in
+//! a [`Timestamped`] adapter additionally records the simulation time of each
+//! event, while a [`ChannelSink`] forwards events to a channel as they are
+//! sent.
 //!
 //!
 //! # Connections
@@ -271,8 +274,9 @@ mod source;
 
 pub use input::markers;
 pub use input::{InputFn, ReplierFn};
-pub use output::{Output, Requestor, UniRequestor};
+pub use output::{BackpressureMetrics, Output, Requestor, TapId, UniRequestor};
 pub use sink::{
-    event_buffer::EventBuffer, event_slot::EventSlot, EventSink, EventSinkStream, EventSinkWriter,
+    channel_sink::ChannelSink, event_buffer::EventBuffer, event_slot::EventSlot,
+    timestamped::Timestamped, EventSink, EventSinkStream, EventSinkWriter,
 };
 pub use source::{EventSource, QuerySource, ReplyReceiver};
@@ -2,6 +2,9 @@ mod broadcaster;
 mod sender;
 
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::model::Model;
 use crate::ports::EventSink;
@@ -10,12 +13,12 @@ use crate::simulation::Address;
 use crate::util::cached_rw_lock::CachedRwLock;
 use crate::util::unwrap_or_throw::UnwrapOrThrow;
 
-use broadcaster::{EventBroadcaster, QueryBroadcaster};
+use broadcaster::{EventBroadcaster, LineId, QueryBroadcaster};
 use sender::{FilterMapReplierSender, Sender};
 
 use self::sender::{
     EventSinkSender, FilterMapEventSinkSender, FilterMapInputSender, InputSender,
-    MapEventSinkSender, MapInputSender, MapReplierSender, ReplierSender,
+    MapEventSinkSender, MapInputSender, MapReplierSender, MeteredSender, ReplierSender,
 };
 
 /// An output port.
@@ -54,12 +57,40 @@ impl<T: Clone + Send + 'static> Output<T> {
         self.broadcaster.write().unwrap().add(sender);
     }
 
+    /// Adds a connection to an input port of the model specified by the
+    /// address and returns a handle to the back-pressure metrics of the
+    /// connection.
+    ///
+    /// This is equivalent to [`Output::connect`], except that the metrics
+    /// record each broadcast that had to wait for the mailbox of the model to
+    /// have free capacity, as well as the cumulative wall clock time spent
+    /// waiting.
+    pub fn connect_with_backpressure_metrics<M, F, S>(
+        &mut self,
+        input: F,
+        address: impl Into<Address<M>>,
+    ) -> BackpressureMetrics
+    where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S> + Clone,
+        S: Send + 'static,
+    {
+        let metrics = BackpressureMetrics::default();
+        let sender = Box::new(MeteredSender::new(
+            InputSender::new(input, address.into().0),
+            metrics.clone(),
+        ));
+        self.broadcaster.write().unwrap().add(sender);
+
+        metrics
+    }
+
     /// Adds a connection to an event sink such as an
     /// [`EventSlot`](crate::ports::EventSlot) or
     /// [`EventBuffer`](crate::ports::EventBuffer).
     pub fn connect_sink<S: EventSink<T>>(&mut self, sink: &S) {
         let sender = Box::new(EventSinkSender::new(sink.writer()));
-        self.broadcaster.write().unwrap().add(sender)
+        self.broadcaster.write().unwrap().add(sender);
     }
 
     /// Adds an auto-converting connection to an input port of the model
@@ -144,6 +175,35 @@ impl<T: Clone + Send + 'static> Output<T> {
         self.broadcaster.write().unwrap().add(sender);
     }
 
+    /// Adds a tap, i.e. a removable connection to an event sink, alongside the
+    /// existing connections.
+    ///
+    /// Since all clones of an `Output` share their connections, a tap can be
+    /// added to a clone kept outside the model to observe the events sent by
+    /// the model, including while the simulation is running. The tap can be
+    /// removed with [`Output::untap`] using the returned identifier.
+    pub fn tap<S: EventSink<T>>(&mut self, sink: &S) -> TapId {
+        let sender = Box::new(EventSinkSender::new(sink.writer()));
+
+        TapId(self.broadcaster.write().unwrap().add(sender))
+    }
+
+    /// Removes a tap previously added with [`Output::tap`].
+    ///
+    /// Returns `false` if the tap was already removed.
+    pub fn untap(&mut self, tap_id: TapId) -> bool {
+        self.broadcaster.write().unwrap().remove(tap_id.0)
+    }
+
+    /// Checks whether the port has at least one connection.
+    ///
+    /// See also [`ProtoModel::validate`](crate::model::ProtoModel::validate).
+    pub fn is_connected(&self) -> bool {
+        // The local cache may not reflect connections made from this or other
+        // clones, so the shared value is read instead.
+        self.broadcaster.read_shared().unwrap().len() != 0
+    }
+
     /// Broadcasts an event to all connected input ports.
     pub async fn send(&mut self, arg: T) {
         let broadcaster = self.broadcaster.write_scratchpad().unwrap();
@@ -169,6 +229,59 @@ impl<T: Clone + Send + 'static> fmt::Debug for Output<T> {
     }
 }
 
+/// Identifier of a tap added with [`Output::tap`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TapId(LineId);
+
+/// Back-pressure metrics of an output port connection.
+///
+/// A broadcast stalls whenever the mailbox of the connected model is full and
+/// the broadcast has to wait until the model processes some of its messages.
+/// A handle is returned by [`Output::connect_with_backpressure_metrics`]; all
+/// clones of the handle share the same metrics.
+#[derive(Clone, Default)]
+pub struct BackpressureMetrics {
+    inner: Arc<BackpressureMetricsInner>,
+}
+
+impl BackpressureMetrics {
+    /// Returns the number of broadcasts that stalled.
+    pub fn stall_count(&self) -> u64 {
+        self.inner.stall_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the cumulative wall clock time spent by stalled broadcasts
+    /// waiting for mailbox capacity.
+    pub fn total_stall_time(&self) -> Duration {
+        Duration::from_nanos(self.inner.total_stall_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Records a stalled broadcast.
+    fn record_stall(&self, stall_time: Duration) {
+        let nanos = u64::try_from(stall_time.as_nanos()).unwrap_or(u64::MAX);
+
+        self.inner.stall_count.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .total_stall_nanos
+            .fetch_add(nanos, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for BackpressureMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BackpressureMetrics")
+            .field("stall_count", &self.stall_count())
+            .field("total_stall_time", &self.total_stall_time())
+            .finish()
+    }
+}
+
+#[derive(Default)]
+struct BackpressureMetricsInner {
+    stall_count: AtomicU64,
+    total_stall_nanos: AtomicU64,
+}
+
 /// A requestor port.
 ///
 /// `Requestor` ports can be connected to replier ports, i.e. to asynchronous
@@ -273,6 +386,15 @@ impl<T: Clone + Send + 'static, R: Send + 'static> Requestor<T, R> {
         self.broadcaster.write().unwrap().add(sender);
     }
 
+    /// Checks whether the port has at least one connection.
+    ///
+    /// See also [`ProtoModel::validate`](crate::model::ProtoModel::validate).
+    pub fn is_connected(&self) -> bool {
+        // The local cache may not reflect connections made from this or other
+        // clones, so the shared value is read instead.
+        self.broadcaster.read_shared().unwrap().len() != 0
+    }
+
     /// Broadcasts a query to all connected replier ports.
     pub async fn send(&mut self, arg: T) -> impl Iterator<Item = R> + '_ {
         self.broadcaster
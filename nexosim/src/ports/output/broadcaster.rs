@@ -9,6 +9,10 @@ use super::sender::{RecycledFuture, Sender};
 use crate::channel::SendError;
 use crate::util::task_set::TaskSet;
 
+/// Unique identifier of a sender within a broadcaster.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) struct LineId(u64);
+
 /// An object that can efficiently broadcast messages to several addresses.
 ///
 /// This is very similar to `source::broadcaster::BroadcasterInner`, but
@@ -24,8 +28,12 @@ use crate::util::task_set::TaskSet;
 /// - the outputs of all sender futures are returned all at once rather than
 ///   with an asynchronous iterator (a.k.a. async stream).
 pub(super) struct BroadcasterInner<T: Clone, R> {
-    /// The list of senders with their associated line identifier.
+    /// The list of senders.
     senders: Vec<Box<dyn Sender<T, R>>>,
+    /// The line identifiers associated to the senders.
+    line_ids: Vec<LineId>,
+    /// The line identifier to be assigned to the next sender.
+    next_line_id: u64,
     /// Fields explicitly borrowed by the `BroadcastFuture`.
     shared: Shared<R>,
 }
@@ -38,15 +46,36 @@ impl<T: Clone, R> BroadcasterInner<T, R> {
     /// This method will panic if the total count of senders would reach
     /// `u32::MAX - 1` due to limitations inherent to the task set
     /// implementation.
-    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, R>>) {
+    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, R>>) -> LineId {
         assert!(self.senders.len() < (u32::MAX as usize - 2));
+        let line_id = LineId(self.next_line_id);
+        self.next_line_id += 1;
         self.senders.push(sender);
+        self.line_ids.push(line_id);
         self.shared.outputs.push(None);
 
         // The storage is alway an empty vector so we just book some capacity.
         if let Some(storage) = self.shared.storage.as_mut() {
             let _ = storage.try_reserve(self.senders.len());
         };
+
+        line_id
+    }
+
+    /// Removes the sender associated to the specified identifier.
+    ///
+    /// Returns `true` if a sender was removed.
+    pub(super) fn remove(&mut self, line_id: LineId) -> bool {
+        match self.line_ids.iter().position(|id| *id == line_id) {
+            Some(idx) => {
+                self.senders.remove(idx);
+                self.line_ids.remove(idx);
+                self.shared.outputs.pop();
+
+                true
+            }
+            None => false,
+        }
     }
 
     /// Returns the number of connected senders.
@@ -94,6 +123,8 @@ impl<T: Clone, R> Default for BroadcasterInner<T, R> {
 
         Self {
             senders: Vec::new(),
+            line_ids: Vec::new(),
+            next_line_id: 0,
             shared: Shared {
                 wake_sink,
                 task_set: TaskSet::new(wake_src),
@@ -108,6 +139,8 @@ impl<T: Clone, R> Clone for BroadcasterInner<T, R> {
     fn clone(&self) -> Self {
         Self {
             senders: self.senders.clone(),
+            line_ids: self.line_ids.clone(),
+            next_line_id: self.next_line_id,
             shared: self.shared.clone(),
         }
     }
@@ -133,10 +166,17 @@ impl<T: Clone> EventBroadcaster<T> {
     /// This method will panic if the total count of senders would reach
     /// `u32::MAX - 1` due to limitations inherent to the task set
     /// implementation.
-    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, ()>>) {
+    pub(super) fn add(&mut self, sender: Box<dyn Sender<T, ()>>) -> LineId {
         self.inner.add(sender)
     }
 
+    /// Removes the sender associated to the specified identifier.
+    ///
+    /// Returns `true` if a sender was removed.
+    pub(super) fn remove(&mut self, line_id: LineId) -> bool {
+        self.inner.remove(line_id)
+    }
+
     /// Returns the number of connected senders.
     pub(super) fn len(&self) -> usize {
         self.inner.len()
@@ -195,7 +235,7 @@ impl<T: Clone, R> QueryBroadcaster<T, R> {
     /// `u32::MAX - 1` due to limitations inherent to the task set
     /// implementation.
     pub(super) fn add(&mut self, sender: Box<dyn Sender<T, R>>) {
-        self.inner.add(sender)
+        self.inner.add(sender);
     }
 
     /// Returns the number of connected senders.
@@ -4,8 +4,10 @@ use std::mem::ManuallyDrop;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Instant;
 
 use dyn_clone::DynClone;
+use pin_project::pin_project;
 use recycle_box::{coerce_box, RecycleBox};
 
 use crate::channel;
@@ -13,6 +15,8 @@ use crate::channel::SendError;
 use crate::model::Model;
 use crate::ports::{EventSinkWriter, InputFn, ReplierFn};
 
+use super::BackpressureMetrics;
+
 /// An event or query sender abstracting over the target model and input or
 /// replier method.
 pub(super) trait Sender<T, R>: DynClone + Send {
@@ -69,6 +73,7 @@ where
         let func = self.func.clone();
 
         let fut = self.sender.send(move |model, scheduler, recycle_box| {
+            scheduler.record_event_type(&arg);
             let fut = func.call(model, arg, scheduler);
 
             coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -139,6 +144,7 @@ where
         let arg = (self.map)(arg);
 
         let fut = self.sender.send(move |model, scheduler, recycle_box| {
+            scheduler.record_event_type(&arg);
             let fut = func.call(model, arg, scheduler);
 
             coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -211,6 +217,7 @@ where
             let func = self.func.clone();
 
             let fut = self.sender.send(move |model, scheduler, recycle_box| {
+                scheduler.record_event_type(&arg);
                 let fut = func.call(model, arg, scheduler);
 
                 coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -458,6 +465,7 @@ where
 
         let send_fut = sender.send(move |model, scheduler, recycle_box| {
             let fut = async move {
+                scheduler.record_event_type(&arg);
                 let reply = func.call(model, arg, scheduler).await;
                 reply_sender.send(reply);
             };
@@ -558,6 +566,7 @@ where
 
         let send_fut = sender.send(move |model, scheduler, recycle_box| {
             let fut = async move {
+                scheduler.record_event_type(&arg);
                 let reply = func.call(model, arg, scheduler).await;
                 reply_sender.send(reply);
             };
@@ -671,6 +680,7 @@ where
 
             let send_fut = sender.send(move |model, scheduler, recycle_box| {
                 let fut = async move {
+                    scheduler.record_event_type(&arg);
                     let reply = func.call(model, arg, scheduler).await;
                     reply_sender.send(reply);
                 };
@@ -716,6 +726,91 @@ where
     }
 }
 
+/// An object that forwards events to another sender and records the time spent
+/// waiting for mailbox capacity.
+#[derive(Clone)]
+pub(super) struct MeteredSender<S> {
+    inner: S,
+    metrics: BackpressureMetrics,
+    fut_storage: Option<RecycleBox<()>>,
+}
+
+impl<S> MeteredSender<S> {
+    pub(super) fn new(inner: S, metrics: BackpressureMetrics) -> Self {
+        Self {
+            inner,
+            metrics,
+            fut_storage: None,
+        }
+    }
+}
+
+impl<S, T> Sender<T, ()> for MeteredSender<S>
+where
+    S: Sender<T, ()> + Clone,
+{
+    fn send(&mut self, arg: &T) -> Option<RecycledFuture<'_, Result<(), SendError>>> {
+        let fut = self.inner.send(arg)?;
+
+        Some(RecycledFuture::new(
+            &mut self.fut_storage,
+            StallMeter::new(fut, &self.metrics),
+        ))
+    }
+
+    fn send_owned(&mut self, arg: T) -> Option<RecycledFuture<'_, Result<(), SendError>>> {
+        let fut = self.inner.send_owned(arg)?;
+
+        Some(RecycledFuture::new(
+            &mut self.fut_storage,
+            StallMeter::new(fut, &self.metrics),
+        ))
+    }
+}
+
+/// A future that records in the provided metrics whether and for how long the
+/// wrapped future was pending.
+#[pin_project]
+struct StallMeter<'a, F> {
+    #[pin]
+    fut: F,
+    metrics: &'a BackpressureMetrics,
+    stall_start: Option<Instant>,
+}
+
+impl<'a, F> StallMeter<'a, F> {
+    fn new(fut: F, metrics: &'a BackpressureMetrics) -> Self {
+        Self {
+            fut,
+            metrics,
+            stall_start: None,
+        }
+    }
+}
+
+impl<F: Future> Future for StallMeter<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        match this.fut.poll(cx) {
+            Poll::Ready(output) => {
+                if let Some(stall_start) = this.stall_start.take() {
+                    this.metrics.record_stall(stall_start.elapsed());
+                }
+
+                Poll::Ready(output)
+            }
+            Poll::Pending => {
+                this.stall_start.get_or_insert_with(Instant::now);
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
 pub(super) struct RecycledFuture<'a, T> {
     fut: ManuallyDrop<Pin<RecycleBox<dyn Future<Output = T> + Send + 'a>>>,
     lender_box: &'a mut Option<RecycleBox<()>>,
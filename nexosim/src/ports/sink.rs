@@ -1,5 +1,7 @@
+pub(crate) mod channel_sink;
 pub(crate) mod event_buffer;
 pub(crate) mod event_slot;
+pub(crate) mod timestamped;
 
 /// A simulation endpoint that can receive events sent by model outputs.
 ///
@@ -33,6 +35,29 @@ pub trait EventSinkStream: Iterator {
     /// Events that were previously in the stream remain available.
     fn close(&mut self);
 
+    /// Reports whether the collection of new events is enabled.
+    ///
+    /// The default implementation always returns `true` and should be
+    /// overridden by streams for which [`close`](EventSinkStream::close)
+    /// actually pauses the collection of events.
+    fn is_open(&self) -> bool {
+        true
+    }
+
+    /// Returns the number of events currently available in the stream.
+    ///
+    /// The default implementation returns the lower bound of
+    /// [`Iterator::size_hint`] and should be overridden by streams that do not
+    /// provide an exact size hint.
+    fn len(&self) -> usize {
+        self.size_hint().0
+    }
+
+    /// Reports whether no event is currently available in the stream.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// This is a stop-gap method that serves the exact same purpose as
     /// `Iterator::try_fold` but is specialized for `Result` rather than the
     /// `Try` trait so it can be implemented on stable Rust.
@@ -0,0 +1,98 @@
+use std::fmt;
+use std::sync::mpsc::Sender;
+
+use super::{EventSink, EventSinkWriter};
+
+/// An [`EventSink`] that forwards events to the sending side of a standard
+/// library channel.
+///
+/// Unlike [`EventBuffer`](crate::ports::EventBuffer) and
+/// [`EventSlot`](crate::ports::EventSlot), a `ChannelSink` does not store
+/// events: each event is sent to the channel as soon as it is written, which
+/// makes it possible to consume events from another thread, for instance a
+/// user interface thread, while the simulation runs. Events are silently
+/// dropped once the receiving side of the channel is disconnected.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::mpsc;
+///
+/// use nexosim::model::Model;
+/// use nexosim::ports::{ChannelSink, Output};
+/// use nexosim::simulation::{Mailbox, SimInit};
+/// use nexosim::time::MonotonicTime;
+///
+/// #[derive(Default)]
+/// struct MyModel {
+///     output: Output<u32>,
+/// }
+/// impl MyModel {
+///     async fn input(&mut self, value: u32) {
+///         self.output.send(value).await;
+///     }
+/// }
+/// impl Model for MyModel {}
+///
+/// let mut model = MyModel::default();
+/// let (sender, receiver) = mpsc::channel();
+/// model.output.connect_sink(&ChannelSink::new(sender));
+///
+/// let mailbox = Mailbox::new();
+/// let address = mailbox.address();
+/// let mut simu = SimInit::new()
+///     .add_model(model, mailbox, "model")
+///     .init(MonotonicTime::EPOCH)
+///     .unwrap()
+///     .0;
+///
+/// simu.process_event(MyModel::input, 42, &address).unwrap();
+/// assert_eq!(receiver.try_recv(), Ok(42));
+/// ```
+pub struct ChannelSink<T> {
+    sender: Sender<T>,
+}
+
+impl<T> ChannelSink<T> {
+    /// Creates a sink that forwards events to the specified channel sender.
+    pub fn new(sender: Sender<T>) -> Self {
+        Self { sender }
+    }
+}
+
+impl<T: Send + 'static> EventSink<T> for ChannelSink<T> {
+    type Writer = Self;
+
+    /// Returns a writer handle.
+    fn writer(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl<T: Send + 'static> EventSinkWriter<T> for ChannelSink<T> {
+    /// Sends an event to the channel.
+    fn write(&self, event: T) {
+        // Ignore if the receiver is disconnected.
+        let _ = self.sender.send(event);
+    }
+}
+
+impl<T> From<Sender<T>> for ChannelSink<T> {
+    fn from(sender: Sender<T>) -> Self {
+        Self::new(sender)
+    }
+}
+
+impl<T> Clone for ChannelSink<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for ChannelSink<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ChannelSink").finish_non_exhaustive()
+    }
+}
@@ -1,7 +1,8 @@
 use std::collections::VecDeque;
 use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
 
 use super::{EventSink, EventSinkStream, EventSinkWriter};
 
@@ -10,6 +11,7 @@ struct Inner<T> {
     capacity: usize,
     is_open: AtomicBool,
     buffer: Mutex<VecDeque<T>>,
+    not_empty: Condvar,
 }
 
 /// An iterator implementing [`EventSink`] and [`EventSinkStream`], backed by a
@@ -44,6 +46,7 @@ impl<T> EventBuffer<T> {
                 capacity,
                 is_open: AtomicBool::new(true),
                 buffer: Mutex::new(VecDeque::new()),
+                not_empty: Condvar::new(),
             }),
         }
     }
@@ -55,9 +58,41 @@ impl<T> EventBuffer<T> {
                 capacity,
                 is_open: AtomicBool::new(false),
                 buffer: Mutex::new(VecDeque::new()),
+                not_empty: Condvar::new(),
             }),
         }
     }
+
+    /// Returns the next event, if necessary blocking the current thread until
+    /// an event is received or the timeout elapses.
+    ///
+    /// This is mainly useful when the buffer is written from another thread,
+    /// as is the case when a simulation paced by a real-time clock runs in the
+    /// background. `None` is returned if no event was received before the
+    /// timeout.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut buffer = self.inner.buffer.lock().unwrap();
+
+        loop {
+            if let Some(event) = buffer.pop_front() {
+                return Some(event);
+            }
+
+            buffer = match deadline {
+                Some(deadline) => {
+                    let timeout = deadline.checked_duration_since(Instant::now())?;
+                    self.inner
+                        .not_empty
+                        .wait_timeout(buffer, timeout)
+                        .unwrap()
+                        .0
+                }
+                // The deadline cannot be represented so wait indefinitely.
+                None => self.inner.not_empty.wait(buffer).unwrap(),
+            };
+        }
+    }
 }
 
 impl<T: Send + 'static> EventSink<T> for EventBuffer<T> {
@@ -87,6 +122,14 @@ impl<T: Send + 'static> EventSinkStream for EventBuffer<T> {
         self.inner.is_open.store(false, Ordering::Relaxed);
     }
 
+    fn is_open(&self) -> bool {
+        self.inner.is_open.load(Ordering::Relaxed)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.buffer.lock().unwrap().len()
+    }
+
     #[doc(hidden)]
     #[allow(private_interfaces)]
     fn __try_fold<B, F, E>(&mut self, init: B, f: F) -> Result<B, E>
@@ -131,6 +174,9 @@ impl<T: Send + 'static> EventSinkWriter<T> for EventBufferWriter<T> {
         }
 
         buffer.push_back(event);
+        drop(buffer);
+
+        self.inner.not_empty.notify_one();
     }
 }
 
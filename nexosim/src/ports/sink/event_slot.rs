@@ -72,6 +72,12 @@ impl<T: Send + 'static> EventSinkStream for EventSlot<T> {
     fn close(&mut self) {
         self.inner.is_open.store(false, Ordering::Relaxed);
     }
+    fn is_open(&self) -> bool {
+        self.inner.is_open.load(Ordering::Relaxed)
+    }
+    fn len(&self) -> usize {
+        self.inner.slot.lock().unwrap().is_some() as usize
+    }
 }
 
 impl<T> Default for EventSlot<T> {
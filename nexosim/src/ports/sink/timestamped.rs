@@ -0,0 +1,86 @@
+use std::fmt;
+
+use crate::executor::SIMULATION_CONTEXT;
+use crate::time::MonotonicTime;
+
+use super::{EventSink, EventSinkWriter};
+
+/// An [`EventSink`] adapter that pairs each event with the simulation time at
+/// which it was sent.
+///
+/// A `Timestamped` adapter is constructed from an event sink for items of type
+/// `(MonotonicTime, T)` and can itself be connected to an output for events of
+/// type `T`. Events written to the adapter are forwarded to the wrapped sink
+/// together with the current simulation time.
+///
+/// # Example
+///
+/// ```
+/// use nexosim::model::Model;
+/// use nexosim::ports::{EventBuffer, Output, Timestamped};
+/// use nexosim::time::MonotonicTime;
+///
+/// struct MyModel {
+///     output: Output<u32>,
+/// }
+/// impl Model for MyModel {}
+///
+/// let mut model = MyModel { output: Output::default() };
+///
+/// let buffer = EventBuffer::<(MonotonicTime, u32)>::new();
+/// model.output.connect_sink(&Timestamped::new(&buffer));
+/// ```
+pub struct Timestamped<W> {
+    writer: W,
+}
+
+impl<W> Timestamped<W> {
+    /// Creates an adapter that forwards timestamped events to the specified
+    /// sink.
+    pub fn new<T, S>(sink: &S) -> Self
+    where
+        S: EventSink<(MonotonicTime, T), Writer = W>,
+    {
+        Self {
+            writer: sink.writer(),
+        }
+    }
+}
+
+impl<T, W: EventSinkWriter<(MonotonicTime, T)>> EventSink<T> for Timestamped<W> {
+    type Writer = Self;
+
+    /// Returns a writer handle.
+    fn writer(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl<T, W: EventSinkWriter<(MonotonicTime, T)>> EventSinkWriter<T> for Timestamped<W> {
+    /// Writes an event paired with the current simulation time.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if called from outside a simulation.
+    fn write(&self, event: T) {
+        let time = SIMULATION_CONTEXT
+            .map(|cx| cx.time_reader.try_read().unwrap())
+            .expect("timestamped events can only be written by simulation models");
+
+        self.writer.write((time, event));
+    }
+}
+
+impl<W: Clone> Clone for Timestamped<W> {
+    fn clone(&self) -> Self {
+        Self {
+            writer: self.writer.clone(),
+        }
+    }
+}
+
+impl<W> fmt::Debug for Timestamped<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Timestamped").finish_non_exhaustive()
+    }
+}
@@ -16,7 +16,7 @@ use crate::util::unwrap_or_throw::UnwrapOrThrow;
 use broadcaster::{EventBroadcaster, QueryBroadcaster, ReplyIterator};
 use sender::{
     FilterMapInputSender, FilterMapReplierSender, InputSender, MapInputSender, MapReplierSender,
-    ReplierSender,
+    NamedReply, ReplierSender,
 };
 
 use super::ReplierFn;
@@ -99,6 +99,14 @@ impl<T: Clone + Send + 'static> EventSource<T> {
         self.broadcaster.add(sender);
     }
 
+    /// Moves all connections of another event source to this event source.
+    ///
+    /// Once merged, an event sent by this event source is broadcast to the
+    /// input ports connected to either source.
+    pub fn merge(&mut self, other: EventSource<T>) {
+        self.broadcaster.append(other.broadcaster);
+    }
+
     /// Returns an action which, when processed, broadcasts an event to all
     /// connected input ports.
     pub fn event(&self, arg: T) -> Action {
@@ -196,7 +204,7 @@ impl<T: Clone + Send + 'static> fmt::Debug for EventSource<T> {
 /// instantiated as a member of a model, but rather as a simulation monitoring
 /// endpoint instantiated during bench assembly.
 pub struct QuerySource<T: Clone + Send + 'static, R: Send + 'static> {
-    broadcaster: QueryBroadcaster<T, R>,
+    broadcaster: QueryBroadcaster<T, NamedReply<R>>,
 }
 
 impl<T: Clone + Send + 'static, R: Send + 'static> QuerySource<T, R> {
@@ -324,14 +332,33 @@ impl<T: Clone + Send + 'static, R: Send + 'static> fmt::Debug for QuerySource<T,
 }
 
 /// A receiver for all replies collected from a single query broadcast.
-pub struct ReplyReceiver<R>(slot::SlotReader<ReplyIterator<R>>);
+pub struct ReplyReceiver<R>(slot::SlotReader<ReplyIterator<NamedReply<R>>>);
 
 impl<R> ReplyReceiver<R> {
     /// Returns all replies to a query.
     ///
     /// Returns `None` if the replies are not yet available or if they were
-    /// already taken in a previous call to `take`.
+    /// already taken in a previous call to `take` or `take_with_names`.
     pub fn take(&mut self) -> Option<impl Iterator<Item = R>> {
+        self.0
+            .try_read()
+            .ok()
+            .map(|replies| replies.map(|(_, reply)| reply))
+    }
+
+    /// Returns all replies to a query, each paired with the fully qualified
+    /// name of the model that produced it.
+    ///
+    /// Returns `None` if the replies are not yet available or if they were
+    /// already taken in a previous call to `take` or `take_with_names`.
+    pub fn take_with_names(&mut self) -> Option<impl Iterator<Item = (String, R)>> {
+        self.take_with_shared_names()
+            .map(|replies| replies.map(|(name, reply)| (name.to_string(), reply)))
+    }
+
+    /// Returns all replies to a query, each paired with a shared handle to the
+    /// fully qualified name of the model that produced it.
+    pub(crate) fn take_with_shared_names(&mut self) -> Option<impl Iterator<Item = (Arc<str>, R)>> {
         self.0.try_read().ok()
     }
 }
@@ -41,6 +41,18 @@ impl<T: Clone, R> BroadcasterInner<T, R> {
         self.senders.push(sender);
     }
 
+    /// Moves all senders of another broadcaster to this broadcaster.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the total count of senders would reach
+    /// `u32::MAX - 1` due to limitations inherent to the task set
+    /// implementation.
+    pub(super) fn append(&mut self, other: Self) {
+        assert!(self.senders.len() + other.senders.len() < (u32::MAX as usize - 1));
+        self.senders.extend(other.senders);
+    }
+
     /// Returns the number of connected senders.
     pub(super) fn len(&self) -> usize {
         self.senders.len()
@@ -101,6 +113,17 @@ impl<T: Clone + Send> EventBroadcaster<T> {
         self.inner.add(sender);
     }
 
+    /// Moves all senders of another broadcaster to this broadcaster.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the total count of senders would reach
+    /// `u32::MAX - 1` due to limitations inherent to the task set
+    /// implementation.
+    pub(super) fn append(&mut self, other: Self) {
+        self.inner.append(other.inner);
+    }
+
     /// Returns the number of connected senders.
     pub(super) fn len(&self) -> usize {
         self.inner.len()
@@ -567,7 +590,7 @@ mod tests {
 
         let th_broadcast = thread::spawn(move || {
             let iter = block_on(broadcaster.broadcast(MESSAGE)).unwrap();
-            let sum = iter.fold(0, |acc, val| acc + val);
+            let sum = iter.fold(0, |acc, (_, val)| acc + val);
 
             sum
         });
@@ -626,7 +649,7 @@ mod tests {
                         .broadcast(id)
                         .await
                         .unwrap()
-                        .fold(0, |acc, val| acc + val);
+                        .fold(0, |acc, (_, val)| acc + val);
                 }
 
                 // Broadcast the special value to all receivers.
@@ -634,7 +657,7 @@ mod tests {
                     .broadcast(BROADCAST_ALL)
                     .await
                     .unwrap()
-                    .fold(0, |acc, val| acc + val);
+                    .fold(0, |acc, (_, val)| acc + val);
 
                 // Send again messages reaching only one receiver each.
                 for id in 0..N_RECV {
@@ -642,7 +665,7 @@ mod tests {
                         .broadcast(id)
                         .await
                         .unwrap()
-                        .fold(0, |acc, val| acc + val);
+                        .fold(0, |acc, (_, val)| acc + val);
                 }
 
                 sum
@@ -13,6 +13,12 @@ use crate::ports::{InputFn, ReplierFn};
 
 pub(super) type SenderFuture<R> = Pin<Box<dyn Future<Output = Result<R, SendError>> + Send>>;
 
+/// A reply tagged with the fully qualified name of the replying model.
+///
+/// The name is shared with the model context so that tagging a reply does not
+/// allocate; it is only turned into a `String` if the names are requested.
+pub(super) type NamedReply<R> = (Arc<str>, R);
+
 /// An event or query sender abstracting over the target model and input method.
 pub(super) trait Sender<T, R>: Send + Sync {
     /// Asynchronously sends a message using a reference to the message.
@@ -67,6 +73,7 @@ where
         Some(Box::pin(async move {
             sender
                 .send(move |model, scheduler, recycle_box| {
+                    scheduler.record_event_type(&arg);
                     let fut = func.call(model, arg, scheduler);
 
                     coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -122,6 +129,7 @@ where
         Some(Box::pin(async move {
             sender
                 .send(move |model, scheduler, recycle_box| {
+                    scheduler.record_event_type(&arg);
                     let fut = func.call(model, arg, scheduler);
 
                     coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -177,6 +185,7 @@ where
             Box::pin(async move {
                 sender
                     .send(move |model, scheduler, recycle_box| {
+                        scheduler.record_event_type(&arg);
                         let fut = func.call(model, arg, scheduler);
 
                         coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -212,7 +221,7 @@ where
     }
 }
 
-impl<M, F, T, R, S> Sender<T, R> for ReplierSender<M, F, T, R, S>
+impl<M, F, T, R, S> Sender<T, NamedReply<R>> for ReplierSender<M, F, T, R, S>
 where
     M: Model,
     F: for<'a> ReplierFn<'a, M, T, R, S> + Clone + Sync,
@@ -220,11 +229,11 @@ where
     R: Send + 'static,
     S: Send + Sync,
 {
-    fn send(&self, arg: &T) -> Option<SenderFuture<R>> {
+    fn send(&self, arg: &T) -> Option<SenderFuture<NamedReply<R>>> {
         self.send_owned(arg.clone())
     }
 
-    fn send_owned(&self, arg: T) -> Option<SenderFuture<R>> {
+    fn send_owned(&self, arg: T) -> Option<SenderFuture<NamedReply<R>>> {
         let func = self.func.clone();
         let sender = self.sender.clone();
         let (reply_sender, reply_receiver) = oneshot::channel();
@@ -233,8 +242,10 @@ where
             sender
                 .send(move |model, scheduler, recycle_box| {
                     let fut = async move {
+                        let name = scheduler.shared_name();
+                        scheduler.record_event_type(&arg);
                         let reply = func.call(model, arg, scheduler).await;
-                        let _ = reply_sender.send(reply);
+                        let _ = reply_sender.send((name, reply));
                     };
 
                     coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -280,7 +291,8 @@ where
     }
 }
 
-impl<M, C, D, F, T, R, U, Q, S> Sender<T, R> for MapReplierSender<M, C, D, F, T, R, U, Q, S>
+impl<M, C, D, F, T, R, U, Q, S> Sender<T, NamedReply<R>>
+    for MapReplierSender<M, C, D, F, T, R, U, Q, S>
 where
     M: Model,
     C: Fn(&T) -> U + Send + Sync,
@@ -292,7 +304,7 @@ where
     Q: Send + 'static,
     S: Send + Sync,
 {
-    fn send(&self, arg: &T) -> Option<SenderFuture<R>> {
+    fn send(&self, arg: &T) -> Option<SenderFuture<NamedReply<R>>> {
         let func = self.func.clone();
         let arg = (self.query_map)(arg);
         let sender = self.sender.clone();
@@ -303,15 +315,20 @@ where
             sender
                 .send(move |model, scheduler, recycle_box| {
                     let fut = async move {
+                        let name = scheduler.shared_name();
+                        scheduler.record_event_type(&arg);
                         let reply = func.call(model, arg, scheduler).await;
-                        let _ = reply_sender.send(reply);
+                        let _ = reply_sender.send((name, reply));
                     };
 
                     coerce_box!(RecycleBox::recycle(recycle_box, fut))
                 })
                 .await?;
 
-            reply_receiver.await.map_err(|_| SendError).map(&*reply_map)
+            reply_receiver
+                .await
+                .map_err(|_| SendError)
+                .map(|(name, reply)| (name, reply_map(reply)))
         }))
     }
 }
@@ -355,7 +372,8 @@ where
     }
 }
 
-impl<M, C, D, F, T, R, U, Q, S> Sender<T, R> for FilterMapReplierSender<M, C, D, F, T, R, U, Q, S>
+impl<M, C, D, F, T, R, U, Q, S> Sender<T, NamedReply<R>>
+    for FilterMapReplierSender<M, C, D, F, T, R, U, Q, S>
 where
     M: Model,
     C: Fn(&T) -> Option<U> + Send + Sync,
@@ -367,7 +385,7 @@ where
     Q: Send + 'static,
     S: Send + Sync,
 {
-    fn send(&self, arg: &T) -> Option<SenderFuture<R>> {
+    fn send(&self, arg: &T) -> Option<SenderFuture<NamedReply<R>>> {
         (self.query_filter_map)(arg).map(|arg| {
             let func = self.func.clone();
             let sender = self.sender.clone();
@@ -378,16 +396,21 @@ where
                 sender
                     .send(move |model, scheduler, recycle_box| {
                         let fut = async move {
+                            let name = scheduler.shared_name();
+                            scheduler.record_event_type(&arg);
                             let reply = func.call(model, arg, scheduler).await;
-                            let _ = reply_sender.send(reply);
+                            let _ = reply_sender.send((name, reply));
                         };
 
                         coerce_box!(RecycleBox::recycle(recycle_box, fut))
                     })
                     .await?;
 
-                reply_receiver.await.map_err(|_| SendError).map(&*reply_map)
-            }) as SenderFuture<R>
+                reply_receiver
+                    .await
+                    .map_err(|_| SendError)
+                    .map(|(name, reply)| (name, reply_map(reply)))
+            }) as SenderFuture<NamedReply<R>>
         })
     }
 }
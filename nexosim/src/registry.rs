@@ -2,16 +2,28 @@
 //!
 //! This module provides the `EndpointRegistry` object which associates each
 //! event sink, event source and query source in a simulation bench to a unique
-//! name.
+//! name, as well as the `ActionKeyRegistry` which makes action keys usable both
+//! in-process and by remote clients.
 
+mod action_key_registry;
+mod codec;
 mod event_sink_registry;
 mod event_source_registry;
 mod query_source_registry;
 
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
 use serde::{de::DeserializeOwned, ser::Serialize};
 
 use crate::ports::{EventSinkStream, EventSource, QuerySource};
+use crate::simulation::{ExecutionError, Scheduler, SchedulingError, Simulation};
+use crate::time::MonotonicTime;
 
+pub use action_key_registry::{ActionKeyRegistry, EventKey};
+pub use codec::Codec;
 pub(crate) use event_sink_registry::EventSinkRegistry;
 pub(crate) use event_source_registry::EventSourceRegistry;
 pub(crate) use query_source_registry::QuerySourceRegistry;
@@ -22,6 +34,8 @@ pub struct EndpointRegistry {
     pub(crate) event_sink_registry: EventSinkRegistry,
     pub(crate) event_source_registry: EventSourceRegistry,
     pub(crate) query_source_registry: QuerySourceRegistry,
+    pub(crate) action_key_registry: ActionKeyRegistry,
+    pub(crate) max_scheduling_horizon: Option<Duration>,
 }
 
 impl EndpointRegistry {
@@ -45,6 +59,27 @@ impl EndpointRegistry {
         self.event_source_registry.add(source, name)
     }
 
+    /// Adds a group of event sources to the registry under a single name.
+    ///
+    /// This makes it possible to expose several event sources, typically
+    /// connected to different models, as a single endpoint: an event scheduled
+    /// or processed through this endpoint is broadcast to the input ports
+    /// connected to all sources of the group. All sources must share the same
+    /// event type.
+    ///
+    /// If the specified name is already in use for another event source, the
+    /// sources provided as argument are returned in the error.
+    pub fn add_multi_event_source<T>(
+        &mut self,
+        sources: Vec<EventSource<T>>,
+        name: impl Into<String>,
+    ) -> Result<(), Vec<EventSource<T>>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+    {
+        self.event_source_registry.add_multi(sources, name)
+    }
+
     /// Adds a query source to the registry.
     ///
     /// If the specified name is already in use for another query source, the
@@ -72,4 +107,244 @@ impl EndpointRegistry {
     {
         self.event_sink_registry.add(sink, name)
     }
+
+    /// Sets the maximum scheduling horizon of remotely-scheduled events.
+    ///
+    /// Events scheduled with the `ScheduleEvent` remote procedure call for a
+    /// deadline beyond the current simulation time plus the specified horizon
+    /// are rejected with a `HORIZON_EXCEEDED` error. The horizon is unlimited
+    /// by default.
+    pub fn set_max_scheduling_horizon(&mut self, horizon: Duration) {
+        self.max_scheduling_horizon = Some(horizon);
+    }
+
+    /// Returns a handle to the registry of action keys.
+    ///
+    /// The returned handle refers to the same registry as the one used by the
+    /// server to store the keys of remotely-scheduled events, and can be
+    /// stored for later use by in-process code.
+    pub fn action_key_registry(&self) -> ActionKeyRegistry {
+        self.action_key_registry.clone()
+    }
+
+    /// Returns the registered event source with the specified name and event
+    /// type.
+    ///
+    /// This gives in-process code access to the typed source, so that actions
+    /// can be built directly from event values rather than from their
+    /// serialized form, which saves an encoding and decoding round trip.
+    /// Event sources registered with
+    /// [`add_multi_event_source`](EndpointRegistry::add_multi_event_source)
+    /// are returned as a single source broadcasting to all ports of the
+    /// group.
+    ///
+    /// `None` is returned if no event source is registered under this name or
+    /// if its event type is not `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::ports::EventSource;
+    /// use nexosim::registry::EndpointRegistry;
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// struct Valve {}
+    /// impl Valve {
+    ///     async fn set_opening(&mut self, _opening: f64) {}
+    /// }
+    /// impl Model for Valve {}
+    ///
+    /// let mailbox = Mailbox::new();
+    /// let mut opening = EventSource::new();
+    /// opening.connect(Valve::set_opening, &mailbox);
+    ///
+    /// let mut registry = EndpointRegistry::new();
+    /// registry.add_event_source(opening, "opening").unwrap();
+    ///
+    /// let mut simu = SimInit::new()
+    ///     .add_model(Valve {}, mailbox, "valve")
+    ///     .init(MonotonicTime::EPOCH)
+    ///     .unwrap()
+    ///     .0;
+    ///
+    /// let source = registry.event_source::<f64>("opening").unwrap();
+    /// simu.process(source.event(0.5)).unwrap();
+    /// assert!(registry.event_source::<f32>("opening").is_none());
+    /// ```
+    pub fn event_source<T>(&self, name: &str) -> Option<&Arc<EventSource<T>>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+    {
+        self.event_source_registry
+            .get(name)?
+            .as_any()
+            .downcast_ref::<Arc<EventSource<T>>>()
+    }
+
+    /// Schedules a sequence of timestamped events with a registered event
+    /// source.
+    ///
+    /// This is the in-process counterpart to the `ScheduleEvent` remote
+    /// procedure call, meant for instance to feed a simulation bench with
+    /// stimuli parsed from a data file. Each record is scheduled at its time
+    /// stamp with [`Scheduler::schedule`], so that records with the same time
+    /// stamp are processed in iteration order.
+    ///
+    /// Records must be sorted by time stamp and the first record must lie in
+    /// the future of the current simulation time. These conditions are checked
+    /// before any record is scheduled, and an error identifying the index of
+    /// the offending record in the sequence is returned if they are not met.
+    pub fn schedule_records<T, I>(
+        &self,
+        source_name: &str,
+        records: I,
+        scheduler: &Scheduler,
+    ) -> Result<(), RecordSchedulingError>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+        I: IntoIterator<Item = (MonotonicTime, T)>,
+    {
+        let source = self
+            .event_source_registry
+            .get(source_name)
+            .ok_or(RecordSchedulingError::SourceNotFound)?
+            .as_any()
+            .downcast_ref::<Arc<EventSource<T>>>()
+            .ok_or(RecordSchedulingError::EventTypeMismatch)?;
+
+        let records: Vec<_> = records.into_iter().collect();
+        if let Some(index) = records.windows(2).position(|w| w[1].0 < w[0].0) {
+            return Err(RecordSchedulingError::OutOfOrder { index: index + 1 });
+        }
+        if let Some((time, _)) = records.first() {
+            if *time <= scheduler.time() {
+                return Err(RecordSchedulingError::Scheduling {
+                    index: 0,
+                    error: SchedulingError::InvalidScheduledTime,
+                });
+            }
+        }
+
+        for (index, (time, arg)) in records.into_iter().enumerate() {
+            scheduler
+                .schedule(time, source.event(arg))
+                .map_err(|error| RecordSchedulingError::Scheduling { index, error })?;
+        }
+
+        Ok(())
+    }
+
+    /// Broadcasts a serialized event from a registered event source, blocking
+    /// until completion.
+    ///
+    /// This is the in-process counterpart to the `ProcessEvent` remote
+    /// procedure call, which makes it possible to drive a simulation bench
+    /// in-process with the same serialized scenario data as a remote client.
+    /// The event is decoded with the specified codec and processed with
+    /// [`Simulation::process`], so simulation time remains unchanged.
+    pub fn process_serialized_event(
+        &self,
+        source_name: &str,
+        event: &[u8],
+        codec: Codec,
+        simulation: &mut Simulation,
+    ) -> Result<(), SerializedEventError> {
+        let action = self
+            .event_source_registry
+            .get(source_name)
+            .ok_or(SerializedEventError::SourceNotFound)?
+            .event(event, codec)
+            .map_err(SerializedEventError::Decoding)?;
+
+        simulation
+            .process(action)
+            .map_err(SerializedEventError::Execution)
+    }
+}
+
+/// Error returned when a serialized event could not be processed with
+/// [`EndpointRegistry::process_serialized_event`].
+#[derive(Debug)]
+pub enum SerializedEventError {
+    /// No event source is registered under the specified name.
+    SourceNotFound,
+    /// The event could not be decoded as a value of the event type of the
+    /// source.
+    Decoding(Box<dyn Error + Send + Sync>),
+    /// The simulation failed to process the event.
+    Execution(ExecutionError),
+}
+
+impl fmt::Display for SerializedEventError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SourceNotFound => write!(fmt, "no event source found with the specified name"),
+            Self::Decoding(e) => write!(fmt, "the event could not be decoded: {}", e),
+            Self::Execution(e) => write!(fmt, "the event could not be processed: {}", e),
+        }
+    }
+}
+
+impl Error for SerializedEventError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Decoding(e) => Some(e.as_ref()),
+            Self::Execution(e) => Some(e),
+            Self::SourceNotFound => None,
+        }
+    }
+}
+
+/// Error returned when a sequence of records could not be scheduled with
+/// [`EndpointRegistry::schedule_records`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecordSchedulingError {
+    /// No event source is registered under the specified name.
+    SourceNotFound,
+    /// The event type of the registered source differs from the record type.
+    EventTypeMismatch,
+    /// The time stamp of a record is earlier than that of the preceding
+    /// record.
+    OutOfOrder {
+        /// Index of the offending record.
+        index: usize,
+    },
+    /// A record could not be scheduled.
+    Scheduling {
+        /// Index of the offending record.
+        index: usize,
+        /// The scheduling error.
+        error: SchedulingError,
+    },
+}
+
+impl fmt::Display for RecordSchedulingError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SourceNotFound => write!(fmt, "no event source found with the specified name"),
+            Self::EventTypeMismatch => write!(
+                fmt,
+                "the record type does not match the event type of the source"
+            ),
+            Self::OutOfOrder { index } => write!(
+                fmt,
+                "record {} is timestamped earlier than the preceding record",
+                index
+            ),
+            Self::Scheduling { index, error } => {
+                write!(fmt, "record {} could not be scheduled: {}", index, error)
+            }
+        }
+    }
+}
+
+impl Error for RecordSchedulingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Scheduling { error, .. } => Some(error),
+            _ => None,
+        }
+    }
 }
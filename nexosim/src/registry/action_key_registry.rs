@@ -0,0 +1,106 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::server::key_registry::{KeyRegistry, KeyRegistryId};
+use crate::simulation::ActionKey;
+
+/// A serializable identifier of an action key.
+///
+/// An `EventKey` has the same layout as the key returned by the
+/// `ScheduleEvent` remote procedure call and accepted by `CancelEvent`. It is
+/// only meaningful in conjunction with the [`ActionKeyRegistry`] of the
+/// simulation bench that produced it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EventKey {
+    /// First part of the key.
+    pub subkey1: u64,
+    /// Second part of the key.
+    pub subkey2: u64,
+}
+
+impl EventKey {
+    /// Converts a registry identifier to an `EventKey`.
+    pub(crate) fn from_registry_id(key_id: KeyRegistryId) -> Self {
+        let (subkey1, subkey2) = key_id.into_raw_parts();
+
+        Self {
+            subkey1: subkey1
+                .try_into()
+                .expect("action key index is too large to be serialized"),
+            subkey2,
+        }
+    }
+
+    /// Converts an `EventKey` to a registry identifier.
+    ///
+    /// Returns `None` if the key cannot possibly designate a registered key.
+    pub(crate) fn to_registry_id(self) -> Option<KeyRegistryId> {
+        let subkey1: usize = self.subkey1.try_into().ok()?;
+
+        Some(KeyRegistryId::from_raw_parts(subkey1, self.subkey2))
+    }
+}
+
+/// A registry of action keys shared between remote clients and in-process
+/// code.
+///
+/// Keys of events scheduled with the `ScheduleEvent` remote procedure call are
+/// stored in this registry. An in-process [`ActionKey`] can in turn be made
+/// available to remote clients with [`ActionKey::to_event_key`], while the key
+/// of a remotely-scheduled event can be retrieved in-process with
+/// [`ActionKey::from_event_key`].
+///
+/// An `ActionKeyRegistry` is a cheap handle that can be cloned and sent to
+/// other threads; all clones refer to the same registry.
+#[derive(Clone, Default)]
+pub struct ActionKeyRegistry {
+    inner: Arc<Mutex<KeyRegistry>>,
+}
+
+impl ActionKeyRegistry {
+    /// Locks the underlying registry.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, KeyRegistry> {
+        self.inner.lock().unwrap()
+    }
+
+    /// Registers a clone of the action key and returns its identifier.
+    ///
+    /// The key remains registered until it is extracted by a `CancelEvent`
+    /// remote procedure call or removed with [`ActionKeyRegistry::remove`].
+    pub(crate) fn register(&self, action_key: &ActionKey) -> EventKey {
+        let key_id = self.lock().insert_eternal_key(action_key.clone());
+
+        EventKey::from_registry_id(key_id)
+    }
+
+    /// Returns a clone of the registered action key, leaving it in the
+    /// registry.
+    pub(crate) fn get(&self, event_key: EventKey) -> Option<ActionKey> {
+        let key_id = event_key.to_registry_id()?;
+
+        self.lock().get_key(key_id).cloned()
+    }
+
+    /// Removes a key from the registry and returns it.
+    ///
+    /// Unlike the keys of events scheduled with the `ScheduleEvent` remote
+    /// procedure call, keys registered in-process with
+    /// [`ActionKey::to_event_key`] do not expire. Such keys should therefore be
+    /// removed once they are no longer meant to be used by remote clients,
+    /// typically after the event was processed or cancelled in-process.
+    ///
+    /// Returns `None` if the identifier does not designate a key of the
+    /// registry, which may in particular be the case if the key was already
+    /// extracted by a `CancelEvent` remote procedure call.
+    pub fn remove(&self, event_key: EventKey) -> Option<ActionKey> {
+        let key_id = event_key.to_registry_id()?;
+
+        self.lock().extract_key(key_id)
+    }
+}
+
+impl fmt::Debug for ActionKeyRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActionKeyRegistry").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,40 @@
+use std::error::Error;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Error returned when a value could not be encoded or decoded.
+pub(crate) type CodecError = Box<dyn Error + Send + Sync>;
+
+/// A serde data format used to encode serialized events, queries and replies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Codec {
+    /// The CBOR format.
+    #[default]
+    Cbor,
+    /// The JSON format, mainly meant for debugging and hand-written requests.
+    Json,
+}
+
+impl Codec {
+    /// Decodes a value.
+    pub(crate) fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, CodecError> {
+        match self {
+            Self::Cbor => Ok(ciborium::from_reader(bytes)?),
+            Self::Json => Ok(serde_json::from_slice(bytes)?),
+        }
+    }
+
+    /// Encodes a value.
+    pub(crate) fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, CodecError> {
+        match self {
+            Self::Cbor => {
+                let mut buffer = Vec::new();
+                ciborium::into_writer(value, &mut buffer)?;
+
+                Ok(buffer)
+            }
+            Self::Json => Ok(serde_json::to_vec(value)?),
+        }
+    }
+}
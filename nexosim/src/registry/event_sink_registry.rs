@@ -39,6 +39,18 @@ impl EventSinkRegistry {
     pub(crate) fn get_mut(&mut self, name: &str) -> Option<&mut dyn EventSinkStreamAny> {
         self.0.get_mut(name).map(|s| s.as_mut())
     }
+
+    /// Returns an iterator over the mutable sinks of the registry, in arbitrary
+    /// order.
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut dyn EventSinkStreamAny> {
+        self.0.values_mut().map(|s| s.as_mut())
+    }
+
+    /// Returns an iterator over the names and sinks of the registry, in
+    /// arbitrary order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &dyn EventSinkStreamAny)> {
+        self.0.iter().map(|(name, s)| (name.as_str(), s.as_ref()))
+    }
 }
 
 impl fmt::Debug for EventSinkRegistry {
@@ -59,8 +71,17 @@ pub(crate) trait EventSinkStreamAny: Send + 'static {
     /// Pauses the collection of new events.
     fn close(&mut self);
 
+    /// Reports whether the collection of new events is enabled.
+    fn is_open(&self) -> bool;
+
+    /// Returns the number of events available in the sink.
+    fn len(&self) -> usize;
+
     /// Encode and collect all events in a vector.
     fn collect(&mut self) -> Result<Vec<Vec<u8>>, SerializationError>;
+
+    /// Discards all events without encoding them.
+    fn clear(&mut self);
 }
 
 impl<E> EventSinkStreamAny for E
@@ -80,6 +101,14 @@ where
         self.close();
     }
 
+    fn is_open(&self) -> bool {
+        EventSinkStream::is_open(self)
+    }
+
+    fn len(&self) -> usize {
+        EventSinkStream::len(self)
+    }
+
     fn collect(&mut self) -> Result<Vec<Vec<u8>>, SerializationError> {
         self.__try_fold(Vec::new(), |mut encoded_events, event| {
             let mut buffer = Vec::new();
@@ -90,4 +119,8 @@ where
             })
         })
     }
+
+    fn clear(&mut self) {
+        self.by_ref().for_each(drop);
+    }
 }
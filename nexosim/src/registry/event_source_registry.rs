@@ -1,16 +1,16 @@
+use std::any::Any;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
 
-use ciborium;
 use serde::de::DeserializeOwned;
 
 use crate::ports::EventSource;
 use crate::simulation::{Action, ActionKey};
 
-type DeserializationError = ciborium::de::Error<std::io::Error>;
+use super::codec::{Codec, CodecError};
 
 /// A registry that holds all sources and sinks meant to be accessed through
 /// remote procedure calls.
@@ -40,11 +40,46 @@ impl EventSourceRegistry {
         }
     }
 
+    /// Adds a group of event sources to the registry under a single name.
+    ///
+    /// The sources are merged so that an event is broadcast to the input ports
+    /// connected to any of them.
+    ///
+    /// If the specified name is already in use for another event source, the
+    /// sources provided as argument are returned in the error.
+    pub(crate) fn add_multi<T>(
+        &mut self,
+        sources: Vec<EventSource<T>>,
+        name: impl Into<String>,
+    ) -> Result<(), Vec<EventSource<T>>>
+    where
+        T: DeserializeOwned + Clone + Send + 'static,
+    {
+        match self.0.entry(name.into()) {
+            Entry::Vacant(s) => {
+                let mut merged_source = EventSource::new();
+                for source in sources {
+                    merged_source.merge(source);
+                }
+                s.insert(Box::new(Arc::new(merged_source)));
+
+                Ok(())
+            }
+            Entry::Occupied(_) => Err(sources),
+        }
+    }
+
     /// Returns a mutable reference to the specified event source if it is in
     /// the registry.
     pub(crate) fn get(&self, name: &str) -> Option<&dyn EventSourceAny> {
         self.0.get(name).map(|s| s.as_ref())
     }
+
+    /// Returns an iterator over the names and event sources of the registry,
+    /// in arbitrary order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &dyn EventSourceAny)> {
+        self.0.iter().map(|(name, s)| (name.as_str(), s.as_ref()))
+    }
 }
 
 impl fmt::Debug for EventSourceRegistry {
@@ -53,78 +88,98 @@ impl fmt::Debug for EventSourceRegistry {
     }
 }
 
-/// A type-erased `EventSource` that operates on CBOR-encoded serialized events.
+/// A type-erased `EventSource` that operates on serialized events.
 pub(crate) trait EventSourceAny: Send + Sync + 'static {
     /// Returns an action which, when processed, broadcasts an event to all
     /// connected input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
-    fn event(&self, serialized_arg: &[u8]) -> Result<Action, DeserializationError>;
+    /// The argument is decoded with the specified codec.
+    fn event(&self, serialized_arg: &[u8], codec: Codec) -> Result<Action, CodecError>;
 
     /// Returns a cancellable action and a cancellation key; when processed, the
     /// action broadcasts an event to all connected input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is decoded with the specified codec.
     fn keyed_event(
         &self,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError>;
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError>;
 
     /// Returns a periodically recurring action which, when processed,
     /// broadcasts an event to all connected input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is decoded with the specified codec.
     fn periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<Action, DeserializationError>;
+        codec: Codec,
+    ) -> Result<Action, CodecError>;
 
     /// Returns a cancellable, periodically recurring action and a cancellation
     /// key; when processed, the action broadcasts an event to all connected
     /// input ports.
     ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is decoded with the specified codec.
     fn keyed_periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError>;
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError>;
 
     /// Human-readable name of the event type, as returned by
     /// `any::type_name`.
     fn event_type_name(&self) -> &'static str;
+
+    /// Returns the underlying `Arc<EventSource<T>>` as `&dyn Any`, for
+    /// downcasting to a typed event source.
+    fn as_any(&self) -> &dyn Any;
 }
 
 impl<T> EventSourceAny for Arc<EventSource<T>>
 where
     T: DeserializeOwned + Clone + Send + 'static,
 {
-    fn event(&self, serialized_arg: &[u8]) -> Result<Action, DeserializationError> {
-        ciborium::from_reader(serialized_arg).map(|arg| EventSource::event(self, arg))
+    fn event(&self, serialized_arg: &[u8], codec: Codec) -> Result<Action, CodecError> {
+        codec
+            .decode(serialized_arg)
+            .map(|arg| EventSource::event(self, arg))
     }
     fn keyed_event(
         &self,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError> {
-        ciborium::from_reader(serialized_arg).map(|arg| EventSource::keyed_event(self, arg))
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError> {
+        codec
+            .decode(serialized_arg)
+            .map(|arg| EventSource::keyed_event(self, arg))
     }
     fn periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<Action, DeserializationError> {
-        ciborium::from_reader(serialized_arg)
+        codec: Codec,
+    ) -> Result<Action, CodecError> {
+        codec
+            .decode(serialized_arg)
             .map(|arg| EventSource::periodic_event(self, period, arg))
     }
     fn keyed_periodic_event(
         &self,
         period: Duration,
         serialized_arg: &[u8],
-    ) -> Result<(Action, ActionKey), DeserializationError> {
-        ciborium::from_reader(serialized_arg).map(|arg| self.keyed_periodic_event(period, arg))
+        codec: Codec,
+    ) -> Result<(Action, ActionKey), CodecError> {
+        codec
+            .decode(serialized_arg)
+            .map(|arg| EventSource::keyed_periodic_event(self, period, arg))
     }
     fn event_type_name(&self) -> &'static str {
         std::any::type_name::<T>()
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
@@ -1,16 +1,15 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
 
-use ciborium;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::ports::{QuerySource, ReplyReceiver};
 use crate::simulation::Action;
 
-type DeserializationError = ciborium::de::Error<std::io::Error>;
-type SerializationError = ciborium::ser::Error<std::io::Error>;
+use super::codec::{Codec, CodecError};
 
 /// A registry that holds all sources and sinks meant to be accessed through
 /// remote procedure calls.
@@ -46,6 +45,12 @@ impl QuerySourceRegistry {
     pub(crate) fn get(&self, name: &str) -> Option<&dyn QuerySourceAny> {
         self.0.get(name).map(|s| s.as_ref())
     }
+
+    /// Returns an iterator over the names and query sources of the registry,
+    /// in arbitrary order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &dyn QuerySourceAny)> {
+        self.0.iter().map(|(name, s)| (name.as_str(), s.as_ref()))
+    }
 }
 
 impl fmt::Debug for QuerySourceRegistry {
@@ -54,18 +59,19 @@ impl fmt::Debug for QuerySourceRegistry {
     }
 }
 
-/// A type-erased `QuerySource` that operates on CBOR-encoded serialized queries
-/// and returns CBOR-encoded replies.
+/// A type-erased `QuerySource` that operates on serialized queries and returns
+/// serialized replies.
 pub(crate) trait QuerySourceAny: Send + Sync + 'static {
     /// Returns an action which, when processed, broadcasts a query to all
     /// connected replier ports.
     ///
-    ///
-    /// The argument is expected to conform to the serde CBOR encoding.
+    /// The argument is decoded with the specified codec, which is then used to
+    /// encode the replies.
     fn query(
         &self,
         arg: &[u8],
-    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), DeserializationError>;
+        codec: Codec,
+    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), CodecError>;
 
     /// Human-readable name of the request type, as returned by
     /// `any::type_name`.
@@ -84,10 +90,14 @@ where
     fn query(
         &self,
         arg: &[u8],
-    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), DeserializationError> {
-        ciborium::from_reader(arg).map(|arg| {
+        codec: Codec,
+    ) -> Result<(Action, Box<dyn ReplyReceiverAny>), CodecError> {
+        codec.decode(arg).map(|arg| {
             let (action, reply_recv) = self.query(arg);
-            let reply_recv: Box<dyn ReplyReceiverAny> = Box::new(reply_recv);
+            let reply_recv: Box<dyn ReplyReceiverAny> = Box::new(EncodingReplyReceiver {
+                receiver: reply_recv,
+                codec,
+            });
 
             (action, reply_recv)
         })
@@ -102,22 +112,29 @@ where
     }
 }
 
-/// A type-erased `ReplyReceiver` that returns CBOR-encoded replies.
+/// A type-erased `ReplyReceiver` that returns serialized replies.
 pub(crate) trait ReplyReceiverAny {
-    /// Take the replies, if any, encode them and collect them in a vector.
-    fn take_collect(&mut self) -> Option<Result<Vec<Vec<u8>>, SerializationError>>;
+    /// Take the replies, if any, encode them and collect them in a vector
+    /// together with the names of the replying models.
+    fn take_collect(&mut self) -> Option<Result<Vec<(Arc<str>, Vec<u8>)>, CodecError>>;
+}
+
+/// A `ReplyReceiver` bundled with the codec used to encode its replies.
+struct EncodingReplyReceiver<R> {
+    receiver: ReplyReceiver<R>,
+    codec: Codec,
 }
 
-impl<R: Serialize + 'static> ReplyReceiverAny for ReplyReceiver<R> {
-    fn take_collect(&mut self) -> Option<Result<Vec<Vec<u8>>, SerializationError>> {
-        let replies = self.take()?;
+impl<R: Serialize + 'static> ReplyReceiverAny for EncodingReplyReceiver<R> {
+    fn take_collect(&mut self) -> Option<Result<Vec<(Arc<str>, Vec<u8>)>, CodecError>> {
+        let codec = self.codec;
+        let replies = self.receiver.take_with_shared_names()?;
 
         let encoded_replies = (move || {
             let mut encoded_replies = Vec::new();
-            for reply in replies {
-                let mut encoded_reply = Vec::new();
-                ciborium::into_writer(&reply, &mut encoded_reply)?;
-                encoded_replies.push(encoded_reply);
+            for (model_name, reply) in replies {
+                let encoded_reply = codec.encode(&reply)?;
+                encoded_replies.push((model_name, encoded_reply));
             }
 
             Ok(encoded_replies)
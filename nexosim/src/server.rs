@@ -1,7 +1,8 @@
 //! Simulation management through remote procedure calls.
 
 mod codegen;
-mod key_registry;
+pub(crate) mod key_registry;
+mod request_metrics;
 mod run;
 mod services;
 
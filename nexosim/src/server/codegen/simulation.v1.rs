@@ -17,6 +17,12 @@ pub struct EventKey {
 pub struct InitRequest {
     #[prost(bytes = "vec", tag = "2")]
     pub cfg: ::prost::alloc::vec::Vec<u8>,
+    /// Optional tolerance for the deadline of `ScheduleEvent` requests specified
+    /// as a `time` or as a `duration` relative to an explicit `base_time`.
+    /// Events scheduled in the past by no more than this duration are accepted
+    /// and scheduled for the current simulation time instead of being rejected.
+    #[prost(message, optional, tag = "3")]
+    pub reorder_window: ::core::option::Option<::prost_types::Duration>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct InitReply {
@@ -136,6 +142,13 @@ pub struct ScheduleEventRequest {
     pub period: ::core::option::Option<::prost_types::Duration>,
     #[prost(bool, tag = "6")]
     pub with_key: bool,
+    #[prost(enumeration = "Encoding", tag = "7")]
+    pub encoding: i32,
+    /// Time relative to which a `duration` deadline is computed. If unset, the
+    /// deadline is relative to the current simulation time. It is ignored if the
+    /// deadline is specified as a `time`.
+    #[prost(message, optional, tag = "8")]
+    pub base_time: ::core::option::Option<::prost_types::Timestamp>,
     /// Expects exactly 1 variant.
     #[prost(oneof = "schedule_event_request::Deadline", tags = "1, 2")]
     pub deadline: ::core::option::Option<schedule_event_request::Deadline>,
@@ -153,6 +166,10 @@ pub mod schedule_event_request {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScheduleEventReply {
+    /// Simulation time at which the event is scheduled, or time of the first
+    /// occurrence of a periodic event. It is always unset if an error is returned.
+    #[prost(message, optional, tag = "3")]
+    pub scheduled_time: ::core::option::Option<::prost_types::Timestamp>,
     /// Always returns exactly 1 variant.
     #[prost(oneof = "schedule_event_reply::Result", tags = "1, 2, 100")]
     pub result: ::core::option::Option<schedule_event_reply::Result>,
@@ -170,6 +187,35 @@ pub mod schedule_event_reply {
         Error(super::Error),
     }
 }
+/// Either all events are scheduled or, if any request is invalid, none of them.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScheduleBatchRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub requests: ::prost::alloc::vec::Vec<ScheduleEventRequest>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScheduleBatchReply {
+    /// This field is hoisted because protobuf3 does not support `repeated` within
+    /// a `oneof`. It is Always empty if an error is returned, and otherwise
+    /// contains the reply to each request in the order of the requests.
+    #[prost(message, repeated, tag = "1")]
+    pub replies: ::prost::alloc::vec::Vec<ScheduleEventReply>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "schedule_batch_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<schedule_batch_reply::Result>,
+}
+/// Nested message and enum types in `ScheduleBatchReply`.
+pub mod schedule_batch_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+/// Cancelling a periodic event cancels all its future occurrences.
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct CancelEventRequest {
     #[prost(message, optional, tag = "1")]
@@ -198,6 +244,8 @@ pub struct ProcessEventRequest {
     pub source_name: ::prost::alloc::string::String,
     #[prost(bytes = "vec", tag = "2")]
     pub event: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration = "Encoding", tag = "3")]
+    pub encoding: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProcessEventReply {
@@ -222,6 +270,18 @@ pub struct ProcessQueryRequest {
     pub source_name: ::prost::alloc::string::String,
     #[prost(bytes = "vec", tag = "2")]
     pub request: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "3")]
+    pub with_model_names: bool,
+    /// Encoding of the request and of the replies.
+    #[prost(enumeration = "Encoding", tag = "4")]
+    pub encoding: i32,
+    /// Optional wall clock timeout for the processing of this query, overriding
+    /// the timeout configured on the server. If exceeded, a `QUERY_TIMEOUT` error
+    /// is returned and the simulation is terminated. No reply is returned in this
+    /// case, even from the models that replied before the timeout.
+    /// A negative timeout is rejected with `INVALID_MESSAGE`.
+    #[prost(message, optional, tag = "5")]
+    pub timeout: ::core::option::Option<::prost_types::Duration>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProcessQueryReply {
@@ -229,6 +289,11 @@ pub struct ProcessQueryReply {
     /// a `oneof`. It is Always empty if an error is returned
     #[prost(bytes = "vec", repeated, tag = "1")]
     pub replies: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Fully qualified names of the replying models, in the same order as the
+    /// replies. It is always empty unless `with_model_names` was set in the
+    /// request.
+    #[prost(string, repeated, tag = "2")]
+    pub model_names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     /// Always returns exactly 1 variant.
     #[prost(oneof = "process_query_reply::Result", tags = "10, 100")]
     pub result: ::core::option::Option<process_query_reply::Result>,
@@ -271,6 +336,33 @@ pub mod read_events_reply {
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadEventsAtTimeRequest {
+    #[prost(string, tag = "1")]
+    pub sink_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadEventsAtTimeReply {
+    /// This field is hoisted because protobuf3 does not support `repeated` within
+    /// a `oneof`. It is Always empty if an error is returned
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "read_events_at_time_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<read_events_at_time_reply::Result>,
+}
+/// Nested message and enum types in `ReadEventsAtTimeReply`.
+pub mod read_events_at_time_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        /// Simulation time at which the events were read.
+        #[prost(message, tag = "10")]
+        Time(::prost_types::Timestamp),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OpenSinkRequest {
     #[prost(string, tag = "1")]
     pub sink_name: ::prost::alloc::string::String,
@@ -314,13 +406,268 @@ pub mod close_sink_reply {
         Error(super::Error),
     }
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestLatency {
+    #[prost(string, tag = "1")]
+    pub request_type: ::prost::alloc::string::String,
+    /// Total number of requests of this type processed since the server started.
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+    /// Mean and maximum latencies over the rolling window.
+    #[prost(message, optional, tag = "3")]
+    pub mean: ::core::option::Option<::prost_types::Duration>,
+    #[prost(message, optional, tag = "4")]
+    pub max: ::core::option::Option<::prost_types::Duration>,
+    /// Number of requests within the rolling window for each latency bucket; the
+    /// last bucket counts the requests above the last bucket bound.
+    #[prost(uint64, repeated, tag = "5")]
+    pub bucket_counts: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RequestMetricsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestMetricsReply {
+    /// Upper bounds of the latency buckets, in increasing order.
+    #[prost(message, repeated, tag = "1")]
+    pub bucket_bounds: ::prost::alloc::vec::Vec<::prost_types::Duration>,
+    /// These fields are hoisted because protobuf3 does not support `repeated`
+    /// within a `oneof`. It is Always empty if an error is returned
+    #[prost(message, repeated, tag = "2")]
+    pub latencies: ::prost::alloc::vec::Vec<RequestLatency>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "request_metrics_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<request_metrics_reply::Result>,
+}
+/// Nested message and enum types in `RequestMetricsReply`.
+pub mod request_metrics_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+/// Reports the state of a sink without draining its events.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SinkState {
+    #[prost(string, tag = "1")]
+    pub sink_name: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub is_open: bool,
+    #[prost(uint64, tag = "3")]
+    pub buffered_count: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SinkStateRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SinkStateReply {
+    /// This field is hoisted because protobuf3 does not support `repeated` within
+    /// a `oneof`. It is Always empty if an error is returned
+    #[prost(message, repeated, tag = "1")]
+    pub sink_states: ::prost::alloc::vec::Vec<SinkState>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "sink_state_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<sink_state_reply::Result>,
+}
+/// Nested message and enum types in `SinkStateReply`.
+pub mod sink_state_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventSourceEndpoint {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuerySourceEndpoint {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub request_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub reply_type: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SinkEndpoint {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+}
+/// Lists the event sources, query sources and sinks of the simulation bench,
+/// sorted by name. Type names are informative only and are not guaranteed to be
+/// stable across compiler versions.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListEndpointsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEndpointsReply {
+    /// These fields are hoisted because protobuf3 does not support `repeated`
+    /// within a `oneof`. They are always empty if an error is returned.
+    #[prost(message, repeated, tag = "1")]
+    pub event_sources: ::prost::alloc::vec::Vec<EventSourceEndpoint>,
+    #[prost(message, repeated, tag = "2")]
+    pub query_sources: ::prost::alloc::vec::Vec<QuerySourceEndpoint>,
+    #[prost(message, repeated, tag = "3")]
+    pub sinks: ::prost::alloc::vec::Vec<SinkEndpoint>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "list_endpoints_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<list_endpoints_reply::Result>,
+}
+/// Nested message and enum types in `ListEndpointsReply`.
+pub mod list_endpoints_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+/// Cancels all scheduled events without altering the state of the models or
+/// the simulation time. All event keys are invalidated.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ClearScheduleRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClearScheduleReply {
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "clear_schedule_reply::Result", tags = "1, 100")]
+    pub result: ::core::option::Option<clear_schedule_reply::Result>,
+}
+/// Nested message and enum types in `ClearScheduleReply`.
+pub mod clear_schedule_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        /// Number of cancelled events; a periodic event counts as a single event.
+        #[prost(uint64, tag = "1")]
+        CancelledCount(u64),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+/// Processes the next scheduled events one at a time, advancing simulation
+/// time as needed.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StepEventsRequest {
+    /// Maximum number of events to process.
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StepEventsReply {
+    /// Number of processed events, which is lower than the requested count only
+    /// if no event remains scheduled. It is always 0 if an error is returned.
+    #[prost(uint64, tag = "2")]
+    pub processed_count: u64,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "step_events_reply::Result", tags = "1, 100")]
+    pub result: ::core::option::Option<step_events_reply::Result>,
+}
+/// Nested message and enum types in `StepEventsReply`.
+pub mod step_events_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "1")]
+        Time(::prost_types::Timestamp),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+/// Checks that the server is alive, whether or not a simulation is active.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PingRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingReply {
+    /// Version of the server.
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    /// Whether a simulation was successfully initialized and was not terminated
+    /// since, e.g. following a halt, a panic, a deadlock or a timeout.
+    #[prost(bool, tag = "2")]
+    pub simulation_active: bool,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "ping_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<ping_reply::Result>,
+}
+/// Nested message and enum types in `PingReply`.
+pub mod ping_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ResetEpisodeRequest {
+    #[prost(message, optional, tag = "1")]
+    pub start_time: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResetEpisodeReply {
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "reset_episode_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<reset_episode_reply::Result>,
+}
+/// Nested message and enum types in `ResetEpisodeReply`.
+pub mod reset_episode_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeSinkRequest {
+    #[prost(string, tag = "1")]
+    pub sink_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeSinkReply {
+    /// This field is hoisted because protobuf3 does not support `repeated` within
+    /// a `oneof`. It is Always empty if an error is returned
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "subscribe_sink_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<subscribe_sink_reply::Result>,
+}
+/// Nested message and enum types in `SubscribeSinkReply`.
+pub mod subscribe_sink_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
+    }
+}
 /// A convenience message type for custom transport implementation.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AnyRequest {
     /// Expects exactly 1 variant.
     #[prost(
         oneof = "any_request::Request",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 19, 20, 21, 22, 23"
     )]
     pub request: ::core::option::Option<any_request::Request>,
 }
@@ -353,6 +700,115 @@ pub mod any_request {
         OpenSinkRequest(super::OpenSinkRequest),
         #[prost(message, tag = "12")]
         CloseSinkRequest(super::CloseSinkRequest),
+        #[prost(message, tag = "13")]
+        RequestMetricsRequest(super::RequestMetricsRequest),
+        #[prost(message, tag = "14")]
+        ReadEventsAtTimeRequest(super::ReadEventsAtTimeRequest),
+        #[prost(message, tag = "15")]
+        SinkStateRequest(super::SinkStateRequest),
+        #[prost(message, tag = "16")]
+        ResetEpisodeRequest(super::ResetEpisodeRequest),
+        #[prost(message, tag = "17")]
+        BatchRequest(super::BatchRequest),
+        #[prost(message, tag = "19")]
+        ScheduleBatchRequest(super::ScheduleBatchRequest),
+        #[prost(message, tag = "20")]
+        ListEndpointsRequest(super::ListEndpointsRequest),
+        #[prost(message, tag = "21")]
+        ClearScheduleRequest(super::ClearScheduleRequest),
+        #[prost(message, tag = "22")]
+        StepEventsRequest(super::StepEventsRequest),
+        #[prost(message, tag = "23")]
+        PingRequest(super::PingRequest),
+    }
+}
+/// A convenience message type for custom transport implementation.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnyReply {
+    /// Always returns exactly 1 variant.
+    #[prost(
+        oneof = "any_reply::Reply",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 19, 20, 21, 22, 23"
+    )]
+    pub reply: ::core::option::Option<any_reply::Reply>,
+}
+/// Nested message and enum types in `AnyReply`.
+pub mod any_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Reply {
+        #[prost(message, tag = "1")]
+        InitReply(super::InitReply),
+        #[prost(message, tag = "2")]
+        HaltReply(super::HaltReply),
+        #[prost(message, tag = "3")]
+        TimeReply(super::TimeReply),
+        #[prost(message, tag = "4")]
+        StepReply(super::StepReply),
+        #[prost(message, tag = "5")]
+        StepUntilReply(super::StepUntilReply),
+        #[prost(message, tag = "6")]
+        ScheduleEventReply(super::ScheduleEventReply),
+        #[prost(message, tag = "7")]
+        CancelEventReply(super::CancelEventReply),
+        #[prost(message, tag = "8")]
+        ProcessEventReply(super::ProcessEventReply),
+        #[prost(message, tag = "9")]
+        ProcessQueryReply(super::ProcessQueryReply),
+        #[prost(message, tag = "10")]
+        ReadEventsReply(super::ReadEventsReply),
+        #[prost(message, tag = "11")]
+        OpenSinkReply(super::OpenSinkReply),
+        #[prost(message, tag = "12")]
+        CloseSinkReply(super::CloseSinkReply),
+        #[prost(message, tag = "13")]
+        RequestMetricsReply(super::RequestMetricsReply),
+        #[prost(message, tag = "14")]
+        ReadEventsAtTimeReply(super::ReadEventsAtTimeReply),
+        #[prost(message, tag = "15")]
+        SinkStateReply(super::SinkStateReply),
+        #[prost(message, tag = "16")]
+        ResetEpisodeReply(super::ResetEpisodeReply),
+        #[prost(message, tag = "17")]
+        BatchReply(super::BatchReply),
+        #[prost(message, tag = "19")]
+        ScheduleBatchReply(super::ScheduleBatchReply),
+        #[prost(message, tag = "20")]
+        ListEndpointsReply(super::ListEndpointsReply),
+        #[prost(message, tag = "21")]
+        ClearScheduleReply(super::ClearScheduleReply),
+        #[prost(message, tag = "22")]
+        StepEventsReply(super::StepEventsReply),
+        #[prost(message, tag = "23")]
+        PingReply(super::PingReply),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub requests: ::prost::alloc::vec::Vec<AnyRequest>,
+    #[prost(bool, tag = "2")]
+    pub stop_on_error: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchReply {
+    /// This field is hoisted because protobuf3 does not support `repeated` within
+    /// a `oneof`. It is Always empty if an error is returned
+    #[prost(message, repeated, tag = "1")]
+    pub replies: ::prost::alloc::vec::Vec<AnyReply>,
+    /// Always returns exactly 1 variant.
+    #[prost(oneof = "batch_reply::Result", tags = "10, 100")]
+    pub result: ::core::option::Option<batch_reply::Result>,
+}
+/// Nested message and enum types in `BatchReply`.
+pub mod batch_reply {
+    /// Always returns exactly 1 variant.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "10")]
+        Empty(()),
+        #[prost(message, tag = "100")]
+        Error(super::Error),
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -365,6 +821,7 @@ pub enum ErrorCode {
     InvalidDeadline = 4,
     InvalidMessage = 5,
     InvalidKey = 6,
+    HorizonExceeded = 7,
     InitializerPanic = 10,
     SimulationNotStarted = 11,
     SimulationHalted = 12,
@@ -377,6 +834,9 @@ pub enum ErrorCode {
     SimulationOutOfSync = 19,
     SimulationBadQuery = 20,
     SimulationTimeOutOfRange = 21,
+    QueryTimeout = 22,
+    SimulationPollBudgetExceeded = 23,
+    SimulationUnconnectedPorts = 24,
     SourceNotFound = 30,
     SinkNotFound = 31,
 }
@@ -394,6 +854,7 @@ impl ErrorCode {
             Self::InvalidDeadline => "INVALID_DEADLINE",
             Self::InvalidMessage => "INVALID_MESSAGE",
             Self::InvalidKey => "INVALID_KEY",
+            Self::HorizonExceeded => "HORIZON_EXCEEDED",
             Self::InitializerPanic => "INITIALIZER_PANIC",
             Self::SimulationNotStarted => "SIMULATION_NOT_STARTED",
             Self::SimulationHalted => "SIMULATION_HALTED",
@@ -406,6 +867,9 @@ impl ErrorCode {
             Self::SimulationOutOfSync => "SIMULATION_OUT_OF_SYNC",
             Self::SimulationBadQuery => "SIMULATION_BAD_QUERY",
             Self::SimulationTimeOutOfRange => "SIMULATION_TIME_OUT_OF_RANGE",
+            Self::QueryTimeout => "QUERY_TIMEOUT",
+            Self::SimulationPollBudgetExceeded => "SIMULATION_POLL_BUDGET_EXCEEDED",
+            Self::SimulationUnconnectedPorts => "SIMULATION_UNCONNECTED_PORTS",
             Self::SourceNotFound => "SOURCE_NOT_FOUND",
             Self::SinkNotFound => "SINK_NOT_FOUND",
         }
@@ -420,6 +884,7 @@ impl ErrorCode {
             "INVALID_DEADLINE" => Some(Self::InvalidDeadline),
             "INVALID_MESSAGE" => Some(Self::InvalidMessage),
             "INVALID_KEY" => Some(Self::InvalidKey),
+            "HORIZON_EXCEEDED" => Some(Self::HorizonExceeded),
             "INITIALIZER_PANIC" => Some(Self::InitializerPanic),
             "SIMULATION_NOT_STARTED" => Some(Self::SimulationNotStarted),
             "SIMULATION_HALTED" => Some(Self::SimulationHalted),
@@ -432,12 +897,42 @@ impl ErrorCode {
             "SIMULATION_OUT_OF_SYNC" => Some(Self::SimulationOutOfSync),
             "SIMULATION_BAD_QUERY" => Some(Self::SimulationBadQuery),
             "SIMULATION_TIME_OUT_OF_RANGE" => Some(Self::SimulationTimeOutOfRange),
+            "QUERY_TIMEOUT" => Some(Self::QueryTimeout),
+            "SIMULATION_POLL_BUDGET_EXCEEDED" => Some(Self::SimulationPollBudgetExceeded),
+            "SIMULATION_UNCONNECTED_PORTS" => Some(Self::SimulationUnconnectedPorts),
             "SOURCE_NOT_FOUND" => Some(Self::SourceNotFound),
             "SINK_NOT_FOUND" => Some(Self::SinkNotFound),
             _ => None,
         }
     }
 }
+/// Encoding of serialized events, queries and replies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Encoding {
+    Cbor = 0,
+    Json = 1,
+}
+impl Encoding {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Cbor => "CBOR",
+            Self::Json => "JSON",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CBOR" => Some(Self::Cbor),
+            "JSON" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
 /// Generated server implementations.
 pub mod simulation_server {
     #![allow(
@@ -511,32 +1006,100 @@ pub mod simulation_server {
             &self,
             request: tonic::Request<super::CloseSinkRequest>,
         ) -> std::result::Result<tonic::Response<super::CloseSinkReply>, tonic::Status>;
-    }
-    #[derive(Debug)]
-    pub struct SimulationServer<T> {
-        inner: Arc<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    impl<T> SimulationServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
-        }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
+        async fn request_metrics(
+            &self,
+            request: tonic::Request<super::RequestMetricsRequest>,
+        ) -> std::result::Result<tonic::Response<super::RequestMetricsReply>, tonic::Status>;
+        async fn read_events_at_time(
+            &self,
+            request: tonic::Request<super::ReadEventsAtTimeRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReadEventsAtTimeReply>, tonic::Status>;
+        async fn sink_state(
+            &self,
+            request: tonic::Request<super::SinkStateRequest>,
+        ) -> std::result::Result<tonic::Response<super::SinkStateReply>, tonic::Status>;
+        async fn reset_episode(
+            &self,
+            request: tonic::Request<super::ResetEpisodeRequest>,
+        ) -> std::result::Result<tonic::Response<super::ResetEpisodeReply>, tonic::Status>;
+        /// Server streaming response type for the SubscribeSink method.
+        type SubscribeSinkStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::SubscribeSinkReply, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn subscribe_sink(
+            &self,
+            request: tonic::Request<super::SubscribeSinkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeSinkStream>,
+            tonic::Status,
+        >;
+        async fn batch(
+            &self,
+            request: tonic::Request<super::BatchRequest>,
+        ) -> std::result::Result<tonic::Response<super::BatchReply>, tonic::Status>;
+        async fn schedule_batch(
+            &self,
+            request: tonic::Request<super::ScheduleBatchRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ScheduleBatchReply>,
+            tonic::Status,
+        >;
+        async fn list_endpoints(
+            &self,
+            request: tonic::Request<super::ListEndpointsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListEndpointsReply>,
+            tonic::Status,
+        >;
+        async fn clear_schedule(
+            &self,
+            request: tonic::Request<super::ClearScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ClearScheduleReply>,
+            tonic::Status,
+        >;
+        async fn step_events(
+            &self,
+            request: tonic::Request<super::StepEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::StepEventsReply>,
+            tonic::Status,
+        >;
+        async fn ping(
+            &self,
+            request: tonic::Request<super::PingRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PingReply>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct SimulationServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> SimulationServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
         where
             F: tonic::service::Interceptor,
         {
@@ -590,21 +1153,375 @@ pub mod simulation_server {
             match req.uri().path() {
                 "/simulation.v1.Simulation/Init" => {
                     #[allow(non_camel_case_types)]
-                    struct InitSvc<T: Simulation>(pub Arc<T>);
-                    impl<T: Simulation> tonic::server::UnaryService<super::InitRequest>
-                    for InitSvc<T> {
-                        type Response = super::InitReply;
+                    struct InitSvc<T: Simulation>(pub Arc<T>);
+                    impl<T: Simulation> tonic::server::UnaryService<super::InitRequest>
+                    for InitSvc<T> {
+                        type Response = super::InitReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::InitRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::init(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = InitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/Halt" => {
+                    #[allow(non_camel_case_types)]
+                    struct HaltSvc<T: Simulation>(pub Arc<T>);
+                    impl<T: Simulation> tonic::server::UnaryService<super::HaltRequest>
+                    for HaltSvc<T> {
+                        type Response = super::HaltReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HaltRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::halt(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = HaltSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/Time" => {
+                    #[allow(non_camel_case_types)]
+                    struct TimeSvc<T: Simulation>(pub Arc<T>);
+                    impl<T: Simulation> tonic::server::UnaryService<super::TimeRequest>
+                    for TimeSvc<T> {
+                        type Response = super::TimeReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TimeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::time(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = TimeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/Step" => {
+                    #[allow(non_camel_case_types)]
+                    struct StepSvc<T: Simulation>(pub Arc<T>);
+                    impl<T: Simulation> tonic::server::UnaryService<super::StepRequest>
+                    for StepSvc<T> {
+                        type Response = super::StepReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StepRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::step(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StepSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/StepUntil" => {
+                    #[allow(non_camel_case_types)]
+                    struct StepUntilSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::StepUntilRequest>
+                    for StepUntilSvc<T> {
+                        type Response = super::StepUntilReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StepUntilRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::step_until(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StepUntilSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/ScheduleEvent" => {
+                    #[allow(non_camel_case_types)]
+                    struct ScheduleEventSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ScheduleEventRequest>
+                    for ScheduleEventSvc<T> {
+                        type Response = super::ScheduleEventReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ScheduleEventRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::schedule_event(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ScheduleEventSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/CancelEvent" => {
+                    #[allow(non_camel_case_types)]
+                    struct CancelEventSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::CancelEventRequest>
+                    for CancelEventSvc<T> {
+                        type Response = super::CancelEventReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelEventRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::cancel_event(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CancelEventSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/ProcessEvent" => {
+                    #[allow(non_camel_case_types)]
+                    struct ProcessEventSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ProcessEventRequest>
+                    for ProcessEventSvc<T> {
+                        type Response = super::ProcessEventReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ProcessEventRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::process_event(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ProcessEventSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/ProcessQuery" => {
+                    #[allow(non_camel_case_types)]
+                    struct ProcessQuerySvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ProcessQueryRequest>
+                    for ProcessQuerySvc<T> {
+                        type Response = super::ProcessQueryReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::InitRequest>,
+                            request: tonic::Request<super::ProcessQueryRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::init(&inner, request).await
+                                <T as Simulation>::process_query(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -615,7 +1532,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = InitSvc(inner);
+                        let method = ProcessQuerySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -631,23 +1548,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/Halt" => {
+                "/simulation.v1.Simulation/ReadEvents" => {
                     #[allow(non_camel_case_types)]
-                    struct HaltSvc<T: Simulation>(pub Arc<T>);
-                    impl<T: Simulation> tonic::server::UnaryService<super::HaltRequest>
-                    for HaltSvc<T> {
-                        type Response = super::HaltReply;
+                    struct ReadEventsSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::ReadEventsRequest>
+                    for ReadEventsSvc<T> {
+                        type Response = super::ReadEventsReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::HaltRequest>,
+                            request: tonic::Request<super::ReadEventsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::halt(&inner, request).await
+                                <T as Simulation>::read_events(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -658,7 +1577,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = HaltSvc(inner);
+                        let method = ReadEventsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -674,23 +1593,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/Time" => {
+                "/simulation.v1.Simulation/OpenSink" => {
                     #[allow(non_camel_case_types)]
-                    struct TimeSvc<T: Simulation>(pub Arc<T>);
-                    impl<T: Simulation> tonic::server::UnaryService<super::TimeRequest>
-                    for TimeSvc<T> {
-                        type Response = super::TimeReply;
+                    struct OpenSinkSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::OpenSinkRequest>
+                    for OpenSinkSvc<T> {
+                        type Response = super::OpenSinkReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::TimeRequest>,
+                            request: tonic::Request<super::OpenSinkRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::time(&inner, request).await
+                                <T as Simulation>::open_sink(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -701,7 +1622,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = TimeSvc(inner);
+                        let method = OpenSinkSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -717,23 +1638,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/Step" => {
+                "/simulation.v1.Simulation/CloseSink" => {
                     #[allow(non_camel_case_types)]
-                    struct StepSvc<T: Simulation>(pub Arc<T>);
-                    impl<T: Simulation> tonic::server::UnaryService<super::StepRequest>
-                    for StepSvc<T> {
-                        type Response = super::StepReply;
+                    struct CloseSinkSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::CloseSinkRequest>
+                    for CloseSinkSvc<T> {
+                        type Response = super::CloseSinkReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::StepRequest>,
+                            request: tonic::Request<super::CloseSinkRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::step(&inner, request).await
+                                <T as Simulation>::close_sink(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -744,7 +1667,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = StepSvc(inner);
+                        let method = CloseSinkSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -760,25 +1683,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/StepUntil" => {
+                "/simulation.v1.Simulation/RequestMetrics" => {
                     #[allow(non_camel_case_types)]
-                    struct StepUntilSvc<T: Simulation>(pub Arc<T>);
+                    struct RequestMetricsSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::StepUntilRequest>
-                    for StepUntilSvc<T> {
-                        type Response = super::StepUntilReply;
+                    > tonic::server::UnaryService<super::RequestMetricsRequest>
+                    for RequestMetricsSvc<T> {
+                        type Response = super::RequestMetricsReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::StepUntilRequest>,
+                            request: tonic::Request<super::RequestMetricsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::step_until(&inner, request).await
+                                <T as Simulation>::request_metrics(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -789,7 +1712,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = StepUntilSvc(inner);
+                        let method = RequestMetricsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -805,25 +1728,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/ScheduleEvent" => {
+                "/simulation.v1.Simulation/ReadEventsAtTime" => {
                     #[allow(non_camel_case_types)]
-                    struct ScheduleEventSvc<T: Simulation>(pub Arc<T>);
+                    struct ReadEventsAtTimeSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::ScheduleEventRequest>
-                    for ScheduleEventSvc<T> {
-                        type Response = super::ScheduleEventReply;
+                    > tonic::server::UnaryService<super::ReadEventsAtTimeRequest>
+                    for ReadEventsAtTimeSvc<T> {
+                        type Response = super::ReadEventsAtTimeReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ScheduleEventRequest>,
+                            request: tonic::Request<super::ReadEventsAtTimeRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::schedule_event(&inner, request).await
+                                <T as Simulation>::read_events_at_time(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -834,7 +1757,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ScheduleEventSvc(inner);
+                        let method = ReadEventsAtTimeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -850,25 +1773,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/CancelEvent" => {
+                "/simulation.v1.Simulation/SinkState" => {
                     #[allow(non_camel_case_types)]
-                    struct CancelEventSvc<T: Simulation>(pub Arc<T>);
+                    struct SinkStateSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::CancelEventRequest>
-                    for CancelEventSvc<T> {
-                        type Response = super::CancelEventReply;
+                    > tonic::server::UnaryService<super::SinkStateRequest>
+                    for SinkStateSvc<T> {
+                        type Response = super::SinkStateReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CancelEventRequest>,
+                            request: tonic::Request<super::SinkStateRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::cancel_event(&inner, request).await
+                                <T as Simulation>::sink_state(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -879,7 +1802,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = CancelEventSvc(inner);
+                        let method = SinkStateSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -895,25 +1818,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/ProcessEvent" => {
+                "/simulation.v1.Simulation/ResetEpisode" => {
                     #[allow(non_camel_case_types)]
-                    struct ProcessEventSvc<T: Simulation>(pub Arc<T>);
+                    struct ResetEpisodeSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::ProcessEventRequest>
-                    for ProcessEventSvc<T> {
-                        type Response = super::ProcessEventReply;
+                    > tonic::server::UnaryService<super::ResetEpisodeRequest>
+                    for ResetEpisodeSvc<T> {
+                        type Response = super::ResetEpisodeReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ProcessEventRequest>,
+                            request: tonic::Request<super::ResetEpisodeRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::process_event(&inner, request).await
+                                <T as Simulation>::reset_episode(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -924,7 +1847,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ProcessEventSvc(inner);
+                        let method = ResetEpisodeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -940,25 +1863,71 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/ProcessQuery" => {
+                "/simulation.v1.Simulation/SubscribeSink" => {
                     #[allow(non_camel_case_types)]
-                    struct ProcessQuerySvc<T: Simulation>(pub Arc<T>);
+                    struct SubscribeSinkSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::ProcessQueryRequest>
-                    for ProcessQuerySvc<T> {
-                        type Response = super::ProcessQueryReply;
+                    > tonic::server::ServerStreamingService<super::SubscribeSinkRequest>
+                    for SubscribeSinkSvc<T> {
+                        type Response = super::SubscribeSinkReply;
+                        type ResponseStream = T::SubscribeSinkStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeSinkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::subscribe_sink(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeSinkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/Batch" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::BatchRequest>
+                    for BatchSvc<T> {
+                        type Response = super::BatchReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ProcessQueryRequest>,
+                            request: tonic::Request<super::BatchRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::process_query(&inner, request).await
+                                <T as Simulation>::batch(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -969,7 +1938,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ProcessQuerySvc(inner);
+                        let method = BatchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -985,25 +1954,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/ReadEvents" => {
+                "/simulation.v1.Simulation/ScheduleBatch" => {
                     #[allow(non_camel_case_types)]
-                    struct ReadEventsSvc<T: Simulation>(pub Arc<T>);
+                    struct ScheduleBatchSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::ReadEventsRequest>
-                    for ReadEventsSvc<T> {
-                        type Response = super::ReadEventsReply;
+                    > tonic::server::UnaryService<super::ScheduleBatchRequest>
+                    for ScheduleBatchSvc<T> {
+                        type Response = super::ScheduleBatchReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ReadEventsRequest>,
+                            request: tonic::Request<super::ScheduleBatchRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::read_events(&inner, request).await
+                                <T as Simulation>::schedule_batch(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1014,7 +1983,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ReadEventsSvc(inner);
+                        let method = ScheduleBatchSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1030,25 +1999,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/OpenSink" => {
+                "/simulation.v1.Simulation/ListEndpoints" => {
                     #[allow(non_camel_case_types)]
-                    struct OpenSinkSvc<T: Simulation>(pub Arc<T>);
+                    struct ListEndpointsSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::OpenSinkRequest>
-                    for OpenSinkSvc<T> {
-                        type Response = super::OpenSinkReply;
+                    > tonic::server::UnaryService<super::ListEndpointsRequest>
+                    for ListEndpointsSvc<T> {
+                        type Response = super::ListEndpointsReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::OpenSinkRequest>,
+                            request: tonic::Request<super::ListEndpointsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::open_sink(&inner, request).await
+                                <T as Simulation>::list_endpoints(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1059,7 +2028,7 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = OpenSinkSvc(inner);
+                        let method = ListEndpointsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -1075,25 +2044,25 @@ pub mod simulation_server {
                     };
                     Box::pin(fut)
                 }
-                "/simulation.v1.Simulation/CloseSink" => {
+                "/simulation.v1.Simulation/ClearSchedule" => {
                     #[allow(non_camel_case_types)]
-                    struct CloseSinkSvc<T: Simulation>(pub Arc<T>);
+                    struct ClearScheduleSvc<T: Simulation>(pub Arc<T>);
                     impl<
                         T: Simulation,
-                    > tonic::server::UnaryService<super::CloseSinkRequest>
-                    for CloseSinkSvc<T> {
-                        type Response = super::CloseSinkReply;
+                    > tonic::server::UnaryService<super::ClearScheduleRequest>
+                    for ClearScheduleSvc<T> {
+                        type Response = super::ClearScheduleReply;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CloseSinkRequest>,
+                            request: tonic::Request<super::ClearScheduleRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Simulation>::close_sink(&inner, request).await
+                                <T as Simulation>::clear_schedule(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1104,7 +2073,97 @@ pub mod simulation_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = CloseSinkSvc(inner);
+                        let method = ClearScheduleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/StepEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct StepEventsSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::StepEventsRequest>
+                    for StepEventsSvc<T> {
+                        type Response = super::StepEventsReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StepEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::step_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StepEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/simulation.v1.Simulation/Ping" => {
+                    #[allow(non_camel_case_types)]
+                    struct PingSvc<T: Simulation>(pub Arc<T>);
+                    impl<
+                        T: Simulation,
+                    > tonic::server::UnaryService<super::PingRequest>
+                    for PingSvc<T> {
+                        type Response = super::PingReply;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PingRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Simulation>::ping(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PingSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
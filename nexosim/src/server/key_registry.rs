@@ -28,6 +28,14 @@ impl KeyRegistry {
         self.keys.insert(MonotonicTime::MAX, action_key)
     }
 
+    /// Returns a reference to an `ActionKey` without removing it from the
+    /// registry.
+    ///
+    /// Returns `None` if the key was not found in the registry.
+    pub(crate) fn get_key(&self, key_id: KeyRegistryId) -> Option<&ActionKey> {
+        self.keys.get(key_id).map(|(_, key)| key)
+    }
+
     /// Removes an `ActionKey` from the registry and returns it.
     ///
     /// Returns `None` if the key was not found in the registry.
@@ -45,4 +53,13 @@ impl KeyRegistry {
             self.keys.pull();
         }
     }
+
+    /// Removes all keys from the registry.
+    ///
+    /// Keys are pulled one by one rather than dropped together with the
+    /// underlying queue so that the identifiers of removed keys are never
+    /// reused.
+    pub(crate) fn clear(&mut self) {
+        while self.keys.pull().is_some() {}
+    }
 }
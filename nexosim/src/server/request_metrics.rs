@@ -0,0 +1,146 @@
+//! Latency metrics of the requests processed by the server.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use super::codegen::simulation::*;
+
+/// Number of latest latencies retained for each request type.
+const WINDOW_SIZE: usize = 1024;
+
+/// Upper bounds of the latency histogram buckets.
+const BUCKET_BOUNDS: [Duration; 7] = [
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+    Duration::from_secs(10),
+];
+
+/// Rolling latency histograms of the requests processed by the server, indexed
+/// by request type.
+#[derive(Default)]
+pub(crate) struct RequestMetrics {
+    latencies: HashMap<&'static str, RollingLatencies>,
+}
+
+impl RequestMetrics {
+    /// Records the processing time of a request.
+    pub(crate) fn record(&mut self, request_type: &'static str, latency: Duration) {
+        self.latencies
+            .entry(request_type)
+            .or_default()
+            .push(latency);
+    }
+
+    /// Returns a snapshot of the latency histograms of all request types.
+    pub(crate) fn reply(&self) -> RequestMetricsReply {
+        let mut latencies: Vec<_> = self
+            .latencies
+            .iter()
+            .map(|(request_type, latencies)| latencies.to_request_latency(request_type))
+            .collect();
+        latencies.sort_by(|a, b| a.request_type.cmp(&b.request_type));
+
+        RequestMetricsReply {
+            bucket_bounds: BUCKET_BOUNDS.iter().copied().map(to_duration).collect(),
+            latencies,
+            result: Some(request_metrics_reply::Result::Empty(())),
+        }
+    }
+}
+
+/// The latest latencies of a request type.
+#[derive(Default)]
+struct RollingLatencies {
+    count: u64,
+    window: VecDeque<Duration>,
+}
+
+impl RollingLatencies {
+    /// Adds a latency, evicting the oldest one if the window is full.
+    fn push(&mut self, latency: Duration) {
+        if self.window.len() == WINDOW_SIZE {
+            self.window.pop_front();
+        }
+        self.window.push_back(latency);
+        self.count += 1;
+    }
+
+    /// Computes the histogram of the latencies within the window.
+    fn to_request_latency(&self, request_type: &str) -> RequestLatency {
+        let mut bucket_counts = vec![0; BUCKET_BOUNDS.len() + 1];
+        let mut total = Duration::ZERO;
+        let mut max = Duration::ZERO;
+        for &latency in &self.window {
+            let bucket = BUCKET_BOUNDS.partition_point(|&bound| bound < latency);
+            bucket_counts[bucket] += 1;
+            total = total.saturating_add(latency);
+            max = max.max(latency);
+        }
+        // The window is never empty since entries are only created on push.
+        let mean = total / self.window.len() as u32;
+
+        RequestLatency {
+            request_type: request_type.to_string(),
+            count: self.count,
+            mean: Some(to_duration(mean)),
+            max: Some(to_duration(max)),
+            bucket_counts,
+        }
+    }
+}
+
+/// Converts a `std::time::Duration` to a protobuf `Duration`, saturating if the
+/// duration exceeds the protobuf-specified range.
+fn to_duration(duration: Duration) -> prost_types::Duration {
+    prost_types::Duration::try_from(duration).unwrap_or(prost_types::Duration {
+        seconds: i64::MAX,
+        nanos: 999_999_999,
+    })
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_metrics_histogram() {
+        let mut metrics = RequestMetrics::default();
+        metrics.record("Step", Duration::from_micros(5));
+        metrics.record("Step", Duration::from_micros(10));
+        metrics.record("Step", Duration::from_millis(50));
+        metrics.record("Time", Duration::from_secs(20));
+
+        let reply = metrics.reply();
+        assert_eq!(reply.bucket_bounds.len(), BUCKET_BOUNDS.len());
+        assert_eq!(reply.latencies.len(), 2);
+
+        let step = &reply.latencies[0];
+        assert_eq!(step.request_type, "Step");
+        assert_eq!(step.count, 3);
+        assert_eq!(step.bucket_counts, vec![2, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(step.max, Some(to_duration(Duration::from_millis(50))));
+
+        let time = &reply.latencies[1];
+        assert_eq!(time.request_type, "Time");
+        assert_eq!(time.bucket_counts, vec![0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn request_metrics_rolling_window() {
+        let mut metrics = RequestMetrics::default();
+        for _ in 0..WINDOW_SIZE {
+            metrics.record("Step", Duration::from_secs(1));
+        }
+        metrics.record("Step", Duration::from_micros(1));
+
+        let reply = metrics.reply();
+        let step = &reply.latencies[0];
+        assert_eq!(step.count, WINDOW_SIZE as u64 + 1);
+        assert_eq!(step.bucket_counts[0], 1);
+        assert_eq!(step.bucket_counts[5], WINDOW_SIZE as u64 - 1);
+    }
+}
@@ -1,11 +1,14 @@
 //! Simulation server.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::time::Instant;
 
 use serde::de::DeserializeOwned;
 use tonic::{transport::Server, Request, Response, Status};
@@ -14,9 +17,9 @@ use crate::registry::EndpointRegistry;
 use crate::simulation::{Simulation, SimulationError};
 
 use super::codegen::simulation::*;
-use super::key_registry::KeyRegistry;
+use super::request_metrics::RequestMetrics;
 use super::services::InitService;
-use super::services::{ControllerService, MonitorService, SchedulerService};
+use super::services::{ControllerService, MonitorService, SchedulerService, SinkEventStream};
 
 /// Runs a simulation from a network server.
 ///
@@ -139,6 +142,8 @@ struct GrpcSimulationService {
     controller_service: Mutex<ControllerService>,
     monitor_service: Mutex<MonitorService>,
     scheduler_service: Mutex<SchedulerService>,
+    request_metrics: Mutex<RequestMetrics>,
+    is_simulation_active: AtomicBool,
 }
 
 impl GrpcSimulationService {
@@ -158,6 +163,8 @@ impl GrpcSimulationService {
             controller_service: Mutex::new(ControllerService::NotStarted),
             monitor_service: Mutex::new(MonitorService::NotStarted),
             scheduler_service: Mutex::new(SchedulerService::NotStarted),
+            request_metrics: Mutex::new(RequestMetrics::default()),
+            is_simulation_active: AtomicBool::new(false),
         }
     }
 
@@ -180,6 +187,134 @@ impl GrpcSimulationService {
     fn scheduler(&self) -> MutexGuard<'_, SchedulerService> {
         self.scheduler_service.lock().unwrap()
     }
+
+    /// Runs a request on the controller, then publishes the events collected
+    /// by subscribed sinks.
+    ///
+    /// The simulation is flagged as inactive if the request terminated it, for
+    /// instance following a halt, a panic, a deadlock or a timeout.
+    fn control<R>(&self, f: impl FnOnce(&mut ControllerService) -> R) -> R {
+        let mut controller = self.controller();
+        let reply = f(&mut controller);
+        self.is_simulation_active
+            .store(controller.is_active(), Ordering::Relaxed);
+        drop(controller);
+
+        self.monitor().publish_events();
+
+        reply
+    }
+
+    /// Processes a request wrapped in an `AnyRequest`.
+    async fn process_any(&self, request: any_request::Request) -> Result<any_reply::Reply, Status> {
+        use any_reply::Reply;
+        use any_request::Request as Req;
+        use simulation_server::Simulation as _;
+
+        Ok(match request {
+            Req::InitRequest(request) => {
+                Reply::InitReply(self.init(Request::new(request)).await?.into_inner())
+            }
+            Req::HaltRequest(request) => {
+                Reply::HaltReply(self.halt(Request::new(request)).await?.into_inner())
+            }
+            Req::TimeRequest(request) => {
+                Reply::TimeReply(self.time(Request::new(request)).await?.into_inner())
+            }
+            Req::StepRequest(request) => {
+                Reply::StepReply(self.step(Request::new(request)).await?.into_inner())
+            }
+            Req::StepUntilRequest(request) => {
+                Reply::StepUntilReply(self.step_until(Request::new(request)).await?.into_inner())
+            }
+            Req::ScheduleEventRequest(request) => Reply::ScheduleEventReply(
+                self.schedule_event(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::CancelEventRequest(request) => Reply::CancelEventReply(
+                self.cancel_event(Request::new(request)).await?.into_inner(),
+            ),
+            Req::ProcessEventRequest(request) => Reply::ProcessEventReply(
+                self.process_event(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::ProcessQueryRequest(request) => Reply::ProcessQueryReply(
+                self.process_query(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::ReadEventsRequest(request) => {
+                Reply::ReadEventsReply(self.read_events(Request::new(request)).await?.into_inner())
+            }
+            Req::OpenSinkRequest(request) => {
+                Reply::OpenSinkReply(self.open_sink(Request::new(request)).await?.into_inner())
+            }
+            Req::CloseSinkRequest(request) => {
+                Reply::CloseSinkReply(self.close_sink(Request::new(request)).await?.into_inner())
+            }
+            Req::RequestMetricsRequest(request) => Reply::RequestMetricsReply(
+                self.request_metrics(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::ReadEventsAtTimeRequest(request) => Reply::ReadEventsAtTimeReply(
+                self.read_events_at_time(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::SinkStateRequest(request) => {
+                Reply::SinkStateReply(self.sink_state(Request::new(request)).await?.into_inner())
+            }
+            Req::ResetEpisodeRequest(request) => Reply::ResetEpisodeReply(
+                self.reset_episode(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::BatchRequest(request) => {
+                Reply::BatchReply(self.batch(Request::new(request)).await?.into_inner())
+            }
+            Req::ScheduleBatchRequest(request) => Reply::ScheduleBatchReply(
+                self.schedule_batch(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::ListEndpointsRequest(request) => Reply::ListEndpointsReply(
+                self.list_endpoints(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::ClearScheduleRequest(request) => Reply::ClearScheduleReply(
+                self.clear_schedule(Request::new(request))
+                    .await?
+                    .into_inner(),
+            ),
+            Req::StepEventsRequest(request) => {
+                Reply::StepEventsReply(self.step_events(Request::new(request)).await?.into_inner())
+            }
+            Req::PingRequest(request) => {
+                Reply::PingReply(self.ping(Request::new(request)).await?.into_inner())
+            }
+        })
+    }
+
+    /// Processes a request and records its processing time.
+    ///
+    /// The processing time includes the time spent waiting for the service
+    /// lock, which may be held by a concurrent request.
+    fn timed<R>(&self, request_type: &'static str, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let reply = f();
+        let latency = start.elapsed();
+
+        self.request_metrics
+            .lock()
+            .unwrap()
+            .record(request_type, latency);
+
+        reply
+    }
 }
 
 #[tonic::async_trait]
@@ -187,12 +322,14 @@ impl simulation_server::Simulation for GrpcSimulationService {
     async fn init(&self, request: Request<InitRequest>) -> Result<Response<InitReply>, Status> {
         let request = request.into_inner();
 
-        let (reply, bench) = self.initializer().init(request);
+        let (reply, bench) = self.timed("Init", || self.initializer().init(request));
 
-        if let Some((simulation, scheduler, endpoint_registry)) = bench {
+        if let Some((simulation, scheduler, endpoint_registry, reorder_window)) = bench {
             let event_source_registry = Arc::new(endpoint_registry.event_source_registry);
             let query_source_registry = endpoint_registry.query_source_registry;
             let event_sink_registry = endpoint_registry.event_sink_registry;
+            let key_registry = endpoint_registry.action_key_registry;
+            let max_horizon = endpoint_registry.max_scheduling_horizon;
 
             *self.controller() = ControllerService::Started {
                 simulation,
@@ -201,12 +338,16 @@ impl simulation_server::Simulation for GrpcSimulationService {
             };
             *self.monitor() = MonitorService::Started {
                 event_sink_registry,
+                subscriptions: HashMap::new(),
             };
             *self.scheduler() = SchedulerService::Started {
                 scheduler,
                 event_source_registry,
-                key_registry: KeyRegistry::default(),
+                key_registry,
+                max_horizon,
+                reorder_window,
             };
+            self.is_simulation_active.store(true, Ordering::Relaxed);
         }
 
         Ok(Response::new(reply))
@@ -214,17 +355,23 @@ impl simulation_server::Simulation for GrpcSimulationService {
     async fn halt(&self, request: Request<HaltRequest>) -> Result<Response<HaltReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().halt(request)))
+        Ok(Response::new(
+            self.timed("Halt", || self.scheduler().halt(request)),
+        ))
     }
     async fn time(&self, request: Request<TimeRequest>) -> Result<Response<TimeReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().time(request)))
+        Ok(Response::new(
+            self.timed("Time", || self.scheduler().time(request)),
+        ))
     }
     async fn step(&self, request: Request<StepRequest>) -> Result<Response<StepReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().step(request)))
+        Ok(Response::new(self.timed("Step", || {
+            self.control(|controller| controller.step(request))
+        })))
     }
     async fn step_until(
         &self,
@@ -232,7 +379,9 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<StepUntilReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().step_until(request)))
+        Ok(Response::new(self.timed("StepUntil", || {
+            self.control(|controller| controller.step_until(request))
+        })))
     }
     async fn schedule_event(
         &self,
@@ -240,7 +389,19 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ScheduleEventReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().schedule_event(request)))
+        Ok(Response::new(self.timed("ScheduleEvent", || {
+            self.scheduler().schedule_event(request)
+        })))
+    }
+    async fn schedule_batch(
+        &self,
+        request: Request<ScheduleBatchRequest>,
+    ) -> Result<Response<ScheduleBatchReply>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.timed("ScheduleBatch", || {
+            self.scheduler().schedule_batch(request)
+        })))
     }
     async fn cancel_event(
         &self,
@@ -248,7 +409,9 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<CancelEventReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.scheduler().cancel_event(request)))
+        Ok(Response::new(self.timed("CancelEvent", || {
+            self.scheduler().cancel_event(request)
+        })))
     }
     async fn process_event(
         &self,
@@ -256,7 +419,9 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ProcessEventReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().process_event(request)))
+        Ok(Response::new(self.timed("ProcessEvent", || {
+            self.control(|controller| controller.process_event(request))
+        })))
     }
     async fn process_query(
         &self,
@@ -264,7 +429,9 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ProcessQueryReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.controller().process_query(request)))
+        Ok(Response::new(self.timed("ProcessQuery", || {
+            self.control(|controller| controller.process_query(request))
+        })))
     }
     async fn read_events(
         &self,
@@ -272,7 +439,46 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<ReadEventsReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.monitor().read_events(request)))
+        Ok(Response::new(self.timed("ReadEvents", || {
+            self.monitor().read_events(request)
+        })))
+    }
+    async fn read_events_at_time(
+        &self,
+        request: Request<ReadEventsAtTimeRequest>,
+    ) -> Result<Response<ReadEventsAtTimeReply>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.timed("ReadEventsAtTime", || {
+            // Holding the controller lock prevents the simulation from
+            // advancing while the events are read.
+            let controller = self.controller();
+
+            self.monitor()
+                .read_events_at_time(request, controller.time())
+        })))
+    }
+    async fn sink_state(
+        &self,
+        request: Request<SinkStateRequest>,
+    ) -> Result<Response<SinkStateReply>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(
+            self.timed("SinkState", || self.monitor().sink_state(request)),
+        ))
+    }
+    async fn list_endpoints(
+        &self,
+        request: Request<ListEndpointsRequest>,
+    ) -> Result<Response<ListEndpointsReply>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.timed("ListEndpoints", || {
+            let sinks = self.monitor().sink_endpoints();
+
+            self.controller().list_endpoints(request, sinks)
+        })))
     }
     async fn open_sink(
         &self,
@@ -280,7 +486,9 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<OpenSinkReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.monitor().open_sink(request)))
+        Ok(Response::new(
+            self.timed("OpenSink", || self.monitor().open_sink(request)),
+        ))
     }
     async fn close_sink(
         &self,
@@ -288,6 +496,173 @@ impl simulation_server::Simulation for GrpcSimulationService {
     ) -> Result<Response<CloseSinkReply>, Status> {
         let request = request.into_inner();
 
-        Ok(Response::new(self.monitor().close_sink(request)))
+        Ok(Response::new(
+            self.timed("CloseSink", || self.monitor().close_sink(request)),
+        ))
+    }
+    async fn reset_episode(
+        &self,
+        request: Request<ResetEpisodeRequest>,
+    ) -> Result<Response<ResetEpisodeReply>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.timed("ResetEpisode", || {
+            self.control(|controller| {
+                controller.reset_episode(request, || {
+                    self.monitor().clear_sinks();
+                    self.scheduler().clear_keys();
+                })
+            })
+        })))
+    }
+    async fn clear_schedule(
+        &self,
+        request: Request<ClearScheduleRequest>,
+    ) -> Result<Response<ClearScheduleReply>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.timed("ClearSchedule", || {
+            self.controller()
+                .clear_schedule(request, || self.scheduler().clear_keys())
+        })))
+    }
+    async fn step_events(
+        &self,
+        request: Request<StepEventsRequest>,
+    ) -> Result<Response<StepEventsReply>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.timed("StepEvents", || {
+            self.control(|controller| controller.step_events(request))
+        })))
+    }
+    type SubscribeSinkStream = SinkEventStream;
+
+    async fn subscribe_sink(
+        &self,
+        request: Request<SubscribeSinkRequest>,
+    ) -> Result<Response<SinkEventStream>, Status> {
+        let request = request.into_inner();
+
+        Ok(Response::new(self.timed("SubscribeSink", || {
+            self.monitor().subscribe_sink(request)
+        })))
+    }
+    async fn batch(&self, request: Request<BatchRequest>) -> Result<Response<BatchReply>, Status> {
+        let request = request.into_inner();
+
+        // Make sure that no request is processed if the batch is malformed.
+        if let Some(index) = request
+            .requests
+            .iter()
+            .position(|any_request| any_request.request.is_none())
+        {
+            return Ok(Response::new(BatchReply {
+                replies: Vec::new(),
+                result: Some(batch_reply::Result::Error(Error {
+                    code: ErrorCode::MissingArgument as i32,
+                    message: format!("missing request at index {}", index),
+                })),
+            }));
+        }
+
+        let mut replies = Vec::with_capacity(request.requests.len());
+        for any_request in request.requests {
+            let reply = self.process_any(any_request.request.unwrap()).await?;
+            let is_error = is_error(&reply);
+            replies.push(AnyReply { reply: Some(reply) });
+
+            if is_error && request.stop_on_error {
+                break;
+            }
+        }
+
+        Ok(Response::new(BatchReply {
+            replies,
+            result: Some(batch_reply::Result::Empty(())),
+        }))
+    }
+    async fn request_metrics(
+        &self,
+        _request: Request<RequestMetricsRequest>,
+    ) -> Result<Response<RequestMetricsReply>, Status> {
+        Ok(Response::new(self.request_metrics.lock().unwrap().reply()))
+    }
+    async fn ping(&self, _request: Request<PingRequest>) -> Result<Response<PingReply>, Status> {
+        // The services are deliberately left untouched so that the server
+        // answers even while a simulation step holds their locks.
+        Ok(Response::new(PingReply {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            simulation_active: self.is_simulation_active.load(Ordering::Relaxed),
+            result: Some(ping_reply::Result::Empty(())),
+        }))
+    }
+}
+
+/// Checks whether a reply reports an error.
+fn is_error(reply: &any_reply::Reply) -> bool {
+    use any_reply::Reply;
+
+    match reply {
+        Reply::InitReply(reply) => matches!(reply.result, Some(init_reply::Result::Error(_))),
+        Reply::HaltReply(reply) => matches!(reply.result, Some(halt_reply::Result::Error(_))),
+        Reply::TimeReply(reply) => matches!(reply.result, Some(time_reply::Result::Error(_))),
+        Reply::StepReply(reply) => matches!(reply.result, Some(step_reply::Result::Error(_))),
+        Reply::StepUntilReply(reply) => {
+            matches!(reply.result, Some(step_until_reply::Result::Error(_)))
+        }
+        Reply::ScheduleEventReply(reply) => {
+            matches!(reply.result, Some(schedule_event_reply::Result::Error(_)))
+        }
+        Reply::CancelEventReply(reply) => {
+            matches!(reply.result, Some(cancel_event_reply::Result::Error(_)))
+        }
+        Reply::ProcessEventReply(reply) => {
+            matches!(reply.result, Some(process_event_reply::Result::Error(_)))
+        }
+        Reply::ProcessQueryReply(reply) => {
+            matches!(reply.result, Some(process_query_reply::Result::Error(_)))
+        }
+        Reply::ReadEventsReply(reply) => {
+            matches!(reply.result, Some(read_events_reply::Result::Error(_)))
+        }
+        Reply::OpenSinkReply(reply) => {
+            matches!(reply.result, Some(open_sink_reply::Result::Error(_)))
+        }
+        Reply::CloseSinkReply(reply) => {
+            matches!(reply.result, Some(close_sink_reply::Result::Error(_)))
+        }
+        Reply::RequestMetricsReply(reply) => {
+            matches!(reply.result, Some(request_metrics_reply::Result::Error(_)))
+        }
+        Reply::ReadEventsAtTimeReply(reply) => matches!(
+            reply.result,
+            Some(read_events_at_time_reply::Result::Error(_))
+        ),
+        Reply::SinkStateReply(reply) => {
+            matches!(reply.result, Some(sink_state_reply::Result::Error(_)))
+        }
+        Reply::ResetEpisodeReply(reply) => {
+            matches!(reply.result, Some(reset_episode_reply::Result::Error(_)))
+        }
+        Reply::BatchReply(reply) => matches!(reply.result, Some(batch_reply::Result::Error(_))),
+        Reply::ScheduleBatchReply(reply) => {
+            matches!(reply.result, Some(schedule_batch_reply::Result::Error(_)))
+        }
+        Reply::ListEndpointsReply(reply) => {
+            matches!(reply.result, Some(list_endpoints_reply::Result::Error(_)))
+        }
+        Reply::ClearScheduleReply(reply) => {
+            matches!(reply.result, Some(clear_schedule_reply::Result::Error(_)))
+        }
+        Reply::StepEventsReply(reply) => {
+            matches!(reply.result, Some(step_events_reply::Result::Error(_)))
+        }
+        Reply::PingReply(reply) => {
+            matches!(reply.result, Some(ping_reply::Result::Error(_)))
+        }
     }
 }
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests;
@@ -0,0 +1,649 @@
+//! Tests of the simulation server through its remote procedure calls.
+
+use std::time::Duration;
+
+use futures_executor::block_on;
+use futures_util::{FutureExt, StreamExt};
+use serde::Serialize;
+
+use crate::model::Model;
+use crate::ports::{EventBuffer, EventSource, Output, QuerySource};
+use crate::registry::EndpointRegistry;
+use crate::simulation::{Mailbox, Scheduler, SimInit, Simulation, SimulationError};
+use crate::time::MonotonicTime;
+
+use super::*;
+
+use simulation_server::Simulation as _;
+
+/// A model that accumulates the received increments and broadcasts the
+/// running total.
+#[derive(Default)]
+struct Counter {
+    total: u64,
+    output: Output<u64>,
+}
+impl Counter {
+    async fn increment(&mut self, by: u64) {
+        self.total += by;
+        self.output.send(self.total).await;
+    }
+    async fn total(&mut self, _: ()) -> u64 {
+        self.total
+    }
+}
+impl Model for Counter {}
+
+/// Builds a bench with a single `Counter` model.
+///
+/// The registry exposes an `increment` event source, a `total` query source
+/// and a `totals` event sink.
+fn counter_bench() -> Result<(Simulation, Scheduler, EndpointRegistry), SimulationError> {
+    let mut model = Counter::default();
+    let mbox = Mailbox::new();
+
+    let mut increment = EventSource::new();
+    increment.connect(Counter::increment, &mbox);
+    let mut total = QuerySource::new();
+    total.connect(Counter::total, &mbox);
+    let totals = EventBuffer::new();
+    model.output.connect_sink(&totals);
+
+    let mut registry = EndpointRegistry::new();
+    registry.add_event_source(increment, "increment").unwrap();
+    registry.add_query_source(total, "total").unwrap();
+    registry.add_event_sink(totals, "totals").unwrap();
+
+    let (simu, scheduler) = SimInit::new()
+        .add_model(model, mbox, "counter")
+        .init(MonotonicTime::EPOCH)?;
+
+    Ok((simu, scheduler, registry))
+}
+
+/// Encodes a value in CBOR.
+fn to_cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    ciborium::into_writer(value, &mut buffer).unwrap();
+
+    buffer
+}
+
+/// Initializes the simulation of a service with a unit configuration.
+fn init(service: &GrpcSimulationService) {
+    let reply = block_on(service.init(Request::new(InitRequest {
+        cfg: to_cbor(&()),
+        reorder_window: None,
+    })))
+    .unwrap()
+    .into_inner();
+
+    assert_eq!(reply.result, Some(init_reply::Result::Empty(())));
+}
+
+/// Advances the simulation of a service to the next scheduled event.
+fn step(service: &GrpcSimulationService) -> StepReply {
+    block_on(service.step(Request::new(StepRequest {})))
+        .unwrap()
+        .into_inner()
+}
+
+/// Builds a request scheduling an increment of the counter after the specified
+/// number of seconds.
+fn increment_request(secs: i64, by: u64) -> ScheduleEventRequest {
+    ScheduleEventRequest {
+        deadline: Some(schedule_event_request::Deadline::Duration(
+            prost_types::Duration {
+                seconds: secs,
+                nanos: 0,
+            },
+        )),
+        source_name: "increment".to_string(),
+        event: to_cbor(&by),
+        ..Default::default()
+    }
+}
+
+/// Schedules an event with the scheduler of a service.
+fn schedule_event(
+    service: &GrpcSimulationService,
+    request: ScheduleEventRequest,
+) -> ScheduleEventReply {
+    block_on(service.schedule_event(Request::new(request)))
+        .unwrap()
+        .into_inner()
+}
+
+/// Returns the total accumulated by the counter.
+fn total(service: &GrpcSimulationService) -> u64 {
+    let reply = block_on(service.process_query(Request::new(ProcessQueryRequest {
+        source_name: "total".to_string(),
+        request: to_cbor(&()),
+        ..Default::default()
+    })))
+    .unwrap()
+    .into_inner();
+
+    assert_eq!(reply.result, Some(process_query_reply::Result::Empty(())));
+    assert_eq!(reply.replies.len(), 1);
+
+    ciborium::from_reader(reply.replies[0].as_slice()).unwrap()
+}
+
+#[test]
+fn cancel_in_process_key() {
+    let event_key = Arc::new(Mutex::new(None));
+
+    let service = GrpcSimulationService::new({
+        let event_key = event_key.clone();
+
+        move |_: ()| -> Result<(Simulation, EndpointRegistry), SimulationError> {
+            let (simu, scheduler, registry) = counter_bench()?;
+
+            let source = registry.event_source::<u64>("increment").unwrap();
+            let (action, action_key) = source.keyed_event(1);
+            scheduler.schedule(Duration::from_secs(1), action)?;
+            *event_key.lock().unwrap() =
+                Some(action_key.to_event_key(&registry.action_key_registry()));
+
+            Ok((simu, registry))
+        }
+    });
+    init(&service);
+
+    let key = event_key.lock().unwrap().unwrap();
+    let reply = block_on(service.cancel_event(Request::new(CancelEventRequest {
+        key: Some(EventKey {
+            subkey1: key.subkey1,
+            subkey2: key.subkey2,
+        }),
+    })))
+    .unwrap()
+    .into_inner();
+    assert_eq!(reply.result, Some(cancel_event_reply::Result::Empty(())));
+
+    // The key was extracted by the cancellation.
+    let reply = block_on(service.cancel_event(Request::new(CancelEventRequest {
+        key: Some(EventKey {
+            subkey1: key.subkey1,
+            subkey2: key.subkey2,
+        }),
+    })))
+    .unwrap()
+    .into_inner();
+    match reply.result {
+        Some(cancel_event_reply::Result::Error(error)) => {
+            assert_eq!(error.code, ErrorCode::InvalidKey as i32)
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    step(&service);
+    assert_eq!(total(&service), 0);
+}
+
+#[test]
+fn remove_in_process_key() {
+    let (_simu, scheduler, registry) = counter_bench().unwrap();
+    let key_registry = registry.action_key_registry();
+
+    let source = registry.event_source::<u64>("increment").unwrap();
+    let (action, action_key) = source.keyed_event(1);
+    scheduler.schedule(Duration::from_secs(1), action).unwrap();
+
+    let event_key = action_key.to_event_key(&key_registry);
+    assert_eq!(key_registry.remove(event_key), Some(action_key));
+    assert!(key_registry.get(event_key).is_none());
+    assert!(key_registry.remove(event_key).is_none());
+}
+
+#[test]
+fn max_scheduling_horizon() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let (simu, _, mut registry) = counter_bench()?;
+        registry.set_max_scheduling_horizon(Duration::from_secs(10));
+
+        Ok((simu, registry))
+    });
+    init(&service);
+
+    // An event scheduled exactly at the horizon is accepted.
+    let reply = schedule_event(&service, increment_request(10, 1));
+    assert_eq!(reply.result, Some(schedule_event_reply::Result::Empty(())));
+
+    let reply = schedule_event(&service, increment_request(11, 2));
+    assert!(reply.scheduled_time.is_none());
+    match reply.result {
+        Some(schedule_event_reply::Result::Error(error)) => {
+            assert_eq!(error.code, ErrorCode::HorizonExceeded as i32)
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    // No event of a batch is scheduled if one of them lies beyond the horizon.
+    let reply = block_on(service.schedule_batch(Request::new(ScheduleBatchRequest {
+        requests: vec![increment_request(5, 4), increment_request(11, 8)],
+    })))
+    .unwrap()
+    .into_inner();
+    assert!(reply.replies.is_empty());
+    match reply.result {
+        Some(schedule_batch_reply::Result::Error(error)) => {
+            assert_eq!(error.code, ErrorCode::HorizonExceeded as i32)
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    step(&service);
+    assert_eq!(total(&service), 1);
+
+    // The horizon is relative to the current simulation time.
+    let reply = schedule_event(&service, increment_request(10, 16));
+    assert_eq!(reply.result, Some(schedule_event_reply::Result::Empty(())));
+    step(&service);
+    assert_eq!(total(&service), 17);
+}
+
+#[test]
+fn reorder_window_with_base_time() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let (simu, _, registry) = counter_bench()?;
+
+        Ok((simu, registry))
+    });
+    let reply = block_on(service.init(Request::new(InitRequest {
+        cfg: to_cbor(&()),
+        reorder_window: Some(prost_types::Duration {
+            seconds: 2,
+            nanos: 0,
+        }),
+    })))
+    .unwrap()
+    .into_inner();
+    assert_eq!(reply.result, Some(init_reply::Result::Empty(())));
+
+    schedule_event(&service, increment_request(5, 1));
+    step(&service);
+
+    let t5 = prost_types::Timestamp {
+        seconds: 5,
+        nanos: 0,
+    };
+    let late_request = |base_secs: i64, by: u64| ScheduleEventRequest {
+        base_time: Some(prost_types::Timestamp {
+            seconds: base_secs,
+            nanos: 0,
+        }),
+        ..increment_request(1, by)
+    };
+
+    // A deadline 1s in the past lies within the reorder window and is moved
+    // to the current simulation time.
+    let reply = schedule_event(&service, late_request(3, 2));
+    assert_eq!(reply.result, Some(schedule_event_reply::Result::Empty(())));
+    assert_eq!(reply.scheduled_time, Some(t5));
+
+    // A deadline 3s in the past lies outside the reorder window.
+    let reply = schedule_event(&service, late_request(1, 4));
+    assert!(reply.scheduled_time.is_none());
+    match reply.result {
+        Some(schedule_event_reply::Result::Error(error)) => {
+            assert_eq!(error.code, ErrorCode::InvalidDeadline as i32)
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    step(&service);
+    assert_eq!(total(&service), 3);
+}
+
+#[test]
+fn subscribe_sink() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let (simu, _, registry) = counter_bench()?;
+
+        Ok((simu, registry))
+    });
+    init(&service);
+
+    let subscribe = |sink_name: &str| {
+        block_on(service.subscribe_sink(Request::new(SubscribeSinkRequest {
+            sink_name: sink_name.to_string(),
+        })))
+        .unwrap()
+        .into_inner()
+    };
+    let read_events = || {
+        block_on(service.read_events(Request::new(ReadEventsRequest {
+            sink_name: "totals".to_string(),
+        })))
+        .unwrap()
+        .into_inner()
+    };
+
+    // Unknown sinks yield a single error before the stream ends.
+    let mut stream = subscribe("unknown");
+    match block_on(stream.next()) {
+        Some(Ok(SubscribeSinkReply {
+            result: Some(subscribe_sink_reply::Result::Error(error)),
+            ..
+        })) => assert_eq!(error.code, ErrorCode::SinkNotFound as i32),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert!(block_on(stream.next()).is_none());
+
+    // Events are streamed after each controller request and consumed by the
+    // subscription.
+    let mut stream = subscribe("totals");
+    schedule_event(&service, increment_request(1, 1));
+    schedule_event(&service, increment_request(2, 2));
+    step(&service);
+    let reply = block_on(stream.next()).unwrap().unwrap();
+    assert_eq!(reply.result, Some(subscribe_sink_reply::Result::Empty(())));
+    assert_eq!(reply.events, vec![to_cbor(&1u64)]);
+    assert!(read_events().events.is_empty());
+
+    // Nothing is streamed if no event was collected.
+    total(&service);
+    assert!(stream.next().now_or_never().is_none());
+
+    // Events are no longer drained once the stream is dropped.
+    drop(stream);
+    step(&service);
+    assert_eq!(read_events().events, vec![to_cbor(&3u64)]);
+}
+
+#[test]
+fn batch() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let (simu, _, registry) = counter_bench()?;
+
+        Ok((simu, registry))
+    });
+    init(&service);
+
+    let batch = |requests: Vec<any_request::Request>, stop_on_error: bool| {
+        let requests = requests
+            .into_iter()
+            .map(|request| AnyRequest {
+                request: Some(request),
+            })
+            .collect();
+
+        block_on(service.batch(Request::new(BatchRequest {
+            requests,
+            stop_on_error,
+        })))
+        .unwrap()
+        .into_inner()
+    };
+    let requests = || {
+        vec![
+            any_request::Request::ScheduleEventRequest(increment_request(1, 1)),
+            any_request::Request::ScheduleEventRequest(ScheduleEventRequest {
+                source_name: "unknown".to_string(),
+                ..increment_request(1, 2)
+            }),
+            any_request::Request::StepRequest(StepRequest {}),
+        ]
+    };
+
+    // No request is processed if the batch is malformed.
+    let reply = block_on(service.batch(Request::new(BatchRequest {
+        requests: vec![
+            AnyRequest {
+                request: Some(any_request::Request::ScheduleEventRequest(
+                    increment_request(1, 4),
+                )),
+            },
+            AnyRequest { request: None },
+        ],
+        stop_on_error: false,
+    })))
+    .unwrap()
+    .into_inner();
+    assert!(reply.replies.is_empty());
+    match reply.result {
+        Some(batch_reply::Result::Error(error)) => {
+            assert_eq!(error.code, ErrorCode::MissingArgument as i32)
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    // Requests are processed in order up to the first error.
+    let reply = batch(requests(), true);
+    assert_eq!(reply.result, Some(batch_reply::Result::Empty(())));
+    let replies: Vec<_> = reply
+        .replies
+        .into_iter()
+        .map(|reply| reply.reply.unwrap())
+        .collect();
+    assert_eq!(replies.len(), 2);
+    assert!(!is_error(&replies[0]));
+    match &replies[1] {
+        any_reply::Reply::ScheduleEventReply(ScheduleEventReply {
+            result: Some(schedule_event_reply::Result::Error(error)),
+            ..
+        }) => assert_eq!(error.code, ErrorCode::SourceNotFound as i32),
+        reply => panic!("unexpected reply: {:?}", reply),
+    }
+    assert!(is_error(&replies[1]));
+    assert_eq!(total(&service), 0);
+
+    // All requests are processed if errors do not stop the batch.
+    let reply = batch(requests(), false);
+    assert_eq!(reply.result, Some(batch_reply::Result::Empty(())));
+    let replies: Vec<_> = reply
+        .replies
+        .into_iter()
+        .map(|reply| reply.reply.unwrap())
+        .collect();
+    assert_eq!(replies.len(), 3);
+    assert!(!is_error(&replies[0]));
+    assert!(is_error(&replies[1]));
+    assert!(matches!(replies[2], any_reply::Reply::StepReply(_)));
+    assert!(!is_error(&replies[2]));
+
+    // Both the event of the first batch and that of the second one are
+    // processed by the step.
+    assert_eq!(total(&service), 2);
+}
+
+#[test]
+fn json_encoding() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let (simu, _, registry) = counter_bench()?;
+
+        Ok((simu, registry))
+    });
+    init(&service);
+
+    let process_event = |event: &[u8]| {
+        block_on(service.process_event(Request::new(ProcessEventRequest {
+            source_name: "increment".to_string(),
+            event: event.to_vec(),
+            encoding: Encoding::Json as i32,
+        })))
+        .unwrap()
+        .into_inner()
+    };
+
+    let reply = process_event(b"3");
+    assert_eq!(reply.result, Some(process_event_reply::Result::Empty(())));
+
+    // A payload that is not valid JSON is rejected.
+    let reply = process_event(&to_cbor(&4u64));
+    match reply.result {
+        Some(process_event_reply::Result::Error(error)) => {
+            assert_eq!(error.code, ErrorCode::InvalidMessage as i32)
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    // Query replies are encoded like the request.
+    let reply = block_on(service.process_query(Request::new(ProcessQueryRequest {
+        source_name: "total".to_string(),
+        request: b"null".to_vec(),
+        encoding: Encoding::Json as i32,
+        ..Default::default()
+    })))
+    .unwrap()
+    .into_inner();
+    assert_eq!(reply.result, Some(process_query_reply::Result::Empty(())));
+    assert_eq!(reply.replies, vec![b"3".to_vec()]);
+}
+
+#[test]
+fn sink_state() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let (simu, _, registry) = counter_bench()?;
+
+        Ok((simu, registry))
+    });
+    init(&service);
+
+    let sink_states = || {
+        let reply = block_on(service.sink_state(Request::new(SinkStateRequest {})))
+            .unwrap()
+            .into_inner();
+        assert_eq!(reply.result, Some(sink_state_reply::Result::Empty(())));
+
+        reply.sink_states
+    };
+    let totals_state = |buffered_count| SinkState {
+        sink_name: "totals".to_string(),
+        is_open: true,
+        buffered_count,
+    };
+
+    assert_eq!(sink_states(), vec![totals_state(0)]);
+
+    // Events are counted without being drained.
+    schedule_event(&service, increment_request(1, 1));
+    schedule_event(&service, increment_request(1, 2));
+    step(&service);
+    assert_eq!(sink_states(), vec![totals_state(2)]);
+    assert_eq!(sink_states(), vec![totals_state(2)]);
+}
+
+/// A model with a loopback connection that never completes a query
+/// requesting a loop.
+#[derive(Default)]
+struct Looper {
+    output: Output<()>,
+}
+impl Looper {
+    async fn input(&mut self) {
+        self.output.send(()).await;
+    }
+    async fn probe(&mut self, looping: bool) -> bool {
+        if looping {
+            self.output.send(()).await;
+        }
+
+        true
+    }
+}
+impl Model for Looper {}
+
+#[test]
+fn query_timeout_override() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let mut model = Looper::default();
+        let mbox = Mailbox::new();
+        model.output.connect(Looper::input, &mbox);
+
+        let mut probe = QuerySource::new();
+        probe.connect(Looper::probe, &mbox);
+        let mut registry = EndpointRegistry::new();
+        registry.add_query_source(probe, "probe").unwrap();
+
+        let (simu, _) = SimInit::new()
+            .add_model(model, mbox, "looper")
+            .init(MonotonicTime::EPOCH)?;
+
+        Ok((simu, registry))
+    });
+    init(&service);
+
+    let probe = |looping: bool, timeout_millis: i64| {
+        let timeout = prost_types::Duration {
+            seconds: timeout_millis / 1000,
+            nanos: (timeout_millis % 1000) as i32 * 1_000_000,
+        };
+
+        block_on(service.process_query(Request::new(ProcessQueryRequest {
+            source_name: "probe".to_string(),
+            request: to_cbor(&looping),
+            timeout: Some(timeout),
+            ..Default::default()
+        })))
+        .unwrap()
+        .into_inner()
+    };
+    let assert_error = |reply: ProcessQueryReply, code: ErrorCode| {
+        assert!(reply.replies.is_empty());
+        match reply.result {
+            Some(process_query_reply::Result::Error(error)) => {
+                assert_eq!(error.code, code as i32)
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    };
+
+    // A negative timeout is rejected without processing the query.
+    let reply = probe(false, -100);
+    assert_error(reply, ErrorCode::InvalidMessage);
+
+    let reply = probe(false, 1000);
+    assert_eq!(reply.result, Some(process_query_reply::Result::Empty(())));
+    assert_eq!(reply.replies, vec![to_cbor(&true)]);
+
+    // No timeout is configured on the simulation, so the query only completes
+    // thanks to the per-request timeout.
+    let reply = probe(true, 100);
+    assert_error(reply, ErrorCode::QueryTimeout);
+
+    let reply = probe(false, 1000);
+    assert_error(reply, ErrorCode::SimulationTerminated);
+}
+
+#[test]
+fn ping() {
+    let service = GrpcSimulationService::new(|_: ()| {
+        let (simu, _, registry) = counter_bench()?;
+
+        Ok((simu, registry))
+    });
+
+    let ping = || {
+        let reply = block_on(service.ping(Request::new(PingRequest {})))
+            .unwrap()
+            .into_inner();
+        assert_eq!(reply.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(reply.result, Some(ping_reply::Result::Empty(())));
+
+        reply.simulation_active
+    };
+
+    assert!(!ping());
+
+    init(&service);
+    assert!(ping());
+
+    // A halted simulation is terminated by the next step.
+    schedule_event(&service, increment_request(1, 1));
+    block_on(service.halt(Request::new(HaltRequest {}))).unwrap();
+    assert!(ping());
+    match step(&service).result {
+        Some(step_reply::Result::Error(error)) => {
+            assert_eq!(error.code, ErrorCode::SimulationHalted as i32)
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+    assert!(!ping());
+
+    // The simulation is active again once re-initialized.
+    init(&service);
+    assert!(ping());
+}
@@ -8,12 +8,13 @@ use std::time::Duration;
 use prost_types::Timestamp;
 use tai_time::MonotonicTime;
 
-use super::codegen::simulation::{Error, ErrorCode};
+use super::codegen::simulation::{Encoding, Error, ErrorCode};
+use crate::registry::Codec;
 use crate::simulation::{ExecutionError, SchedulingError, SimulationError};
 
 pub(crate) use controller_service::ControllerService;
 pub(crate) use init_service::InitService;
-pub(crate) use monitor_service::MonitorService;
+pub(crate) use monitor_service::{MonitorService, SinkEventStream};
 pub(crate) use scheduler_service::SchedulerService;
 
 /// Transforms an error code and a message into a Protobuf error.
@@ -40,11 +41,14 @@ fn map_execution_error(error: ExecutionError) -> Error {
         ExecutionError::NoRecipient { .. } => ErrorCode::SimulationNoRecipient,
         ExecutionError::Panic { .. } => ErrorCode::SimulationPanic,
         ExecutionError::Timeout => ErrorCode::SimulationTimeout,
+        ExecutionError::PollBudgetExceeded => ErrorCode::SimulationPollBudgetExceeded,
         ExecutionError::OutOfSync(_) => ErrorCode::SimulationOutOfSync,
-        ExecutionError::BadQuery => ErrorCode::SimulationBadQuery,
+        ExecutionError::BadQuery(_) => ErrorCode::SimulationBadQuery,
         ExecutionError::Halted => ErrorCode::SimulationHalted,
         ExecutionError::Terminated => ErrorCode::SimulationTerminated,
         ExecutionError::InvalidDeadline(_) => ErrorCode::InvalidDeadline,
+        ExecutionError::InitScheduledInPast { .. } => ErrorCode::InvalidTime,
+        ExecutionError::UnconnectedPorts(_) => ErrorCode::SimulationUnconnectedPorts,
     };
 
     let error_message = error.to_string();
@@ -57,6 +61,8 @@ fn map_scheduling_error(error: SchedulingError) -> Error {
     let error_code = match error {
         SchedulingError::InvalidScheduledTime => ErrorCode::InvalidDeadline,
         SchedulingError::NullRepetitionPeriod => ErrorCode::InvalidPeriod,
+        SchedulingError::TimeOverflow => ErrorCode::InvalidTime,
+        SchedulingError::NoRecipient => ErrorCode::SimulationNoRecipient,
     };
 
     let error_message = error.to_string();
@@ -72,6 +78,18 @@ fn map_simulation_error(error: SimulationError) -> Error {
     }
 }
 
+/// Attempts a cast from a Protobuf encoding to a `Codec`.
+fn to_codec(encoding: i32) -> Result<Codec, Error> {
+    match Encoding::try_from(encoding) {
+        Ok(Encoding::Cbor) => Ok(Codec::Cbor),
+        Ok(Encoding::Json) => Ok(Codec::Json),
+        Err(_) => Err(to_error(
+            ErrorCode::InvalidMessage,
+            "the specified encoding is not supported",
+        )),
+    }
+}
+
 /// Attempts a cast from a `MonotonicTime` to a protobuf `Timestamp`.
 ///
 /// This will fail if the time is outside the protobuf-specified range for
@@ -4,12 +4,13 @@ use std::sync::Arc;
 use prost_types::Timestamp;
 
 use crate::registry::{EventSourceRegistry, QuerySourceRegistry};
-use crate::simulation::Simulation;
+use crate::simulation::{ExecutionError, Simulation};
+use crate::time::MonotonicTime;
 
 use super::super::codegen::simulation::*;
 use super::{
     map_execution_error, monotonic_to_timestamp, simulation_not_started_error,
-    timestamp_to_monotonic, to_error, to_positive_duration,
+    timestamp_to_monotonic, to_codec, to_error, to_positive_duration,
 };
 
 /// Protobuf-based simulation controller.
@@ -27,6 +28,24 @@ pub(crate) enum ControllerService {
 }
 
 impl ControllerService {
+    /// Returns the current simulation time, or `None` if the simulation was not
+    /// started.
+    pub(crate) fn time(&self) -> Option<MonotonicTime> {
+        match self {
+            Self::Started { simulation, .. } => Some(simulation.time()),
+            Self::NotStarted => None,
+        }
+    }
+
+    /// Reports whether the simulation was started and was not terminated
+    /// since.
+    pub(crate) fn is_active(&self) -> bool {
+        match self {
+            Self::Started { simulation, .. } => !simulation.is_terminated(),
+            Self::NotStarted => false,
+        }
+    }
+
     /// Advances simulation time to that of the next scheduled event, processing
     /// that event as well as all other events scheduled for the same time.
     ///
@@ -37,7 +56,7 @@ impl ControllerService {
     pub(crate) fn step(&mut self, _request: StepRequest) -> StepReply {
         let reply = match self {
             Self::Started { simulation, .. } => match simulation.step() {
-                Ok(()) => {
+                Ok(_) => {
                     if let Some(timestamp) = monotonic_to_timestamp(simulation.time()) {
                         step_reply::Result::Time(timestamp)
                     } else {
@@ -80,11 +99,12 @@ impl ControllerService {
                             "out-of-range nanosecond field",
                         ))?;
 
-                        simulation.step_until(time).map_err(|_| {
-                            to_error(
+                        simulation.step_until(time).map_err(|e| match e {
+                            ExecutionError::InvalidDeadline(_) => to_error(
                                 ErrorCode::InvalidDeadline,
                                 "the specified deadline lies in the past",
-                            )
+                            ),
+                            e => map_execution_error(e),
                         })?;
                     }
                     step_until_request::Deadline::Duration(duration) => {
@@ -117,6 +137,153 @@ impl ControllerService {
         }
     }
 
+    /// Processes the next scheduled events one at a time, as if by calling
+    /// [`Simulation::step_events`](crate::simulation::Simulation::step_events).
+    ///
+    /// The number of processed events is returned together with the final
+    /// simulation time.
+    pub(crate) fn step_events(&mut self, request: StepEventsRequest) -> StepEventsReply {
+        let reply = match self {
+            Self::Started { simulation, .. } => move || -> Result<(u64, Timestamp), Error> {
+                let max_events = request.count.try_into().unwrap_or(usize::MAX);
+                let event_count = simulation
+                    .step_events(max_events)
+                    .map_err(map_execution_error)?;
+
+                let timestamp = monotonic_to_timestamp(simulation.time()).ok_or(to_error(
+                    ErrorCode::SimulationTimeOutOfRange,
+                    "the final simulation time is out of range",
+                ))?;
+
+                Ok((event_count as u64, timestamp))
+            }(),
+            Self::NotStarted => Err(simulation_not_started_error()),
+        };
+
+        match reply {
+            Ok((processed_count, timestamp)) => StepEventsReply {
+                processed_count,
+                result: Some(step_events_reply::Result::Time(timestamp)),
+            },
+            Err(error) => StepEventsReply {
+                processed_count: 0,
+                result: Some(step_events_reply::Result::Error(error)),
+            },
+        }
+    }
+
+    /// Lists the event and query sources of the bench together with the
+    /// provided event sinks, each sorted by name.
+    pub(crate) fn list_endpoints(
+        &self,
+        _request: ListEndpointsRequest,
+        sinks: Vec<SinkEndpoint>,
+    ) -> ListEndpointsReply {
+        match self {
+            Self::Started {
+                event_source_registry,
+                query_source_registry,
+                ..
+            } => {
+                let mut event_sources: Vec<_> = event_source_registry
+                    .iter()
+                    .map(|(source_name, source)| EventSourceEndpoint {
+                        name: source_name.to_string(),
+                        event_type: source.event_type_name().to_string(),
+                    })
+                    .collect();
+                event_sources.sort_by(|a, b| a.name.cmp(&b.name));
+
+                let mut query_sources: Vec<_> = query_source_registry
+                    .iter()
+                    .map(|(source_name, source)| QuerySourceEndpoint {
+                        name: source_name.to_string(),
+                        request_type: source.request_type_name().to_string(),
+                        reply_type: source.reply_type_name().to_string(),
+                    })
+                    .collect();
+                query_sources.sort_by(|a, b| a.name.cmp(&b.name));
+
+                ListEndpointsReply {
+                    event_sources,
+                    query_sources,
+                    sinks,
+                    result: Some(list_endpoints_reply::Result::Empty(())),
+                }
+            }
+            Self::NotStarted => ListEndpointsReply {
+                event_sources: Vec::new(),
+                query_sources: Vec::new(),
+                sinks: Vec::new(),
+                result: Some(list_endpoints_reply::Result::Error(
+                    simulation_not_started_error(),
+                )),
+            },
+        }
+    }
+
+    /// Starts a new episode with the same models and connections, as if by
+    /// calling [`Simulation::reset`](crate::simulation::Simulation::reset).
+    ///
+    /// The provided closure is called once the request has been validated but
+    /// before the models are reset, so that the event sinks and the key
+    /// registry can be cleared at the same step boundary.
+    pub(crate) fn reset_episode(
+        &mut self,
+        request: ResetEpisodeRequest,
+        clear_endpoints: impl FnOnce(),
+    ) -> ResetEpisodeReply {
+        let reply = match self {
+            Self::Started { simulation, .. } => move || -> Result<(), Error> {
+                let start_time = request.start_time.ok_or(to_error(
+                    ErrorCode::MissingArgument,
+                    "missing start time argument",
+                ))?;
+                let start_time = timestamp_to_monotonic(start_time).ok_or(to_error(
+                    ErrorCode::InvalidTime,
+                    "out-of-range nanosecond field",
+                ))?;
+
+                clear_endpoints();
+
+                simulation.reset(start_time).map_err(map_execution_error)
+            }(),
+            Self::NotStarted => Err(simulation_not_started_error()),
+        };
+
+        ResetEpisodeReply {
+            result: Some(match reply {
+                Ok(()) => reset_episode_reply::Result::Empty(()),
+                Err(error) => reset_episode_reply::Result::Error(error),
+            }),
+        }
+    }
+
+    /// Cancels all scheduled events, as if by calling
+    /// [`Simulation::clear_schedule`](crate::simulation::Simulation::clear_schedule).
+    ///
+    /// The provided closure is called once the events are cancelled so that
+    /// the key registry can be cleared as well.
+    pub(crate) fn clear_schedule(
+        &mut self,
+        _request: ClearScheduleRequest,
+        clear_keys: impl FnOnce(),
+    ) -> ClearScheduleReply {
+        let reply = match self {
+            Self::Started { simulation, .. } => {
+                let cancelled_count = simulation.clear_schedule();
+                clear_keys();
+
+                clear_schedule_reply::Result::CancelledCount(cancelled_count as u64)
+            }
+            Self::NotStarted => clear_schedule_reply::Result::Error(simulation_not_started_error()),
+        };
+
+        ClearScheduleReply {
+            result: Some(reply),
+        }
+    }
+
     /// Broadcasts an event from an event source immediately, blocking until
     /// completion.
     ///
@@ -130,13 +297,14 @@ impl ControllerService {
             } => move || -> Result<(), Error> {
                 let source_name = &request.source_name;
                 let event = &request.event;
+                let codec = to_codec(request.encoding)?;
 
                 let source = event_source_registry.get(source_name).ok_or(to_error(
                     ErrorCode::SourceNotFound,
                     "no source is registered with the name '{}'".to_string(),
                 ))?;
 
-                let event = source.event(event).map_err(|e| {
+                let event = source.event(event, codec).map_err(|e| {
                     to_error(
                         ErrorCode::InvalidMessage,
                         format!(
@@ -165,13 +333,25 @@ impl ControllerService {
     ///
     /// Simulation time remains unchanged.
     pub(crate) fn process_query(&mut self, request: ProcessQueryRequest) -> ProcessQueryReply {
+        let with_model_names = request.with_model_names;
         let reply = match self {
             Self::Started {
                 simulation,
                 query_source_registry,
                 ..
-            } => move || -> Result<Vec<Vec<u8>>, Error> {
+            } => move || -> Result<Vec<(Arc<str>, Vec<u8>)>, Error> {
                 let source_name = &request.source_name;
+                let codec = to_codec(request.encoding)?;
+                let timeout = request
+                    .timeout
+                    .map(|timeout| {
+                        to_positive_duration(timeout).ok_or(to_error(
+                            ErrorCode::InvalidMessage,
+                            "the specified query timeout is negative",
+                        ))
+                    })
+                    .transpose()?
+                    .filter(|timeout| !timeout.is_zero());
                 let request = &request.request;
 
                 let source = query_source_registry.get(source_name).ok_or(to_error(
@@ -179,7 +359,7 @@ impl ControllerService {
                     "no source is registered with the name '{}'".to_string(),
                 ))?;
 
-                let (query, mut promise) = source.query(request).map_err(|e| {
+                let (query, mut promise) = source.query(request, codec).map_err(|e| {
                     to_error(
                         ErrorCode::InvalidMessage,
                         format!(
@@ -190,7 +370,15 @@ impl ControllerService {
                     )
                 })?;
 
-                simulation.process(query).map_err(map_execution_error)?;
+                simulation
+                    .process_query_action(query, timeout)
+                    .map_err(|e| match e {
+                        ExecutionError::Timeout => to_error(
+                            ErrorCode::QueryTimeout,
+                            "the query did not complete within the query timeout; the simulation was terminated",
+                        ),
+                        e => map_execution_error(e),
+                    })?;
 
                 let replies = promise.take_collect().ok_or(to_error(
                     ErrorCode::SimulationBadQuery,
@@ -212,12 +400,22 @@ impl ControllerService {
         };
 
         match reply {
-            Ok(replies) => ProcessQueryReply {
-                replies,
-                result: Some(process_query_reply::Result::Empty(())),
-            },
+            Ok(named_replies) => {
+                let (model_names, replies): (Vec<_>, _) = named_replies.into_iter().unzip();
+
+                ProcessQueryReply {
+                    replies,
+                    model_names: if with_model_names {
+                        model_names.iter().map(|name| name.to_string()).collect()
+                    } else {
+                        Vec::new()
+                    },
+                    result: Some(process_query_reply::Result::Empty(())),
+                }
+            }
             Err(error) => ProcessQueryReply {
                 replies: Vec::new(),
+                model_names: Vec::new(),
                 result: Some(process_query_reply::Result::Error(error)),
             },
         }
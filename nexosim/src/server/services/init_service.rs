@@ -1,4 +1,5 @@
 use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
 
 use ciborium;
 use serde::de::DeserializeOwned;
@@ -6,7 +7,7 @@ use serde::de::DeserializeOwned;
 use crate::registry::EndpointRegistry;
 use crate::simulation::{Scheduler, Simulation, SimulationError};
 
-use super::{map_simulation_error, to_error};
+use super::{map_simulation_error, to_error, to_positive_duration};
 
 use super::super::codegen::simulation::*;
 
@@ -14,6 +15,10 @@ type InitResult = Result<(Simulation, EndpointRegistry), SimulationError>;
 type DeserializationError = ciborium::de::Error<std::io::Error>;
 type SimGen = Box<dyn FnMut(&[u8]) -> Result<InitResult, DeserializationError> + Send + 'static>;
 
+/// A simulation bench created by an `InitService`, together with the reorder
+/// window requested for the scheduling of events.
+pub(crate) type Bench = (Simulation, Scheduler, EndpointRegistry, Option<Duration>);
+
 /// Protobuf-based simulation initializer.
 ///
 /// An `InitService` creates a new simulation bench based on a serialized
@@ -47,10 +52,25 @@ impl InitService {
     }
 
     /// Initializes the simulation based on the specified configuration.
-    pub(crate) fn init(
-        &mut self,
-        request: InitRequest,
-    ) -> (InitReply, Option<(Simulation, Scheduler, EndpointRegistry)>) {
+    pub(crate) fn init(&mut self, request: InitRequest) -> (InitReply, Option<Bench>) {
+        let reorder_window = match request.reorder_window.map(to_positive_duration) {
+            Some(None) => {
+                let reply = init_reply::Result::Error(to_error(
+                    ErrorCode::InvalidMessage,
+                    "the reorder window is negative",
+                ));
+
+                return (
+                    InitReply {
+                        result: Some(reply),
+                    },
+                    None,
+                );
+            }
+            Some(Some(reorder_window)) => Some(reorder_window),
+            None => None,
+        };
+
         let reply = panic::catch_unwind(AssertUnwindSafe(|| (self.sim_gen)(&request.cfg)))
             .map_err(|payload| {
                 let panic_msg: Option<&str> = if let Some(s) = payload.downcast_ref::<&str>() {
@@ -90,7 +110,7 @@ impl InitService {
                 let scheduler = simulation.scheduler();
                 (
                     init_reply::Result::Empty(()),
-                    Some((simulation, scheduler, registry)),
+                    Some((simulation, scheduler, registry, reorder_window)),
                 )
             }
             Err(e) => (init_reply::Result::Error(e), None),
@@ -1,9 +1,15 @@
+use std::collections::HashMap;
 use std::fmt;
 
+use futures_channel::mpsc;
+use prost_types::Timestamp;
+use tonic::Status;
+
 use crate::registry::EventSinkRegistry;
+use crate::time::MonotonicTime;
 
 use super::super::codegen::simulation::*;
-use super::{simulation_not_started_error, to_error};
+use super::{monotonic_to_timestamp, simulation_not_started_error, to_error};
 
 /// Protobuf-based simulation monitor.
 ///
@@ -12,35 +18,25 @@ use super::{simulation_not_started_error, to_error};
 pub(crate) enum MonitorService {
     Started {
         event_sink_registry: EventSinkRegistry,
+        subscriptions: HashMap<String, Vec<SinkSubscriber>>,
     },
     NotStarted,
 }
 
+/// Stream of the events published to a sink subscriber.
+pub(crate) type SinkEventStream = mpsc::UnboundedReceiver<Result<SubscribeSinkReply, Status>>;
+
+/// Sending side of a `SinkEventStream`.
+type SinkSubscriber = mpsc::UnboundedSender<Result<SubscribeSinkReply, Status>>;
+
 impl MonitorService {
     /// Read all events from an event sink.
     pub(crate) fn read_events(&mut self, request: ReadEventsRequest) -> ReadEventsReply {
         let reply = match self {
             Self::Started {
                 event_sink_registry,
-            } => move || -> Result<Vec<Vec<u8>>, Error> {
-                let sink_name = &request.sink_name;
-
-                let sink = event_sink_registry.get_mut(sink_name).ok_or(to_error(
-                    ErrorCode::SinkNotFound,
-                    format!("no sink is registered with the name '{}'", sink_name),
-                ))?;
-
-                sink.collect().map_err(|e| {
-                    to_error(
-                        ErrorCode::InvalidMessage,
-                        format!(
-                            "the event could not be serialized from type '{}': {}",
-                            sink.event_type_name(),
-                            e
-                        ),
-                    )
-                })
-            }(),
+                ..
+            } => collect_events(event_sink_registry, &request.sink_name),
             Self::NotStarted => Err(simulation_not_started_error()),
         };
 
@@ -56,11 +52,203 @@ impl MonitorService {
         }
     }
 
+    /// Read all events from an event sink together with the simulation time at
+    /// which they were read.
+    ///
+    /// The simulation time is expected to be provided by the caller while it
+    /// prevents the simulation from advancing, or to be `None` if the
+    /// simulation was not started.
+    pub(crate) fn read_events_at_time(
+        &mut self,
+        request: ReadEventsAtTimeRequest,
+        time: Option<MonotonicTime>,
+    ) -> ReadEventsAtTimeReply {
+        let reply = match (self, time) {
+            (
+                Self::Started {
+                    event_sink_registry,
+                    ..
+                },
+                Some(time),
+            ) => move || -> Result<(Vec<Vec<u8>>, Timestamp), Error> {
+                // The time is converted first so that no events are lost if it
+                // is out of range.
+                let timestamp = monotonic_to_timestamp(time).ok_or(to_error(
+                    ErrorCode::SimulationTimeOutOfRange,
+                    "the simulation time is out of range",
+                ))?;
+                let events = collect_events(event_sink_registry, &request.sink_name)?;
+
+                Ok((events, timestamp))
+            }(),
+            _ => Err(simulation_not_started_error()),
+        };
+
+        match reply {
+            Ok((events, timestamp)) => ReadEventsAtTimeReply {
+                events,
+                result: Some(read_events_at_time_reply::Result::Time(timestamp)),
+            },
+            Err(error) => ReadEventsAtTimeReply {
+                events: Vec::new(),
+                result: Some(read_events_at_time_reply::Result::Error(error)),
+            },
+        }
+    }
+
+    /// Returns the name of each event sink, whether it is open and how many
+    /// events it holds, without draining them.
+    pub(crate) fn sink_state(&mut self, _request: SinkStateRequest) -> SinkStateReply {
+        match self {
+            Self::Started {
+                event_sink_registry,
+                ..
+            } => {
+                let mut sink_states: Vec<_> = event_sink_registry
+                    .iter()
+                    .map(|(sink_name, sink)| SinkState {
+                        sink_name: sink_name.to_string(),
+                        is_open: sink.is_open(),
+                        buffered_count: sink.len() as u64,
+                    })
+                    .collect();
+                sink_states.sort_by(|a, b| a.sink_name.cmp(&b.sink_name));
+
+                SinkStateReply {
+                    sink_states,
+                    result: Some(sink_state_reply::Result::Empty(())),
+                }
+            }
+            Self::NotStarted => SinkStateReply {
+                sink_states: Vec::new(),
+                result: Some(sink_state_reply::Result::Error(
+                    simulation_not_started_error(),
+                )),
+            },
+        }
+    }
+
+    /// Returns the name and event type of each event sink, sorted by name.
+    ///
+    /// The list is empty if the simulation was not started.
+    pub(crate) fn sink_endpoints(&self) -> Vec<SinkEndpoint> {
+        match self {
+            Self::Started {
+                event_sink_registry,
+                ..
+            } => {
+                let mut sinks: Vec<_> = event_sink_registry
+                    .iter()
+                    .map(|(sink_name, sink)| SinkEndpoint {
+                        name: sink_name.to_string(),
+                        event_type: sink.event_type_name().to_string(),
+                    })
+                    .collect();
+                sinks.sort_by(|a, b| a.name.cmp(&b.name));
+
+                sinks
+            }
+            Self::NotStarted => Vec::new(),
+        }
+    }
+
+    /// Discards all events from all event sinks.
+    ///
+    /// This is a no-op if the simulation was not started.
+    pub(crate) fn clear_sinks(&mut self) {
+        if let Self::Started {
+            event_sink_registry,
+            ..
+        } = self
+        {
+            for sink in event_sink_registry.values_mut() {
+                sink.clear();
+            }
+        }
+    }
+
+    /// Subscribes to the events collected by an event sink.
+    ///
+    /// The returned stream yields the events collected by the sink each time
+    /// they are published with [`MonitorService::publish_events`]. Events of a
+    /// sink with at least one subscriber are consumed by the subscription and
+    /// are no longer returned by `read_events`. If the sink does not exist or
+    /// the simulation was not started, the stream yields a single error and
+    /// ends.
+    pub(crate) fn subscribe_sink(&mut self, request: SubscribeSinkRequest) -> SinkEventStream {
+        let (subscriber, stream) = mpsc::unbounded();
+
+        let error = match self {
+            Self::Started {
+                event_sink_registry,
+                subscriptions,
+            } => {
+                let sink_name = request.sink_name;
+
+                if event_sink_registry.get_mut(&sink_name).is_some() {
+                    subscriptions.entry(sink_name).or_default().push(subscriber);
+
+                    return stream;
+                }
+
+                to_error(
+                    ErrorCode::SinkNotFound,
+                    format!("no sink is registered with the name '{}'", sink_name),
+                )
+            }
+            Self::NotStarted => simulation_not_started_error(),
+        };
+
+        let _ = subscriber.unbounded_send(Ok(SubscribeSinkReply {
+            events: Vec::new(),
+            result: Some(subscribe_sink_reply::Result::Error(error)),
+        }));
+
+        stream
+    }
+
+    /// Sends the events collected by subscribed sinks to their subscribers.
+    ///
+    /// Subscribers whose stream was dropped are removed beforehand, and sinks
+    /// left without subscribers are no longer drained.
+    pub(crate) fn publish_events(&mut self) {
+        if let Self::Started {
+            event_sink_registry,
+            subscriptions,
+        } = self
+        {
+            subscriptions.retain(|sink_name, subscribers| {
+                subscribers.retain(|subscriber| !subscriber.is_closed());
+                if subscribers.is_empty() {
+                    return false;
+                }
+
+                let reply = match collect_events(event_sink_registry, sink_name) {
+                    Ok(events) if events.is_empty() => return true,
+                    Ok(events) => SubscribeSinkReply {
+                        events,
+                        result: Some(subscribe_sink_reply::Result::Empty(())),
+                    },
+                    Err(error) => SubscribeSinkReply {
+                        events: Vec::new(),
+                        result: Some(subscribe_sink_reply::Result::Error(error)),
+                    },
+                };
+                for subscriber in subscribers.iter() {
+                    let _ = subscriber.unbounded_send(Ok(reply.clone()));
+                }
+
+                true
+            });
+        }
+    }
+
     /// Opens an event sink.
     pub(crate) fn open_sink(&mut self, request: OpenSinkRequest) -> OpenSinkReply {
         let reply = match self {
             Self::Started {
                 event_sink_registry,
+                ..
             } => {
                 let sink_name = &request.sink_name;
 
@@ -88,6 +276,7 @@ impl MonitorService {
         let reply = match self {
             Self::Started {
                 event_sink_registry,
+                ..
             } => {
                 let sink_name = &request.sink_name;
 
@@ -111,6 +300,28 @@ impl MonitorService {
     }
 }
 
+/// Collects all events from the specified event sink.
+fn collect_events(
+    event_sink_registry: &mut EventSinkRegistry,
+    sink_name: &str,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let sink = event_sink_registry.get_mut(sink_name).ok_or(to_error(
+        ErrorCode::SinkNotFound,
+        format!("no sink is registered with the name '{}'", sink_name),
+    ))?;
+
+    sink.collect().map_err(|e| {
+        to_error(
+            ErrorCode::InvalidMessage,
+            format!(
+                "the event could not be serialized from type '{}': {}",
+                sink.event_type_name(),
+                e
+            ),
+        )
+    })
+}
+
 impl fmt::Debug for MonitorService {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SimulationService").finish_non_exhaustive()
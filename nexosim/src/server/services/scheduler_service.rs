@@ -1,14 +1,17 @@
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::registry::EventSourceRegistry;
-use crate::server::key_registry::{KeyRegistry, KeyRegistryId};
-use crate::simulation::Scheduler;
+use crate::registry::{self, ActionKeyRegistry, EventSourceRegistry};
+use crate::server::key_registry::KeyRegistryId;
+use crate::simulation::{Action, ActionKey, Scheduler};
+use crate::time::MonotonicTime;
 
 use super::super::codegen::simulation::*;
 use super::{
     map_scheduling_error, monotonic_to_timestamp, simulation_not_started_error,
-    timestamp_to_monotonic, to_error, to_strictly_positive_duration,
+    timestamp_to_monotonic, to_codec, to_error, to_positive_duration,
+    to_strictly_positive_duration,
 };
 
 /// Protobuf-based simulation scheduler.
@@ -20,7 +23,9 @@ pub(crate) enum SchedulerService {
     Started {
         scheduler: Scheduler,
         event_source_registry: Arc<EventSourceRegistry>,
-        key_registry: KeyRegistry,
+        key_registry: ActionKeyRegistry,
+        max_horizon: Option<Duration>,
+        reorder_window: Option<Duration>,
     },
 }
 
@@ -53,102 +58,118 @@ impl SchedulerService {
                 scheduler,
                 event_source_registry,
                 key_registry,
-            } => move || -> Result<Option<KeyRegistryId>, Error> {
-                let source_name = &request.source_name;
-                let event = &request.event;
-                let with_key = request.with_key;
-                let period = request
-                    .period
-                    .map(|period| {
-                        to_strictly_positive_duration(period).ok_or(to_error(
-                            ErrorCode::InvalidPeriod,
-                            "the specified event period is not strictly positive",
-                        ))
-                    })
-                    .transpose()?;
-
-                let source = event_source_registry.get(source_name).ok_or(to_error(
-                    ErrorCode::SourceNotFound,
-                    "no event source is registered with the name '{}'".to_string(),
-                ))?;
+                max_horizon,
+                reorder_window,
+            } => move || -> Result<(Option<KeyRegistryId>, MonotonicTime), Error> {
+                let event = prepare_event(request, scheduler, event_source_registry, *max_horizon)?;
 
-                let (action, action_key) = match (with_key, period) {
-                    (false, None) => source.event(event).map(|action| (action, None)),
-                    (false, Some(period)) => source
-                        .periodic_event(period, event)
-                        .map(|action| (action, None)),
-                    (true, None) => source
-                        .keyed_event(event)
-                        .map(|(action, key)| (action, Some(key))),
-                    (true, Some(period)) => source
-                        .keyed_periodic_event(period, event)
-                        .map(|(action, key)| (action, Some(key))),
+                // Events scheduled slightly in the past are accepted if a
+                // reorder window was specified, in which case the effective
+                // deadline may differ from the requested one.
+                let deadline = match reorder_window {
+                    Some(reorder_window) => scheduler.schedule_with_tolerance(
+                        event.deadline,
+                        *reorder_window,
+                        event.action,
+                    ),
+                    None => scheduler
+                        .schedule(event.deadline, event.action)
+                        .map(|_| event.deadline),
                 }
-                .map_err(|e| {
-                    to_error(
-                        ErrorCode::InvalidMessage,
-                        format!(
-                            "the event could not be deserialized as type '{}': {}",
-                            source.event_type_name(),
-                            e
-                        ),
-                    )
-                })?;
-
-                let deadline = request.deadline.ok_or(to_error(
-                    ErrorCode::MissingArgument,
-                    "missing deadline argument",
-                ))?;
+                .map_err(map_scheduling_error)?;
 
-                let deadline = match deadline {
-                    schedule_event_request::Deadline::Time(time) => timestamp_to_monotonic(time)
-                        .ok_or(to_error(
-                            ErrorCode::InvalidTime,
-                            "out-of-range nanosecond field",
-                        ))?,
-                    schedule_event_request::Deadline::Duration(duration) => {
-                        let duration = to_strictly_positive_duration(duration).ok_or(to_error(
-                            ErrorCode::InvalidDeadline,
-                            "the specified scheduling deadline is not in the future",
-                        ))?;
-
-                        scheduler.time() + duration
-                    }
-                };
-
-                let key_id = action_key.map(|action_key| {
-                    key_registry.remove_expired_keys(scheduler.time());
-
-                    if period.is_some() {
-                        key_registry.insert_eternal_key(action_key)
-                    } else {
-                        key_registry.insert_key(action_key, deadline)
-                    }
-                });
-
-                scheduler
-                    .schedule(deadline, action)
-                    .map_err(map_scheduling_error)?;
+                let key_id = register_key(
+                    key_registry,
+                    scheduler,
+                    event.action_key,
+                    event.is_periodic,
+                    deadline,
+                );
 
-                Ok(key_id)
+                Ok((key_id, deadline))
             }(),
             Self::NotStarted => Err(simulation_not_started_error()),
         };
 
-        ScheduleEventReply {
-            result: Some(match reply {
-                Ok(Some(key_id)) => {
-                    let (subkey1, subkey2) = key_id.into_raw_parts();
-                    schedule_event_reply::Result::Key(EventKey {
-                        subkey1: subkey1
-                            .try_into()
-                            .expect("action key index is too large to be serialized"),
-                        subkey2,
+        to_schedule_event_reply(reply)
+    }
+
+    /// Schedules several events at once.
+    ///
+    /// Either all events are scheduled or, if any of them is invalid or cannot
+    /// be scheduled, none of them.
+    pub(crate) fn schedule_batch(&mut self, request: ScheduleBatchRequest) -> ScheduleBatchReply {
+        let reply = match self {
+            Self::Started {
+                scheduler,
+                event_source_registry,
+                key_registry,
+                max_horizon,
+                reorder_window,
+            } => move || -> Result<Vec<ScheduleEventReply>, Error> {
+                // All events are deserialized and validated before anything is
+                // scheduled.
+                let events = request
+                    .requests
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, request)| {
+                        prepare_event(request, scheduler, event_source_registry, *max_horizon)
+                            .map_err(|mut error| {
+                                error.message = format!(
+                                    "invalid request at index {}: {}",
+                                    index, error.message
+                                );
+
+                                error
+                            })
                     })
-                }
-                Ok(None) => schedule_event_reply::Result::Empty(()),
-                Err(error) => schedule_event_reply::Result::Error(error),
-            }),
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let (actions, keys): (Vec<_>, Vec<_>) = events
+                    .into_iter()
+                    .map(|event| {
+                        (
+                            (event.deadline, event.action),
+                            (event.action_key, event.is_periodic),
+                        )
+                    })
+                    .unzip();
+
+                let deadlines = scheduler
+                    .schedule_all(actions, *reorder_window)
+                    .map_err(map_scheduling_error)?;
+
+                let replies = keys
+                    .into_iter()
+                    .zip(deadlines)
+                    .map(|((action_key, is_periodic), deadline)| {
+                        let key_id = register_key(
+                            key_registry,
+                            scheduler,
+                            action_key,
+                            is_periodic,
+                            deadline,
+                        );
+
+                        to_schedule_event_reply(Ok((key_id, deadline)))
+                    })
+                    .collect();
+
+                Ok(replies)
+            }(),
+            Self::NotStarted => Err(simulation_not_started_error()),
+        };
+
+        match reply {
+            Ok(replies) => ScheduleBatchReply {
+                replies,
+                result: Some(schedule_batch_reply::Result::Empty(())),
+            },
+            Err(error) => ScheduleBatchReply {
+                replies: Vec::new(),
+                result: Some(schedule_batch_reply::Result::Error(error)),
+            },
         }
     }
 
@@ -163,14 +184,14 @@ impl SchedulerService {
                 let key = request
                     .key
                     .ok_or(to_error(ErrorCode::MissingArgument, "missing key argument"))?;
-                let subkey1: usize = key
-                    .subkey1
-                    .try_into()
-                    .map_err(|_| to_error(ErrorCode::InvalidKey, "invalid event key"))?;
-                let subkey2 = key.subkey2;
-
-                let key_id = KeyRegistryId::from_raw_parts(subkey1, subkey2);
+                let key_id = registry::EventKey {
+                    subkey1: key.subkey1,
+                    subkey2: key.subkey2,
+                }
+                .to_registry_id()
+                .ok_or(to_error(ErrorCode::InvalidKey, "invalid event key"))?;
 
+                let mut key_registry = key_registry.lock();
                 key_registry.remove_expired_keys(scheduler.time());
                 let key = key_registry.extract_key(key_id).ok_or(to_error(
                     ErrorCode::InvalidKey,
@@ -192,6 +213,15 @@ impl SchedulerService {
         }
     }
 
+    /// Removes all keys of remotely-scheduled events from the key registry.
+    ///
+    /// This is a no-op if the simulation was not started.
+    pub(crate) fn clear_keys(&mut self) {
+        if let Self::Started { key_registry, .. } = self {
+            key_registry.lock().clear();
+        }
+    }
+
     /// Requests the simulation to stop when advancing to the next step.
     pub(crate) fn halt(&mut self, _request: HaltRequest) -> HaltReply {
         let reply = match self {
@@ -209,6 +239,181 @@ impl SchedulerService {
     }
 }
 
+/// A deserialized event ready to be scheduled.
+struct PreparedEvent {
+    action: Action,
+    action_key: Option<ActionKey>,
+    is_periodic: bool,
+    deadline: MonotonicTime,
+}
+
+/// Deserializes the event of a scheduling request and computes its requested
+/// deadline.
+fn prepare_event(
+    request: ScheduleEventRequest,
+    scheduler: &Scheduler,
+    event_source_registry: &EventSourceRegistry,
+    max_horizon: Option<Duration>,
+) -> Result<PreparedEvent, Error> {
+    let source_name = &request.source_name;
+    let event = &request.event;
+    let with_key = request.with_key;
+    let codec = to_codec(request.encoding)?;
+    let period = request
+        .period
+        .map(|period| {
+            to_strictly_positive_duration(period).ok_or(to_error(
+                ErrorCode::InvalidPeriod,
+                "the specified event period is not strictly positive",
+            ))
+        })
+        .transpose()?;
+
+    let source = event_source_registry.get(source_name).ok_or(to_error(
+        ErrorCode::SourceNotFound,
+        "no event source is registered with the name '{}'".to_string(),
+    ))?;
+
+    let (action, action_key) = match (with_key, period) {
+        (false, None) => source.event(event, codec).map(|action| (action, None)),
+        (false, Some(period)) => source
+            .periodic_event(period, event, codec)
+            .map(|action| (action, None)),
+        (true, None) => source
+            .keyed_event(event, codec)
+            .map(|(action, key)| (action, Some(key))),
+        (true, Some(period)) => source
+            .keyed_periodic_event(period, event, codec)
+            .map(|(action, key)| (action, Some(key))),
+    }
+    .map_err(|e| {
+        to_error(
+            ErrorCode::InvalidMessage,
+            format!(
+                "the event could not be deserialized as type '{}': {}",
+                source.event_type_name(),
+                e
+            ),
+        )
+    })?;
+
+    let deadline = request.deadline.ok_or(to_error(
+        ErrorCode::MissingArgument,
+        "missing deadline argument",
+    ))?;
+
+    let base_time = request
+        .base_time
+        .map(|time| {
+            timestamp_to_monotonic(time).ok_or(to_error(
+                ErrorCode::InvalidTime,
+                "out-of-range nanosecond field",
+            ))
+        })
+        .transpose()?;
+
+    let deadline = match deadline {
+        schedule_event_request::Deadline::Time(time) => timestamp_to_monotonic(time).ok_or(
+            to_error(ErrorCode::InvalidTime, "out-of-range nanosecond field"),
+        )?,
+        schedule_event_request::Deadline::Duration(duration) => match base_time {
+            Some(base_time) => {
+                let duration = to_positive_duration(duration).ok_or(to_error(
+                    ErrorCode::InvalidDeadline,
+                    "the specified scheduling deadline is negative",
+                ))?;
+
+                // A deadline in the past is rejected by the scheduler, unless
+                // it lies within the reorder window.
+                base_time.checked_add(duration).ok_or(to_error(
+                    ErrorCode::InvalidTime,
+                    "the specified scheduling deadline is out of range",
+                ))?
+            }
+            None => {
+                let duration = to_strictly_positive_duration(duration).ok_or(to_error(
+                    ErrorCode::InvalidDeadline,
+                    "the specified scheduling deadline is not in the future",
+                ))?;
+
+                scheduler.time().checked_add(duration).ok_or(to_error(
+                    ErrorCode::InvalidTime,
+                    "the specified scheduling deadline is out of range",
+                ))?
+            }
+        },
+    };
+
+    if let Some(max_horizon) = max_horizon {
+        let is_beyond_horizon = scheduler
+            .time()
+            .checked_add(max_horizon)
+            .is_some_and(|horizon| deadline > horizon);
+        if is_beyond_horizon {
+            return Err(to_error(
+                ErrorCode::HorizonExceeded,
+                "the specified deadline lies beyond the maximum scheduling horizon",
+            ));
+        }
+    }
+
+    Ok(PreparedEvent {
+        action,
+        action_key,
+        is_periodic: period.is_some(),
+        deadline,
+    })
+}
+
+/// Registers the action key of a scheduled event, if any, and returns its
+/// registry ID.
+fn register_key(
+    key_registry: &ActionKeyRegistry,
+    scheduler: &Scheduler,
+    action_key: Option<ActionKey>,
+    is_periodic: bool,
+    deadline: MonotonicTime,
+) -> Option<KeyRegistryId> {
+    action_key.map(|action_key| {
+        let mut key_registry = key_registry.lock();
+        key_registry.remove_expired_keys(scheduler.time());
+
+        if is_periodic {
+            key_registry.insert_eternal_key(action_key)
+        } else {
+            key_registry.insert_key(action_key, deadline)
+        }
+    })
+}
+
+/// Builds the reply to a scheduling request from its outcome.
+fn to_schedule_event_reply(
+    reply: Result<(Option<KeyRegistryId>, MonotonicTime), Error>,
+) -> ScheduleEventReply {
+    let (result, scheduled_time) = match reply {
+        Ok((key_id, deadline)) => {
+            let result = match key_id {
+                Some(key_id) => {
+                    let key = registry::EventKey::from_registry_id(key_id);
+                    schedule_event_reply::Result::Key(EventKey {
+                        subkey1: key.subkey1,
+                        subkey2: key.subkey2,
+                    })
+                }
+                None => schedule_event_reply::Result::Empty(()),
+            };
+
+            (result, monotonic_to_timestamp(deadline))
+        }
+        Err(error) => (schedule_event_reply::Result::Error(error), None),
+    };
+
+    ScheduleEventReply {
+        scheduled_time,
+        result: Some(result),
+    }
+}
+
 impl fmt::Debug for SchedulerService {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SchedulerService").finish_non_exhaustive()
@@ -70,33 +70,46 @@
 //! most typically at the very beginning of a simulation when models
 //! simultaneously and mutually send events during the call to [`Model::init`].
 //! If such a large amount of events is deemed normal behavior, the issue can be
-//! remedied by increasing the capacity of the saturated mailboxes.
+//! remedied by increasing the capacity of the saturated mailboxes. Alternatively,
+//! if only the latest events matter to a model, its mailbox can be created with
+//! [`Mailbox::with_policy`] so that events sent to a full mailbox are discarded
+//! rather than waited upon.
 //!
 //! Deadlocks are reported as [`ExecutionError::Deadlock`] errors, which
 //! identify all involved models and the count of unprocessed messages (events
 //! or requests) in their mailboxes.
+mod determinism;
+mod event_journal;
 mod mailbox;
 mod scheduler;
 mod sim_init;
 
+pub(crate) use event_journal::EventJournal;
+
 pub(crate) use scheduler::{
-    GlobalScheduler, KeyedOnceAction, KeyedPeriodicAction, OnceAction, PeriodicAction,
+    GlobalScheduler, InitSchedulingMonitor, KeyedOnceAction, KeyedPeriodicAction, OnceAction,
+    PeriodicAction,
 };
 
-pub use mailbox::{Address, Mailbox};
+pub use determinism::{DeterminismChecker, DeterminismError};
+pub use event_journal::EventLogEntry;
+pub use mailbox::{Address, AnyAddress, Mailbox, OverflowPolicy};
 pub use scheduler::{Action, ActionKey, AutoActionKey, Scheduler, SchedulingError};
 pub use sim_init::SimInit;
 
 use std::any::{Any, TypeId};
 use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "tracing")]
+use std::sync::RwLock;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::task::Poll;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{panic, task};
 
 use pin_project::pin_project;
@@ -105,10 +118,13 @@ use recycle_box::{coerce_box, RecycleBox};
 use scheduler::SchedulerQueue;
 
 use crate::channel::{ChannelObserver, SendError};
+#[cfg(feature = "tracing")]
+use crate::executor::SIMULATION_CONTEXT;
 use crate::executor::{Executor, ExecutorError, Signal};
-use crate::model::{BuildContext, Context, Model, ProtoModel};
-use crate::ports::{InputFn, ReplierFn};
+use crate::model::{BuildContext, ConnectionWarning, Context, Model, ProtoModel};
+use crate::ports::{EventSinkStream, InputFn, QuerySource, ReplierFn};
 use crate::time::{AtomicTime, Clock, Deadline, MonotonicTime, SyncStatus};
+use crate::util::semaphore::Semaphore;
 use crate::util::seq_futures::SeqFuture;
 use crate::util::slot;
 
@@ -158,10 +174,22 @@ pub struct Simulation {
     clock: Box<dyn Clock>,
     clock_tolerance: Option<Duration>,
     timeout: Duration,
+    query_timeout: Duration,
+    poll_budget: usize,
+    has_posted_events: bool,
     observers: Vec<(String, Box<dyn ChannelObserver>)>,
-    model_names: Vec<String>,
+    models: BTreeMap<usize, SpawnedModel>,
+    next_model_id: usize,
     is_halted: Arc<AtomicBool>,
     is_terminated: bool,
+    has_failed: bool,
+    abort_signal: Signal,
+    #[cfg(feature = "tracing")]
+    time_slice_span: Arc<RwLock<tracing::Span>>,
+    queue_length_history: QueueLengthHistory,
+    instrumentation: Instrumentation,
+    sink_callbacks: Vec<Box<dyn FnMut() + Send>>,
+    key_callbacks: HashMap<ActionKey, Vec<KeyCallback>>,
 }
 
 impl Simulation {
@@ -175,8 +203,12 @@ impl Simulation {
         clock_tolerance: Option<Duration>,
         timeout: Duration,
         observers: Vec<(String, Box<dyn ChannelObserver>)>,
-        model_names: Vec<String>,
+        models: Vec<SpawnedModel>,
         is_halted: Arc<AtomicBool>,
+        queue_length_history_capacity: usize,
+        instrumentation: Instrumentation,
+        abort_signal: Signal,
+        #[cfg(feature = "tracing")] time_slice_span: Arc<RwLock<tracing::Span>>,
     ) -> Self {
         Self {
             executor,
@@ -185,10 +217,22 @@ impl Simulation {
             clock,
             clock_tolerance,
             timeout,
+            query_timeout: Duration::ZERO,
+            poll_budget: 0,
+            has_posted_events: false,
             observers,
-            model_names,
+            next_model_id: models.len(),
+            models: models.into_iter().enumerate().collect(),
             is_halted,
             is_terminated: false,
+            has_failed: false,
+            abort_signal,
+            #[cfg(feature = "tracing")]
+            time_slice_span,
+            queue_length_history: QueueLengthHistory::new(queue_length_history_capacity),
+            instrumentation,
+            sink_callbacks: Vec::new(),
+            key_callbacks: HashMap::new(),
         }
     }
 
@@ -206,19 +250,303 @@ impl Simulation {
         self.timeout = timeout;
     }
 
+    /// Sets a timeout for the processing of queries.
+    ///
+    /// The timeout corresponds to the maximum wall clock time allocated for the
+    /// completion of a query processed with [`Simulation::process_query`] or
+    /// with a remote procedure call, which protects against queries that never
+    /// complete, for instance due to a forwarding loop. If exceeded, an
+    /// [`ExecutionError::Timeout`] error is raised.
+    ///
+    /// As for a step timeout, this error is fatal and the simulation is
+    /// terminated. No reply is returned, including replies from models that
+    /// did reply before the timeout elapsed.
+    ///
+    /// A null duration makes queries subject to the step timeout set with
+    /// [`Simulation::set_timeout`], which is the default behavior.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn set_query_timeout(&mut self, timeout: Duration) {
+        self.query_timeout = timeout;
+    }
+
+    /// Sets the maximum number of task polls for each simulation step.
+    ///
+    /// A model that endlessly re-triggers itself within a single time slice,
+    /// for instance by sending events to itself in a loop, prevents the
+    /// simulation step from completing. The poll budget makes it possible to
+    /// detect such livelocks deterministically, as an alternative to a
+    /// wall-clock timeout: once the budget is exhausted, an
+    /// [`ExecutionError::PollBudgetExceeded`] error is raised.
+    ///
+    /// A null budget disables the check, which is the default behavior. The
+    /// budget is only enforced by single-threaded simulations, i.e. those
+    /// initialized with `SimInit::with_num_threads(1)`.
+    pub fn set_poll_budget(&mut self, poll_budget: usize) {
+        self.poll_budget = poll_budget;
+    }
+
+    /// Returns the number of tasks run by the executor during the most recent
+    /// simulation step.
+    ///
+    /// Each time slice processed by [`Simulation::step`] or
+    /// [`Simulation::step_until`] and each call to [`Simulation::process`] or
+    /// its variants counts as a separate step, so after a call to
+    /// `step_until` the count only covers the last time slice. Since a model
+    /// is polled whenever it is woken by a new message, an unusually large
+    /// count typically points to a model that floods its peers with messages
+    /// or re-triggers itself within a single time slice.
+    ///
+    /// The count remains available when a step fails, for instance with an
+    /// [`ExecutionError::PollBudgetExceeded`] error.
+    pub fn last_step_task_count(&self) -> usize {
+        self.executor.tasks_run_last()
+    }
+
     /// Returns the current simulation time.
     pub fn time(&self) -> MonotonicTime {
         self.time.read()
     }
 
+    /// Returns the number of threads on which the simulation runs.
+    pub fn num_threads(&self) -> usize {
+        self.executor.num_threads()
+    }
+
+    /// Returns the current simulation time, to be used as a marker for
+    /// [`Simulation::elapsed_since`].
+    ///
+    /// This is equivalent to [`Simulation::time`].
+    pub fn mark(&self) -> MonotonicTime {
+        self.time()
+    }
+
+    /// Returns the simulation time elapsed since the specified marker.
+    ///
+    /// `None` is returned if the marker lies in the future of the current
+    /// simulation time.
+    pub fn elapsed_since(&self, marker: MonotonicTime) -> Option<Duration> {
+        self.time().checked_duration_since(marker)
+    }
+
+    /// Returns the time of the earliest scheduled action, if any, without
+    /// advancing simulation time.
+    ///
+    /// Cancelled actions are only discarded when simulation time advances, so
+    /// the returned time may be that of a cancelled action. It is therefore a
+    /// lower bound of the time to which the next call to [`Simulation::step`]
+    /// advances, which makes it suitable for conservative synchronization
+    /// with other simulators.
+    pub fn next_event_time(&self) -> Option<MonotonicTime> {
+        self.scheduler_queue
+            .lock()
+            .unwrap()
+            .peek()
+            .map(|(&(time, _, _), _)| time)
+    }
+
+    /// Returns the number of actions in the scheduler queue.
+    ///
+    /// Together with [`Simulation::next_event_time`], this makes it possible
+    /// to report the progress of a simulation. As for the latter, note that
+    /// the count may include cancelled actions that have not been discarded
+    /// yet, and that each occurrence of a periodic action is only queued once
+    /// the previous occurrence has been processed.
+    pub fn pending_event_count(&self) -> usize {
+        self.scheduler_queue.lock().unwrap().len()
+    }
+
+    /// Returns the time scale of the simulation clock, *i.e.* the ratio
+    /// between the rates of simulation time and wall clock time.
+    ///
+    /// See [`Clock::time_scale`].
+    pub fn time_scale(&self) -> f64 {
+        self.clock.time_scale()
+    }
+
+    /// Sets the time scale of the simulation clock, *i.e.* the ratio between
+    /// the rates of simulation time and wall clock time.
+    ///
+    /// The new scale takes effect from the current instant. This call is
+    /// ignored if the clock does not support time scaling.
+    ///
+    /// See [`Clock::set_time_scale`].
+    pub fn set_time_scale(&mut self, scale: f64) {
+        self.clock.set_time_scale(scale);
+    }
+
+    /// Pauses the simulation clock.
+    ///
+    /// The wall clock time elapsed until the clock is resumed is not accounted
+    /// for when synchronizing the simulation, so that no burst of events
+    /// occurs once the simulation is stepped again. The clock is resumed either
+    /// explicitly with [`Simulation::resume_clock`] or implicitly by the next
+    /// simulation step. This call is ignored if the clock does not support
+    /// pausing.
+    ///
+    /// See [`Clock::pause`].
+    pub fn pause_clock(&mut self) {
+        self.clock.pause();
+    }
+
+    /// Resumes the simulation clock after a call to
+    /// [`Simulation::pause_clock`].
+    ///
+    /// See [`Clock::resume`].
+    pub fn resume_clock(&mut self) {
+        self.clock.resume();
+    }
+
+    /// Replaces the simulation clock.
+    ///
+    /// The new clock is immediately synchronized to the current simulation
+    /// time, which sets the time reference of clocks such as
+    /// [`AutoSystemClock`](crate::time::AutoSystemClock) so that the
+    /// simulation resumes from the current instant without a time jump. This
+    /// makes it possible, for instance, to fast-forward through a warm-up
+    /// phase with a [`NoClock`](crate::time::NoClock) before switching to a
+    /// real-time clock.
+    ///
+    /// An [`ExecutionError::OutOfSync`] error is returned if the new clock
+    /// lags behind the current simulation time by more than the tolerance set
+    /// with [`SimInit::set_clock_tolerance`], in which case the new clock is
+    /// nevertheless kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::simulation::SimInit;
+    /// use nexosim::time::{AutoSystemClock, MonotonicTime};
+    ///
+    /// let (mut simu, _scheduler) = SimInit::new().init(MonotonicTime::EPOCH).unwrap();
+    ///
+    /// // Warm-up phase, as fast as possible.
+    /// simu.step_until(Duration::from_secs(3600)).unwrap();
+    ///
+    /// // Live phase, in real time.
+    /// simu.set_clock(AutoSystemClock::new()).unwrap();
+    /// simu.step_until(Duration::from_millis(10)).unwrap();
+    /// ```
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) -> Result<(), ExecutionError> {
+        self.clock = Box::new(clock);
+
+        if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(self.time.read()) {
+            if let Some(tolerance) = &self.clock_tolerance {
+                if &lag > tolerance {
+                    return Err(ExecutionError::OutOfSync(lag));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the most recent records of the scheduler queue length, in
+    /// chronological order.
+    ///
+    /// A record made of the simulation time and the number of actions
+    /// remaining in the scheduler queue is taken at the end of each step that
+    /// processed at least one scheduled action. Note that the count may include
+    /// cancelled actions that have not been discarded yet.
+    ///
+    /// The history is only recorded if a non-zero history capacity was set
+    /// with [`SimInit::set_queue_length_history`], otherwise an empty slice is
+    /// returned. A queue length that grows steadily may indicate that some
+    /// models schedule actions faster than they are processed.
+    pub fn queue_length_history(&self) -> &[(MonotonicTime, usize)] {
+        self.queue_length_history.as_slice()
+    }
+
+    /// Returns the name, the current number of messages and the capacity of
+    /// the mailbox of each model.
+    ///
+    /// Mailboxes are normally empty between simulation steps, but this is
+    /// not the case after a step fails with an [`ExecutionError::Deadlock`]
+    /// error, in which case a full mailbox suggests that its capacity should
+    /// be increased.
+    ///
+    /// Only the mailboxes of the models added with [`SimInit::add_model`] and
+    /// [`Simulation::add_model`] are reported, in the order in which the
+    /// models were added.
+    pub fn mailbox_occupancy(&self) -> Vec<(String, usize, usize)> {
+        self.observers
+            .iter()
+            .map(|(name, observer)| (name.clone(), observer.len(), observer.capacity()))
+            .collect()
+    }
+
+    /// Returns the number of events and queries processed by models since the
+    /// simulation was initialized or since the last call to
+    /// [`Simulation::reset_event_type_counts`], broken down by event type.
+    ///
+    /// Event types are identified by their name as returned by
+    /// [`std::any::type_name`]. For connections that map events to another
+    /// type, the type delivered to the input or replier port is counted.
+    ///
+    /// Counting must be enabled with [`SimInit::set_event_type_counting`],
+    /// otherwise an empty map is returned.
+    pub fn event_type_counts(&self) -> HashMap<&'static str, u64> {
+        self.instrumentation
+            .event_type_counter()
+            .map(|counter| counter.counts())
+            .unwrap_or_default()
+    }
+
+    /// Returns the most recent events and queries processed by models, from
+    /// the oldest to the most recent.
+    ///
+    /// Each entry records the simulation time, the fully qualified name of the
+    /// model and the type of the event or query. For connections that map
+    /// events to another type, the type delivered to the input or replier port
+    /// is recorded.
+    ///
+    /// The log must be enabled with [`SimInit::set_event_log`], otherwise an
+    /// empty vector is returned.
+    pub fn event_log(&self) -> Vec<EventLogEntry> {
+        self.instrumentation
+            .event_journal()
+            .map(|event_journal| event_journal.log_entries())
+            .unwrap_or_default()
+    }
+
+    /// Resets all event type counts to zero.
+    ///
+    /// Calling this method after each step makes it possible to collect
+    /// per-step counts with [`Simulation::event_type_counts`].
+    pub fn reset_event_type_counts(&mut self) {
+        if let Some(counter) = self.instrumentation.event_type_counter() {
+            counter.reset();
+        }
+    }
+
+    /// Wakes up all worker threads and makes them process a trivial task.
+    ///
+    /// Worker threads that were idle for a while may incur a noticeable wake-up
+    /// latency when the next events are processed. Calling this method just
+    /// before a timing-critical step reduces the latency jitter of that step.
+    /// Simulation time is left unchanged.
+    ///
+    /// A single-threaded simulation has no worker threads, so this method has
+    /// no effect in such case.
+    pub fn prewarm(&mut self) -> Result<(), ExecutionError> {
+        self.executor.prewarm();
+
+        self.run()
+    }
+
     /// Advances simulation time to that of the next scheduled event, processing
     /// that event as well as all other events scheduled for the same time.
     ///
     /// Processing is gated by a (possibly blocking) call to
     /// [`Clock::synchronize`] on the configured simulation clock. This method
     /// blocks until all newly processed events have completed.
-    pub fn step(&mut self) -> Result<(), ExecutionError> {
-        self.step_to_next(None).map(|_| ())
+    ///
+    /// The new simulation time is returned, or `None` if no event was
+    /// scheduled, in which case simulation time remains unchanged. See also
+    /// [`Simulation::step_unbounded`] to process all scheduled events.
+    pub fn step(&mut self) -> Result<Option<MonotonicTime>, ExecutionError> {
+        self.step_to_next(None)
     }
 
     /// Iteratively advances the simulation time until the specified deadline,
@@ -228,9 +556,17 @@ impl Simulation {
     /// time have completed. The simulation time upon completion is equal to the
     /// specified target time, whether or not an event was scheduled for that
     /// time.
+    ///
+    /// A target time equal to the current simulation time is valid: simulation
+    /// time then remains unchanged, but events already due at the current time
+    /// (such as events held back by [`Simulation::step_subset`]) are
+    /// processed. An [`ExecutionError::InvalidDeadline`] error is only returned
+    /// if the target time lies in the past or beyond [`MonotonicTime::MAX`].
     pub fn step_until(&mut self, deadline: impl Deadline) -> Result<(), ExecutionError> {
         let now = self.time.read();
-        let target_time = deadline.into_time(now);
+        let target_time = deadline
+            .checked_into_time(now)
+            .ok_or(ExecutionError::InvalidDeadline(MonotonicTime::MAX))?;
         if target_time < now {
             return Err(ExecutionError::InvalidDeadline(target_time));
         }
@@ -245,18 +581,356 @@ impl Simulation {
         self.step_until_unchecked(None)
     }
 
+    /// Iteratively advances the simulation time, as if by calling
+    /// [`Simulation::step`] repeatedly, until at most `max_slices` time slices
+    /// have been processed or no event remains scheduled.
+    ///
+    /// This makes it possible to hand control back to the caller at regular
+    /// intervals, for instance to refresh a user interface while a simulation
+    /// is running. The time of the last processed time slice is returned, or
+    /// `None` if no time slice was processed, in which case simulation time
+    /// remains unchanged.
+    pub fn step_bounded(
+        &mut self,
+        max_slices: usize,
+    ) -> Result<Option<MonotonicTime>, ExecutionError> {
+        let mut last_time = None;
+        for _ in 0..max_slices {
+            match self.step_to_next(None)? {
+                Some(time) => last_time = Some(time),
+                None => break,
+            }
+        }
+
+        Ok(last_time)
+    }
+
+    /// Iteratively advances the simulation time, as if by calling
+    /// [`Simulation::step`] repeatedly, until the specified wall clock duration
+    /// has elapsed or no event remains scheduled.
+    ///
+    /// This is the wall clock counterpart of [`Simulation::step_until`] and is
+    /// mostly useful with a real-time clock. The elapsed time is checked before
+    /// each step, so the last step may complete after the specified duration
+    /// has elapsed, all the more so since its processing may first be delayed
+    /// by the clock until the time slice is due. With a [`NoClock`] clock, the
+    /// simulation typically runs until no event remains scheduled.
+    ///
+    /// The simulation time upon completion is returned.
+    ///
+    /// [`NoClock`]: crate::time::NoClock
+    pub fn run_for_walltime(
+        &mut self,
+        duration: Duration,
+    ) -> Result<MonotonicTime, ExecutionError> {
+        let start = Instant::now();
+        while start.elapsed() < duration {
+            if self.step_to_next(None)?.is_none() {
+                break;
+            }
+        }
+
+        Ok(self.time())
+    }
+
+    /// Processes the next `max_events` scheduled events one at a time,
+    /// advancing simulation time as needed.
+    ///
+    /// Unlike [`Simulation::step`], which processes all events of a time slice
+    /// concurrently, this method processes each event in isolation and waits
+    /// for its completion, including that of all the messages it triggers,
+    /// before moving on to the next event. This makes it possible to follow a
+    /// causal chain event by event irrespective of the number of time slices
+    /// it spans. Events scheduled for the same time are processed in their
+    /// scheduling order, and any remaining events of a partially processed
+    /// time slice are processed by the next call to this method or to
+    /// [`Simulation::step`] and related methods, which do not advance time
+    /// until these events are processed.
+    ///
+    /// Each scheduled action counts as a single event, even if it broadcasts
+    /// an event to several models. The number of processed events is returned;
+    /// it is lower than `max_events` only if no event remains scheduled.
+    pub fn step_events(&mut self, max_events: usize) -> Result<usize, ExecutionError> {
+        let mut event_count = 0;
+
+        while event_count < max_events {
+            if self.is_terminated {
+                return Err(ExecutionError::Terminated);
+            }
+
+            // Pull the next action and set aside all other actions scheduled
+            // for the same time, so that the former is processed alone.
+            let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+            let (next_key, next_action) = loop {
+                match scheduler_queue.pull() {
+                    Some((_, action)) if action.is_cancelled() => {}
+                    Some(entry) => break entry,
+                    None => return Ok(event_count),
+                }
+            };
+            let mut other_actions = Vec::new();
+            while let Some((&key, _)) = scheduler_queue.peek() {
+                if key.0 != next_key.0 {
+                    break;
+                }
+                let (key, action) = scheduler_queue.pull().unwrap();
+                if !action.is_cancelled() {
+                    other_actions.push((key, action));
+                }
+            }
+            scheduler_queue.insert(next_key, next_action);
+            drop(scheduler_queue);
+
+            let result = self.step_to_next(Some(next_key.0));
+
+            // Re-queue the other actions, preserving their relative order.
+            let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+            for (key, action) in other_actions {
+                scheduler_queue.insert(key, action);
+            }
+            drop(scheduler_queue);
+
+            result?;
+            event_count += 1;
+        }
+
+        Ok(event_count)
+    }
+
+    /// Advances simulation time to that of the next event scheduled for any of
+    /// the specified models, processing only the events scheduled at that time
+    /// for these models.
+    ///
+    /// Events targeting other models that are due at or before the new
+    /// simulation time are held back: they are re-queued for the new simulation
+    /// time, preserving their relative order, and are processed by the next
+    /// call to [`Simulation::step`] or related methods, which does not advance
+    /// time until these events are processed. If no event is scheduled for the
+    /// specified models, simulation time remains unchanged and no event is
+    /// processed, even if events are scheduled for other models. Note that
+    /// held-back periodic events are rescheduled relative to the time at which
+    /// they are eventually processed.
+    ///
+    /// Only events scheduled for a model address with the [`Scheduler`] or a
+    /// [`Context`] are attributed to their target model. Actions created from
+    /// an [`EventSource`](crate::ports::EventSource) or a
+    /// [`QuerySource`](crate::ports::QuerySource) may target several models and
+    /// are always held back.
+    ///
+    /// Note that messages sent by the processed events to models that are not
+    /// in the subset, for instance through output ports, are delivered
+    /// normally.
+    pub fn step_subset(&mut self, models: &[AnyAddress]) -> Result<(), ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        let is_in_subset = |action: &Action| {
+            action
+                .target_id()
+                .is_some_and(|id| models.iter().any(|model| model.channel_id() == id))
+        };
+
+        // Pull all actions scheduled up to the time of the first action that
+        // targets the subset, setting aside those that do not.
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let mut subset_time = None;
+        let mut subset_actions = Vec::new();
+        let mut other_actions = Vec::new();
+        while let Some((&key, action)) = scheduler_queue.peek() {
+            match subset_time {
+                Some(time) if key.0 > time => break,
+                None if is_in_subset(action) && !action.is_cancelled() => {
+                    subset_time = Some(key.0);
+                }
+                _ => {}
+            }
+            let (key, action) = scheduler_queue.pull().unwrap();
+            if is_in_subset(&action) {
+                subset_actions.push((key, action));
+            } else if !action.is_cancelled() {
+                other_actions.push((key, action));
+            }
+        }
+
+        // If no action targets the subset, restore the queue and return.
+        let subset_time = match subset_time {
+            Some(time) => time,
+            None => {
+                for (key, action) in subset_actions.into_iter().chain(other_actions) {
+                    scheduler_queue.insert(key, action);
+                }
+
+                return Ok(());
+            }
+        };
+
+        // Process the actions targeting the subset.
+        for (key, action) in subset_actions {
+            scheduler_queue.insert(key, action);
+        }
+        drop(scheduler_queue);
+
+        let result = self.step_to_next(Some(subset_time));
+
+        // Re-queue all other actions at the new simulation time.
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        for ((_, rank, origin_id), action) in other_actions {
+            scheduler_queue.insert((subset_time, rank, origin_id), action);
+        }
+
+        result.map(|_| ())
+    }
+
+    /// Adds a model and its mailbox to a running simulation.
+    ///
+    /// The model and its submodels, if any, are built and initialized with
+    /// [`Model::init`](crate::model::Model::init) before this method returns,
+    /// so that events sent or scheduled to its address thereafter are
+    /// delivered normally. Simulation time remains unchanged.
+    ///
+    /// See [`SimInit::add_model`] for the requirements on `name`. Note that
+    /// models added to a running simulation always have the default
+    /// scheduling rank and are not subject to the initialization scheduling
+    /// check.
+    ///
+    /// If the prototype of the model or of one of its submodels reports
+    /// connection warnings, an [`ExecutionError::UnconnectedPorts`] error is
+    /// returned and the model is not added.
+    pub fn add_model<P: ProtoModel>(
+        &mut self,
+        model: P,
+        mailbox: Mailbox<P::Model>,
+        name: impl Into<String>,
+    ) -> Result<(), ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        let mut name = name.into();
+        if name.is_empty() {
+            name = String::from("<unknown>");
+        };
+        let observer = Box::new(mailbox.0.observer());
+
+        let mut pending_models = Vec::new();
+        add_model(model, mailbox, name.clone(), &mut pending_models);
+        check_connections(&pending_models)?;
+
+        self.observers.push((name, observer));
+
+        for model in pending_models {
+            let model_scheduler = GlobalScheduler::new(
+                self.scheduler_queue.clone(),
+                self.time.reader(),
+                self.is_halted.clone(),
+            );
+            let model_id = self.next_model_id;
+            self.next_model_id += 1;
+            let model = model.spawn(
+                ModelId::new(model_id),
+                model_scheduler,
+                self.instrumentation.clone(),
+                &self.executor,
+                &self.abort_signal,
+            );
+            self.models.insert(model_id, model);
+        }
+
+        self.run()
+    }
+
+    /// Removes a model from a running simulation.
+    ///
+    /// All actions scheduled for the address of the model with the
+    /// [`Scheduler`] or a [`Context`], including periodic ones, are cancelled.
+    /// The model then executes [`Model::terminate`] once all messages already
+    /// in its mailbox are processed, after which its mailbox is closed and the
+    /// model is dropped. This method returns once the model has been dropped.
+    /// Simulation time remains unchanged.
+    ///
+    /// Once the model is removed, events sent to its address from a model
+    /// output or from an [`EventSource`](crate::ports::EventSource) result in
+    /// an [`ExecutionError::NoRecipient`] error, just as for any other address
+    /// with no live mailbox.
+    ///
+    /// Submodels of the removed model are dropped as well, unless their
+    /// address is held by another model, but they do not execute
+    /// [`Model::terminate`] and the actions scheduled for them are not
+    /// cancelled.
+    pub fn remove_model<M: Model>(
+        &mut self,
+        address: impl Into<Address<M>>,
+    ) -> Result<(), ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        let sender = address.into().0;
+        let channel_id = sender.channel_id();
+
+        self.scheduler_queue
+            .lock()
+            .unwrap()
+            .retain(|_, action| action.target_id() != Some(channel_id));
+
+        let model_id = self
+            .models
+            .iter()
+            .find(|(_, model)| model.channel_id == channel_id)
+            .map(|(&model_id, _)| model_id);
+        let terminator = model_id
+            .and_then(|model_id| self.models.get_mut(&model_id))
+            .and_then(|model| model.terminator.take());
+        if let Some(terminator) = terminator {
+            self.executor.spawn_and_forget(terminator);
+            self.run()?;
+        }
+
+        sender.close();
+        self.observers
+            .retain(|(_, observer)| observer.channel_id() != channel_id);
+        self.run()?;
+
+        if let Some(model_id) = model_id {
+            self.models.remove(&model_id);
+        }
+
+        Ok(())
+    }
+
     /// Processes an action immediately, blocking until completion.
     ///
     /// Simulation time remains unchanged. The periodicity of the action, if
     /// any, is ignored.
     pub fn process(&mut self, action: Action) -> Result<(), ExecutionError> {
         action.spawn_and_forget(&self.executor);
-        self.run()
+        self.run()?;
+        self.run_sink_callbacks();
+
+        Ok(())
+    }
+
+    /// Processes a query action immediately, blocking until completion or
+    /// until the specified timeout, or by default the query timeout, elapses.
+    ///
+    /// If the timeout elapses, the simulation is terminated and the replies
+    /// collected so far are lost.
+    #[cfg(feature = "server")]
+    pub(crate) fn process_query_action(
+        &mut self,
+        action: Action,
+        timeout: Option<Duration>,
+    ) -> Result<(), ExecutionError> {
+        action.spawn_and_forget(&self.executor);
+        self.run_with_timeout(timeout.unwrap_or_else(|| self.query_run_timeout()))
     }
 
     /// Processes an event immediately, blocking until completion.
     ///
-    /// Simulation time remains unchanged.
+    /// Simulation time remains unchanged. The event is silently discarded if
+    /// the mailbox targeted by the event is no longer alive, which can be
+    /// checked beforehand with [`Address::is_connected`].
     pub fn process_event<M, F, T, S>(
         &mut self,
         func: F,
@@ -266,7 +940,24 @@ impl Simulation {
     where
         M: Model,
         F: for<'a> InputFn<'a, M, T, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
+    {
+        self.post_event(func, arg, address);
+        self.process_posted_events()
+    }
+
+    /// Posts an event for immediate processing without blocking.
+    ///
+    /// The event is only processed once [`Simulation::process_posted_events`]
+    /// is called, which makes it possible to process concurrently several
+    /// events targeting independent models. Pending posted events are
+    /// otherwise processed by the next call to any method that runs the
+    /// simulation, and always before simulation time is advanced.
+    pub fn post_event<M, F, T, S>(&mut self, func: F, arg: T, address: impl Into<Address<M>>)
+    where
+        M: Model,
+        F: for<'a> InputFn<'a, M, T, S>,
+        T: Send + 'static,
     {
         let sender = address.into().0;
         let fut = async move {
@@ -277,6 +968,7 @@ impl Simulation {
                           scheduler,
                           recycle_box: RecycleBox<()>|
                           -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                        scheduler.record_event_type(&arg);
                         let fut = func.call(model, arg, scheduler);
 
                         coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -286,14 +978,25 @@ impl Simulation {
         };
 
         self.executor.spawn_and_forget(fut);
-        self.run()
+        self.has_posted_events = true;
+    }
+
+    /// Processes all events posted with [`Simulation::post_event`], blocking
+    /// until completion.
+    ///
+    /// Simulation time remains unchanged.
+    pub fn process_posted_events(&mut self) -> Result<(), ExecutionError> {
+        self.run()?;
+        self.run_sink_callbacks();
+
+        Ok(())
     }
 
     /// Processes a query immediately, blocking until completion.
     ///
     /// Simulation time remains unchanged. If the mailbox targeted by the query
-    /// was not found in the simulation, an [`ExecutionError::BadQuery`] is
-    /// returned.
+    /// is no longer alive, an [`ExecutionError::BadQuery`] error is returned
+    /// with a [`QueryError::NoRecipient`] reason.
     pub fn process_query<M, F, T, R, S>(
         &mut self,
         func: F,
@@ -303,7 +1006,7 @@ impl Simulation {
     where
         M: Model,
         F: for<'a> ReplierFn<'a, M, T, R, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
         R: Send + 'static,
     {
         let (reply_writer, mut reply_reader) = slot::slot();
@@ -318,6 +1021,7 @@ impl Simulation {
                           recycle_box: RecycleBox<()>|
                           -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
                         let fut = async move {
+                            scheduler.record_event_type(&arg);
                             let reply = func.call(model, arg, scheduler).await;
                             let _ = reply_writer.write(reply);
                         };
@@ -329,15 +1033,210 @@ impl Simulation {
         };
 
         self.executor.spawn_and_forget(fut);
+        self.run_with_timeout(self.query_run_timeout())?;
+        self.run_sink_callbacks();
+
+        // The reply writer is dropped together with the message if the
+        // targeted mailbox is closed.
+        reply_reader.try_read().map_err(|e| match e {
+            slot::ReadError::Closed => ExecutionError::BadQuery(QueryError::NoRecipient),
+            slot::ReadError::NoValue => ExecutionError::BadQuery(QueryError::NoReply),
+        })
+    }
+
+    /// Broadcasts a query from a query source immediately, blocking until
+    /// completion.
+    ///
+    /// Unlike [`Simulation::process_query`], which targets a single model,
+    /// this returns the replies of all replier ports connected to the source.
+    /// Simulation time remains unchanged. The query is subject to the query
+    /// timeout, if any.
+    pub fn broadcast_query<T, R>(
+        &mut self,
+        source: &QuerySource<T, R>,
+        arg: T,
+    ) -> Result<Vec<R>, ExecutionError>
+    where
+        T: Clone + Send + 'static,
+        R: Send + 'static,
+    {
+        let (action, mut reply_receiver) = source.query(arg);
+
+        action.spawn_and_forget(&self.executor);
+        self.run_with_timeout(self.query_run_timeout())?;
+        self.run_sink_callbacks();
+
+        reply_receiver
+            .take()
+            .map(|replies| replies.collect())
+            .ok_or(ExecutionError::BadQuery(QueryError::NoReply))
+    }
+
+    /// Starts a new episode with the same models and connections.
+    ///
+    /// All scheduled actions are discarded, simulation time is set to the
+    /// specified start time and the [`Model::reset`] method of each model is
+    /// then executed. This method blocks until all models have been reset.
+    /// This is typically much cheaper than building and initializing a new
+    /// bench with [`SimInit`], but note that:
+    ///
+    /// - [`Model::init`] is not executed again,
+    /// - events already collected by event sinks are left untouched,
+    /// - callbacks registered with [`Simulation::on_key_fired`] are dropped,
+    /// - the simulation clock is not reset, so this method is mainly meant for
+    ///   simulations that run as fast as possible.
+    pub fn reset(&mut self, start_time: MonotonicTime) -> Result<(), ExecutionError> {
+        if self.is_terminated {
+            return Err(ExecutionError::Terminated);
+        }
+
+        self.scheduler_queue.lock().unwrap().clear();
+        self.key_callbacks.clear();
+        self.time.write(start_time);
+
+        for model in self.models.values() {
+            self.executor.spawn_and_forget((model.resetter)());
+        }
         self.run()?;
+        self.run_sink_callbacks();
+
+        Ok(())
+    }
+
+    /// Cancels all scheduled actions and returns the number of cancelled
+    /// actions.
+    ///
+    /// Unlike [`Simulation::reset`], this leaves both the state of the models
+    /// and the simulation time untouched. A periodic action counts as a single
+    /// action. Callbacks registered with [`Simulation::on_key_fired`] are
+    /// dropped.
+    pub fn clear_schedule(&mut self) -> usize {
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let action_count = scheduler_queue.len();
+        scheduler_queue.clear();
+        drop(scheduler_queue);
+
+        self.key_callbacks.clear();
+
+        action_count
+    }
+
+    /// Shuts down the simulation, executing [`Model::terminate`] on each live
+    /// model.
+    ///
+    /// Models are terminated one at a time in the reverse order of their
+    /// addition to the simulation, each model processing all messages already
+    /// in its mailbox before its termination. Simulation time remains
+    /// unchanged. Once this method returns, the simulation is terminated and
+    /// any subsequent attempt to run it returns an
+    /// [`ExecutionError::Terminated`] error. Models that were already
+    /// terminated are not terminated again.
+    ///
+    /// Models are terminated even if the simulation was halted or lost
+    /// synchronization with its clock. If the simulation was terminated by a
+    /// panic, a deadlock, a message loss, an exceeded poll budget or a
+    /// timeout, however, models are dropped without being terminated since
+    /// they may be in an inconsistent state or may no longer be running, and
+    /// an [`ExecutionError::Terminated`] error is returned.
+    ///
+    /// This method is called automatically when the simulation is dropped.
+    pub fn shutdown(&mut self) -> Result<(), ExecutionError> {
+        if self.has_failed {
+            return Err(ExecutionError::Terminated);
+        }
 
-        reply_reader
-            .try_read()
-            .map_err(|_| ExecutionError::BadQuery)
+        self.has_posted_events = false;
+        self.is_terminated = true;
+
+        let terminators: Vec<_> = self
+            .models
+            .values_mut()
+            .rev()
+            .filter_map(|model| model.terminator.take())
+            .collect();
+
+        // The executor is run directly since a halted simulation is already
+        // terminated.
+        terminators.into_iter().try_for_each(|terminator| {
+            self.executor.spawn_and_forget(terminator);
+            self.run_executor(self.timeout)
+        })
+    }
+
+    /// Registers a callback to be invoked with each event collected by an event
+    /// sink.
+    ///
+    /// This is a push-based alternative to iterating over the sink. Each time
+    /// the processing of a time slice by [`Simulation::step`] or related
+    /// methods completes, as well as each time a call to
+    /// [`Simulation::process`], [`Simulation::process_event`] or
+    /// [`Simulation::process_query`] completes, the sink is drained and the
+    /// callback is invoked with each collected event in order of collection.
+    ///
+    /// Callbacks are invoked on the thread that drives the simulation once the
+    /// executor is idle, so they never run concurrently with models. Callbacks
+    /// registered with several sinks are invoked in registration order.
+    pub fn add_sink_callback<S, F>(&mut self, mut sink: S, mut callback: F)
+    where
+        S: EventSinkStream + Send + 'static,
+        F: FnMut(S::Item) + Send + 'static,
+    {
+        self.sink_callbacks
+            .push(Box::new(move || sink.by_ref().for_each(&mut callback)));
+    }
+
+    /// Registers a callback to be invoked when the action associated to the
+    /// specified key is processed.
+    ///
+    /// The callback is invoked with the simulation time of the action once all
+    /// actions of its time slice with the same ordering rank have completed.
+    /// For a periodic action, the callback is only invoked on the first
+    /// occurrence. If the action is cancelled before the callback is invoked,
+    /// the callback is dropped without being invoked.
+    ///
+    /// Callbacks are invoked on the thread that drives the simulation, in
+    /// registration order for callbacks registered with the same key.
+    pub fn on_key_fired<F>(&mut self, key: &ActionKey, callback: F)
+    where
+        F: FnOnce(MonotonicTime) + Send + 'static,
+    {
+        // Callbacks registered with already cancelled keys are never invoked;
+        // those of keys cancelled later are pruned at each step.
+        if key.is_cancelled() {
+            return;
+        }
+
+        self.key_callbacks
+            .entry(key.clone())
+            .or_default()
+            .push(Box::new(callback));
+    }
+
+    /// Drains all sinks with a registered callback.
+    fn run_sink_callbacks(&mut self) {
+        for callback in &mut self.sink_callbacks {
+            callback();
+        }
     }
 
     /// Runs the executor.
     fn run(&mut self) -> Result<(), ExecutionError> {
+        self.run_with_timeout(self.timeout)
+    }
+
+    /// Returns the timeout applicable to the processing of a query.
+    fn query_run_timeout(&self) -> Duration {
+        if self.query_timeout.is_zero() {
+            self.timeout
+        } else {
+            self.query_timeout
+        }
+    }
+
+    /// Runs the executor with the specified timeout.
+    fn run_with_timeout(&mut self, timeout: Duration) -> Result<(), ExecutionError> {
+        self.has_posted_events = false;
+
         if self.is_terminated {
             return Err(ExecutionError::Terminated);
         }
@@ -347,8 +1246,17 @@ impl Simulation {
             return Err(ExecutionError::Halted);
         }
 
-        self.executor.run(self.timeout).map_err(|e| {
+        self.run_executor(timeout)
+    }
+
+    /// Runs the executor with the specified timeout, regardless of whether the
+    /// simulation was halted or terminated.
+    ///
+    /// On error, the simulation is terminated and flagged as failed.
+    fn run_executor(&mut self, timeout: Duration) -> Result<(), ExecutionError> {
+        self.executor.run(timeout, self.poll_budget).map_err(|e| {
             self.is_terminated = true;
+            self.has_failed = true;
 
             match e {
                 ExecutorError::UnprocessedMessages(msg_count) => {
@@ -370,10 +1278,12 @@ impl Simulation {
                     }
                 }
                 ExecutorError::Timeout => ExecutionError::Timeout,
+                ExecutorError::PollBudgetExceeded => ExecutionError::PollBudgetExceeded,
                 ExecutorError::Panic(model_id, payload) => {
                     let model = model_id
                         .get()
-                        .map(|id| self.model_names.get(id).unwrap().clone());
+                        .and_then(|id| self.models.get(&id))
+                        .map(|model| model.name.clone());
 
                     // Filter out panics originating from a `SendError`.
                     if (*payload).type_id() == TypeId::of::<SendError>() {
@@ -410,12 +1320,24 @@ impl Simulation {
             return Err(ExecutionError::Halted);
         }
 
+        // Process posted events at the current time.
+        if self.has_posted_events {
+            self.process_posted_events()?;
+        }
+
+        // Drop the callbacks of cancelled actions.
+        self.key_callbacks.retain(|key, _| !key.is_cancelled());
+
         // Function pulling the next action. If the action is periodic, it is
         // immediately re-scheduled.
         fn pull_next_action(scheduler_queue: &mut MutexGuard<SchedulerQueue>) -> Action {
-            let ((time, channel_id), action) = scheduler_queue.pull().unwrap();
+            let ((time, rank, origin_id), action) = scheduler_queue.pull().unwrap();
             if let Some((action_clone, period)) = action.next() {
-                scheduler_queue.insert((time + period, channel_id), action_clone);
+                // A recurrence that would lie beyond the latest representable
+                // time can never be processed and is dropped.
+                if let Some(next_time) = time.checked_add(period) {
+                    scheduler_queue.insert((next_time, rank, origin_id), action_clone);
+                }
             }
 
             action
@@ -447,9 +1369,24 @@ impl Simulation {
             None => return Ok(None),
         };
         self.time.write(current_key.0);
+        let mut is_synchronized = false;
+        let mut fired_callbacks = Vec::new();
+
+        #[cfg(feature = "tracing")]
+        let time_slice = TimeSliceSpan::new(current_key.0, self.time_slice_span.clone());
+        #[cfg(feature = "tracing")]
+        let _enter = time_slice.span.enter();
 
         loop {
             let action = pull_next_action(&mut scheduler_queue);
+            #[cfg(feature = "tracing")]
+            time_slice.record_action();
+            if let Some(entry) = action
+                .key()
+                .and_then(|key| self.key_callbacks.remove_entry(key))
+            {
+                fired_callbacks.push(entry);
+            }
             let mut next_key = peek_next_key(&mut scheduler_queue);
             if next_key != Some(current_key) {
                 // Since there are no other actions with the same origin and the
@@ -463,6 +1400,14 @@ impl Simulation {
                 action_sequence.push(action.into_future());
                 loop {
                     let action = pull_next_action(&mut scheduler_queue);
+                    #[cfg(feature = "tracing")]
+                    time_slice.record_action();
+                    if let Some(entry) = action
+                        .key()
+                        .and_then(|key| self.key_callbacks.remove_entry(key))
+                    {
+                        fired_callbacks.push(entry);
+                    }
                     action_sequence.push(action.into_future());
                     next_key = peek_next_key(&mut scheduler_queue);
                     if next_key != Some(current_key) {
@@ -476,24 +1421,58 @@ impl Simulation {
             }
 
             current_key = match next_key {
-                // If the next action is scheduled at the same time, update the
-                // key and continue.
-                Some(k) if k.0 == current_key.0 => k,
-                // Otherwise wait until all actions have completed and return.
+                // If the next action is scheduled at the same time and with the
+                // same rank, update the key and continue.
+                Some(k) if k.0 == current_key.0 && k.1 == current_key.1 => k,
+                // Otherwise wait until all actions have completed.
                 _ => {
                     drop(scheduler_queue); // make sure the queue's mutex is released.
 
                     let current_time = current_key.0;
-                    if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(current_time) {
-                        if let Some(tolerance) = &self.clock_tolerance {
-                            if &lag > tolerance {
-                                self.is_terminated = true;
+                    if !is_synchronized {
+                        is_synchronized = true;
+                        if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(current_time) {
+                            if let Some(tolerance) = &self.clock_tolerance {
+                                if &lag > tolerance {
+                                    self.is_terminated = true;
 
-                                return Err(ExecutionError::OutOfSync(lag));
+                                    return Err(ExecutionError::OutOfSync(lag));
+                                }
                             }
                         }
                     }
                     self.run()?;
+                    for (key, callbacks) in fired_callbacks.drain(..) {
+                        // The action may have been cancelled after it was
+                        // pulled, e.g. by another action of the time slice.
+                        if key.is_cancelled() {
+                            continue;
+                        }
+                        for callback in callbacks {
+                            callback(current_time);
+                        }
+                    }
+
+                    // If actions with a higher rank remain for the current
+                    // time, process them in a new phase. The next key is
+                    // peeked again since the remaining actions may have been
+                    // cancelled in the meantime.
+                    if matches!(next_key, Some(k) if k.0 == current_time) {
+                        scheduler_queue = self.scheduler_queue.lock().unwrap();
+                        if let Some(k) = peek_next_key(&mut scheduler_queue) {
+                            if k.0 == current_time {
+                                current_key = k;
+                                continue;
+                            }
+                        }
+                        drop(scheduler_queue);
+                    }
+
+                    if self.queue_length_history.is_enabled() {
+                        let queue_length = self.scheduler_queue.lock().unwrap().len();
+                        self.queue_length_history.push(current_time, queue_length);
+                    }
+                    self.run_sink_callbacks();
 
                     return Ok(Some(current_time));
                 }
@@ -542,6 +1521,13 @@ impl Simulation {
         }
     }
 
+    /// Reports whether the simulation was terminated, in which case it can no
+    /// longer be run.
+    #[cfg(feature = "server")]
+    pub(crate) fn is_terminated(&self) -> bool {
+        self.is_terminated
+    }
+
     /// Returns a scheduler handle.
     #[cfg(feature = "server")]
     pub(crate) fn scheduler(&self) -> Scheduler {
@@ -561,6 +1547,143 @@ impl fmt::Debug for Simulation {
     }
 }
 
+impl Drop for Simulation {
+    fn drop(&mut self) {
+        // Models are not terminated while unwinding since the simulation may
+        // be in an inconsistent state.
+        if !std::thread::panicking() {
+            let _ = self.shutdown();
+        }
+    }
+}
+
+/// Callback invoked with the simulation time of a processed keyed action.
+type KeyCallback = Box<dyn FnOnce(MonotonicTime) + Send>;
+
+/// Bounded history of the scheduler queue length.
+///
+/// Records are stored in a buffer with twice the requested capacity so that
+/// the oldest records can be discarded in batches while still exposing the
+/// last records as a contiguous slice.
+struct QueueLengthHistory {
+    records: Vec<(MonotonicTime, usize)>,
+    capacity: usize,
+}
+
+impl QueueLengthHistory {
+    /// Creates a history holding at most `capacity` records.
+    fn new(capacity: usize) -> Self {
+        Self {
+            records: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Checks whether records should be taken.
+    fn is_enabled(&self) -> bool {
+        self.capacity != 0
+    }
+
+    /// Appends a record, discarding old records if necessary.
+    fn push(&mut self, time: MonotonicTime, queue_length: usize) {
+        if self.records.len() == 2 * self.capacity {
+            self.records.drain(..self.capacity);
+        }
+        self.records.push((time, queue_length));
+    }
+
+    /// Returns the most recent records.
+    fn as_slice(&self) -> &[(MonotonicTime, usize)] {
+        let start = self.records.len().saturating_sub(self.capacity);
+
+        &self.records[start..]
+    }
+}
+
+/// Counter of the events processed by models, by event type.
+#[derive(Default)]
+pub(crate) struct EventTypeCounter {
+    counts: Mutex<HashMap<&'static str, u64>>,
+}
+
+impl EventTypeCounter {
+    /// Increments the count of the specified event type.
+    pub(crate) fn record(&self, type_name: &'static str) {
+        *self.counts.lock().unwrap().entry(type_name).or_insert(0) += 1;
+    }
+
+    /// Returns a copy of all counts.
+    fn counts(&self) -> HashMap<&'static str, u64> {
+        self.counts.lock().unwrap().clone()
+    }
+
+    /// Resets all counts.
+    fn reset(&self) {
+        self.counts.lock().unwrap().clear();
+    }
+}
+
+impl fmt::Debug for EventTypeCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventTypeCounter").finish_non_exhaustive()
+    }
+}
+
+/// Recorders of the events and queries processed by models, shared by all
+/// models of a simulation.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Instrumentation {
+    event_type_counter: Option<Arc<EventTypeCounter>>,
+    event_journal: Option<Arc<EventJournal>>,
+}
+
+impl Instrumentation {
+    /// Creates the recorders required by the specified settings.
+    ///
+    /// A single journal is shared by the event journal and the event log: it
+    /// is complete if the event journal is enabled, and bounded to the event
+    /// log capacity otherwise.
+    pub(crate) fn new(
+        event_type_counting: bool,
+        event_journal: bool,
+        event_log_capacity: usize,
+    ) -> Self {
+        Self {
+            event_type_counter: event_type_counting.then(Default::default),
+            event_journal: (event_journal || event_log_capacity != 0)
+                .then(|| Arc::new(EventJournal::new(event_journal, event_log_capacity))),
+        }
+    }
+
+    /// Returns the event type counter, if enabled.
+    fn event_type_counter(&self) -> Option<&EventTypeCounter> {
+        self.event_type_counter.as_deref()
+    }
+
+    /// Returns the event journal, if enabled.
+    fn event_journal(&self) -> Option<&EventJournal> {
+        self.event_journal.as_deref()
+    }
+
+    /// Records the processing of an event or query of the specified type by
+    /// the specified model.
+    ///
+    /// The simulation time is only read if the event journal is enabled.
+    pub(crate) fn record(
+        &self,
+        model_name: &str,
+        type_name: &'static str,
+        time: impl FnOnce() -> MonotonicTime,
+    ) {
+        if let Some(event_type_counter) = &self.event_type_counter {
+            event_type_counter.record(type_name);
+        }
+        if let Some(event_journal) = &self.event_journal {
+            event_journal.record(time(), model_name, type_name);
+        }
+    }
+}
+
 /// Information regarding a deadlocked model.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct DeadlockInfo {
@@ -569,7 +1692,8 @@ pub struct DeadlockInfo {
     /// This is the name of the model, if relevant prepended by the
     /// dot-separated names of all parent models.
     pub model: String,
-    /// Number of messages in the mailbox.
+    /// Number of messages in the mailbox, including a message that awaits a
+    /// concurrency permit (see [`SimInit::add_model_with_concurrency`]).
     pub mailbox_size: usize,
 }
 
@@ -580,7 +1704,8 @@ pub enum ExecutionError {
     /// The simulation has been intentionally stopped.
     Halted,
     /// The simulation has been terminated due to an earlier deadlock, message
-    /// loss, missing recipient, model panic, timeout or synchronization loss.
+    /// loss, missing recipient, model panic, timeout or synchronization loss,
+    /// or because it was shut down.
     Terminated,
     /// The simulation has deadlocked due to the enlisted models.
     ///
@@ -636,6 +1761,14 @@ pub enum ExecutionError {
     ///
     /// See also [`SimInit::set_timeout`] and [`Simulation::set_timeout`].
     Timeout,
+    /// The simulation step has failed to complete within the allocated number
+    /// of task polls.
+    ///
+    /// This is a fatal error: any subsequent attempt to run the simulation will
+    /// return an [`ExecutionError::Terminated`] error.
+    ///
+    /// See also [`Simulation::set_poll_budget`].
+    PollBudgetExceeded,
     /// The simulation has lost synchronization with the clock and lags behind
     /// by the duration given in the payload.
     ///
@@ -644,16 +1777,37 @@ pub enum ExecutionError {
     ///
     /// See also [`SimInit::set_clock_tolerance`].
     OutOfSync(Duration),
-    /// The query did not obtain a response because the mailbox targeted by the
-    /// query was not found in the simulation.
+    /// The query did not obtain a response.
+    ///
+    /// The payload indicates the reason for the missing response.
     ///
     /// This is a non-fatal error.
-    BadQuery,
+    BadQuery(QueryError),
     /// The specified simulation deadline is in the past of the current
-    /// simulation time.
+    /// simulation time, or lies beyond [`MonotonicTime::MAX`], in which case
+    /// the payload is `MonotonicTime::MAX`.
     ///
     /// This is a non-fatal error.
     InvalidDeadline(MonotonicTime),
+    /// A model attempted to schedule an action at or before the simulation
+    /// start time during its initialization.
+    ///
+    /// This error is only reported if enabled with
+    /// [`SimInit::set_init_scheduling_check`].
+    InitScheduledInPast {
+        /// The fully qualified name of the model that attempted to schedule
+        /// the action.
+        model: String,
+    },
+    /// The prototypes of one or more models reported connection warnings.
+    ///
+    /// The payload lists each warning together with the fully qualified name
+    /// of the model.
+    ///
+    /// This is a non-fatal error.
+    ///
+    /// See also [`ProtoModel::validate`].
+    UnconnectedPorts(Vec<(String, ConnectionWarning)>),
 }
 
 impl fmt::Display for ExecutionError {
@@ -706,6 +1860,7 @@ impl fmt::Display for ExecutionError {
                 write!(f, "model '{}' has panicked with the message: '{}'", model, msg)
             }
             Self::Timeout => f.write_str("the simulation step has failed to complete within the allocated time"),
+            Self::PollBudgetExceeded => f.write_str("the simulation step has failed to complete within the allocated number of task polls"),
             Self::OutOfSync(lag) => {
                 write!(
                     f,
@@ -713,7 +1868,10 @@ impl fmt::Display for ExecutionError {
                     lag
                 )
             }
-            Self::BadQuery => f.write_str("the query did not return any response; was the target mailbox added to the simulation?"),
+            Self::BadQuery(reason) => write!(f, "the query did not return any response: {}", reason),
+            Self::InvalidDeadline(time) if *time == MonotonicTime::MAX => f.write_str(
+                "the specified deadline exceeds the latest representable timestamp",
+            ),
             Self::InvalidDeadline(time) => {
                 write!(
                     f,
@@ -721,12 +1879,54 @@ impl fmt::Display for ExecutionError {
                     time
                 )
             }
+            Self::InitScheduledInPast { model } => {
+                write!(
+                    f,
+                    "model '{}' attempted to schedule an action at or before the simulation start time during initialization",
+                    model
+                )
+            }
+            Self::UnconnectedPorts(list) => {
+                f.write_str("the following connection issues have been detected: ")?;
+                let mut first_item = true;
+                for (model, warning) in list {
+                    if first_item {
+                        first_item = false;
+                    } else {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{} in model '{}'", warning, model)?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
 
 impl Error for ExecutionError {}
 
+/// The reason why a query did not obtain a response.
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum QueryError {
+    /// The mailbox targeted by the query is no longer alive.
+    NoRecipient,
+    /// The query was delivered but no reply was produced.
+    NoReply,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoRecipient => f.write_str("the recipient's mailbox is no longer alive"),
+            Self::NoReply => f.write_str("no reply was produced"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
 /// An error returned upon simulation execution or scheduling failure.
 #[derive(Debug)]
 pub enum SimulationError {
@@ -766,47 +1966,212 @@ impl From<SchedulingError> for SimulationError {
     }
 }
 
-/// Adds a model and its mailbox to the simulation bench.
+/// Adds a model and its mailbox to the list of models pending spawning.
+///
+/// The model is built immediately, but its future is only spawned on an
+/// executor by [`PendingModel::spawn`], which makes it possible to add models
+/// to a bench before its executor and scheduler are known.
 pub(crate) fn add_model<P: ProtoModel>(
     model: P,
     mailbox: Mailbox<P::Model>,
     name: String,
-    scheduler: GlobalScheduler,
-    executor: &Executor,
-    abort_signal: &Signal,
-    model_names: &mut Vec<String>,
+    pending_models: &mut Vec<PendingModel>,
 ) {
-    #[cfg(feature = "tracing")]
-    let span = tracing::span!(target: env!("CARGO_PKG_NAME"), tracing::Level::INFO, "model", name);
-
-    let mut build_cx = BuildContext::new(
-        &mailbox,
-        &name,
-        &scheduler,
-        executor,
-        abort_signal,
-        model_names,
-    );
+    let connection_warnings = model.validate().err().unwrap_or_default();
+
+    let mut build_cx = BuildContext::new(&mailbox, &name, pending_models);
     let model = model.build(&mut build_cx);
 
     let address = mailbox.address();
+    let channel_id = address.0.channel_id();
+    let reset_sender = address.0.clone();
     let mut receiver = mailbox.0;
-    let abort_signal = abort_signal.clone();
-    let mut cx = Context::new(name.clone(), scheduler, address);
-    let fut = async move {
-        let mut model = model.init(&mut cx).await.0;
-        while !abort_signal.is_set() && receiver.recv(&mut model, &mut cx).await.is_ok() {}
-    };
+    let model_name = name.clone();
+    let spawner: ModelSpawner = Box::new(
+        move |scheduler, origin_id, instrumentation, abort_signal, limiter| {
+            let mut cx = Context::new(model_name, scheduler, address, origin_id);
+            cx.set_instrumentation(instrumentation);
+            if let Some(limiter) = limiter {
+                receiver.set_limiter(limiter);
+            }
+
+            Box::pin(async move {
+                let mut model = model.init(&mut cx).await.0;
+                while !abort_signal.is_set() && receiver.recv(&mut model, &mut cx).await.is_ok() {}
+            })
+        },
+    );
 
-    let model_id = ModelId::new(model_names.len());
-    model_names.push(name);
+    let terminator_sender = reset_sender.clone();
+    let resetter: ModelResetter = Box::new(move || {
+        let sender = reset_sender.clone();
 
-    #[cfg(not(feature = "tracing"))]
-    let fut = ModelFuture::new(fut, model_id);
-    #[cfg(feature = "tracing")]
-    let fut = ModelFuture::new(fut, model_id, span);
+        Box::pin(async move {
+            // Ignore send errors, which only occur if the model was removed.
+            let _ = sender
+                .send(
+                    |model: &mut P::Model,
+                     cx,
+                     recycle_box: RecycleBox<()>|
+                     -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                        let fut = Model::reset(model, cx);
+
+                        coerce_box!(RecycleBox::recycle(recycle_box, fut))
+                    },
+                )
+                .await;
+        })
+    });
+
+    let terminator: ModelTerminator = Box::pin(async move {
+        // Ignore send errors, which only occur if the model was removed.
+        let _ = terminator_sender
+            .send(
+                |model: &mut P::Model,
+                 cx,
+                 recycle_box: RecycleBox<()>|
+                 -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                    let fut = Model::terminate(model, cx);
+
+                    coerce_box!(RecycleBox::recycle(recycle_box, fut))
+                },
+            )
+            .await;
+    });
+
+    pending_models.push(PendingModel {
+        name,
+        channel_id,
+        spawner,
+        resetter,
+        terminator,
+        limiter: None,
+        connection_warnings,
+    });
+}
+
+/// Returns an [`ExecutionError::UnconnectedPorts`] error if the prototype of
+/// any of the pending models reported connection warnings.
+pub(crate) fn check_connections(pending_models: &[PendingModel]) -> Result<(), ExecutionError> {
+    let warnings: Vec<_> = pending_models
+        .iter()
+        .flat_map(|model| {
+            model
+                .connection_warnings
+                .iter()
+                .map(|warning| (model.name.clone(), warning.clone()))
+        })
+        .collect();
+
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(ExecutionError::UnconnectedPorts(warnings))
+    }
+}
+
+/// Type-erased function producing the future of a model given its scheduler,
+/// its scheduling origin ID, the event recorders, the simulation abort signal
+/// and an optional concurrency limiter.
+type ModelSpawner = Box<
+    dyn FnOnce(
+        GlobalScheduler,
+        usize,
+        Instrumentation,
+        Signal,
+        Option<Arc<Semaphore>>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>>,
+>;
+
+/// Type-erased function producing a future that requests a model to execute
+/// [`Model::reset`].
+type ModelResetter = Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
+
+/// Type-erased future that requests a model to execute [`Model::terminate`].
+type ModelTerminator = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A model spawned on the executor.
+pub(crate) struct SpawnedModel {
+    name: String,
+    channel_id: usize,
+    resetter: ModelResetter,
+    /// The terminator, unless it was already spawned.
+    terminator: Option<ModelTerminator>,
+}
+
+/// A model that was built and added to a bench but not spawned yet.
+pub(crate) struct PendingModel {
+    name: String,
+    channel_id: usize,
+    spawner: ModelSpawner,
+    resetter: ModelResetter,
+    terminator: ModelTerminator,
+    limiter: Option<Arc<Semaphore>>,
+    connection_warnings: Vec<ConnectionWarning>,
+}
+
+impl PendingModel {
+    /// Returns the fully qualified name of the model.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the channel ID of the model's mailbox.
+    pub(crate) fn channel_id(&self) -> usize {
+        self.channel_id
+    }
+
+    /// Sets a semaphore that caps the number of messages processed
+    /// concurrently by this model and all other models sharing the semaphore.
+    pub(crate) fn set_limiter(&mut self, limiter: Arc<Semaphore>) {
+        self.limiter = Some(limiter);
+    }
+
+    /// Spawns the model on the executor with the specified model ID.
+    fn spawn(
+        self,
+        model_id: ModelId,
+        scheduler: GlobalScheduler,
+        instrumentation: Instrumentation,
+        executor: &Executor,
+        abort_signal: &Signal,
+    ) -> SpawnedModel {
+        // Origin IDs follow the order in which models are spawned, which makes
+        // the processing order of actions scheduled by different models for
+        // the same time reproducible from one run to another. ID 0 is
+        // reserved for the global scheduler.
+        let origin_id = model_id.0 + 1;
+
+        let fut = (self.spawner)(
+            scheduler,
+            origin_id,
+            instrumentation,
+            abort_signal.clone(),
+            self.limiter,
+        );
+
+        #[cfg(not(feature = "tracing"))]
+        let fut = ModelFuture::new(fut, model_id);
+        #[cfg(feature = "tracing")]
+        let fut = ModelFuture::new(fut, model_id, self.name.clone());
+
+        executor.spawn_and_forget(fut);
+
+        SpawnedModel {
+            name: self.name,
+            channel_id: self.channel_id,
+            resetter: self.resetter,
+            terminator: Some(self.terminator),
+        }
+    }
+}
 
-    executor.spawn_and_forget(fut);
+impl fmt::Debug for PendingModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingModel")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
 }
 
 /// A unique index assigned to a model instance.
@@ -840,13 +2205,75 @@ impl Default for ModelId {
     }
 }
 
+/// Creates the span of a model, optionally nested within a parent span.
+#[cfg(feature = "tracing")]
+fn model_span(name: &str, parent: Option<tracing::span::Id>) -> tracing::Span {
+    tracing::span!(
+        target: env!("CARGO_PKG_NAME"),
+        parent: parent,
+        tracing::Level::INFO,
+        "model",
+        name
+    )
+}
+
+/// The span of the time slice being processed by the simulation.
+///
+/// The span is published to model tasks for as long as this object is alive,
+/// and records the number of actions processed within the time slice when
+/// dropped.
+#[cfg(feature = "tracing")]
+struct TimeSliceSpan {
+    span: tracing::Span,
+    slot: Arc<RwLock<tracing::Span>>,
+    action_count: Cell<usize>,
+}
+
+#[cfg(feature = "tracing")]
+impl TimeSliceSpan {
+    fn new(time: MonotonicTime, slot: Arc<RwLock<tracing::Span>>) -> Self {
+        let span = tracing::span!(
+            target: env!("CARGO_PKG_NAME"),
+            tracing::Level::DEBUG,
+            "time_slice",
+            time = %time,
+            actions = tracing::field::Empty
+        );
+        *slot.write().unwrap() = span.clone();
+
+        Self {
+            span,
+            slot,
+            action_count: Cell::new(0),
+        }
+    }
+
+    fn record_action(&self) {
+        self.action_count.set(self.action_count.get() + 1);
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for TimeSliceSpan {
+    fn drop(&mut self) {
+        self.span.record("actions", self.action_count.get());
+        *self.slot.write().unwrap() = tracing::Span::none();
+    }
+}
+
 #[pin_project]
 struct ModelFuture<F> {
     #[pin]
     fut: F,
     id: ModelId,
     #[cfg(feature = "tracing")]
+    name: String,
+    /// The model span, nested within the span of the time slice identified by
+    /// `time_slice_id`, if any.
+    #[cfg(feature = "tracing")]
     span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    time_slice_id: Option<tracing::span::Id>,
 }
 
 impl<F> ModelFuture<F> {
@@ -855,8 +2282,16 @@ impl<F> ModelFuture<F> {
         Self { fut, id }
     }
     #[cfg(feature = "tracing")]
-    fn new(fut: F, id: ModelId, span: tracing::Span) -> Self {
-        Self { fut, id, span }
+    fn new(fut: F, id: ModelId, name: String) -> Self {
+        let span = model_span(&name, None);
+
+        Self {
+            fut,
+            id,
+            name,
+            span,
+            time_slice_id: None,
+        }
     }
 }
 
@@ -867,8 +2302,22 @@ impl<F: Future> Future for ModelFuture<F> {
     fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
 
+        // A new model span is created whenever the model is polled within
+        // another time slice, so that model spans are nested within the span
+        // of the time slice they belong to.
         #[cfg(feature = "tracing")]
-        let _enter = this.span.enter();
+        let _enter = {
+            SIMULATION_CONTEXT.map(|cx| {
+                let time_slice_span = cx.time_slice_span.read().unwrap();
+                let time_slice_id = time_slice_span.id();
+                if time_slice_id != *this.time_slice_id {
+                    *this.span = model_span(this.name, time_slice_id.clone());
+                    *this.time_slice_id = time_slice_id;
+                }
+            });
+
+            this.span.enter()
+        };
 
         // The current model ID is not set/unset through a guard or scoped TLS
         // because it must survive panics to identify the last model that was
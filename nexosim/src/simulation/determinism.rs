@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::time::MonotonicTime;
+
+use super::Simulation;
+
+/// A helper that checks whether several runs of a simulation bench process
+/// the same events.
+///
+/// The checker relies on the journal of processed events and queries, which
+/// must be enabled on each run with
+/// [`SimInit::set_event_journal`](crate::simulation::SimInit::set_event_journal).
+/// Each journal entry comprises the simulation time, the fully qualified name
+/// of the model and the type name of the event or query. Since the relative
+/// order in which distinct models process their messages within a time slice is
+/// not deterministic on a multi-threaded executor, only the order of the
+/// messages processed by each model is taken into account, which requires
+/// model names to be unique.
+///
+/// The first simulation passed to [`DeterminismChecker::check`] serves as a
+/// reference for all subsequent ones. Note that journal digests are only
+/// comparable within the same process.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use nexosim::model::Model;
+/// use nexosim::simulation::{DeterminismChecker, Mailbox, SimInit, Simulation};
+/// use nexosim::time::MonotonicTime;
+///
+/// struct MyModel {}
+/// impl MyModel {
+///     async fn input(&mut self, _value: u32) {}
+/// }
+/// impl Model for MyModel {}
+///
+/// fn run(num_threads: usize) -> Simulation {
+///     let mailbox = Mailbox::new();
+///     let address = mailbox.address();
+///     let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+///         .add_model(MyModel {}, mailbox, "model")
+///         .set_event_journal(true)
+///         .init(MonotonicTime::EPOCH)
+///         .unwrap();
+///     scheduler
+///         .schedule_event(Duration::from_secs(1), MyModel::input, 42, &address)
+///         .unwrap();
+///     simu.step().unwrap();
+///
+///     simu
+/// }
+///
+/// let mut checker = DeterminismChecker::new();
+/// checker.check(&run(1)).unwrap();
+/// checker.check(&run(4)).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct DeterminismChecker {
+    reference: Option<Vec<(MonotonicTime, u64)>>,
+}
+
+impl DeterminismChecker {
+    /// Creates a checker with no reference run.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compares the journal of a simulation to that of the reference run.
+    ///
+    /// If no reference was recorded yet, the journal of the simulation becomes
+    /// the reference and the check succeeds. Otherwise, the check fails with a
+    /// [`DeterminismError::Divergence`] error identifying the first time slice
+    /// that differs from the reference, if any.
+    pub fn check(&mut self, simulation: &Simulation) -> Result<(), DeterminismError> {
+        let digests = simulation
+            .instrumentation
+            .event_journal()
+            .filter(|journal| journal.is_complete())
+            .ok_or(DeterminismError::JournalDisabled)?
+            .slice_digests();
+
+        let reference = match &self.reference {
+            Some(reference) => reference,
+            None => {
+                self.reference = Some(digests);
+
+                return Ok(());
+            }
+        };
+
+        let len = digests.len().min(reference.len());
+        let divergence = (0..len)
+            .find(|&slice| digests[slice] != reference[slice])
+            .or((digests.len() != reference.len()).then_some(len));
+
+        match divergence {
+            Some(slice) => {
+                let time = match (digests.get(slice), reference.get(slice)) {
+                    (Some(&(t1, _)), Some(&(t2, _))) => t1.min(t2),
+                    (Some(&(t, _)), None) | (None, Some(&(t, _))) => t,
+                    (None, None) => unreachable!(),
+                };
+
+                Err(DeterminismError::Divergence { slice, time })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Error returned when the determinism of a simulation could not be
+/// established.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DeterminismError {
+    /// The event journal was not enabled for the simulation.
+    JournalDisabled,
+    /// The processed events diverge from those of the reference run.
+    Divergence {
+        /// Index of the first divergent time slice, starting from 0.
+        slice: usize,
+        /// Simulation time of the first divergent time slice.
+        ///
+        /// If both runs processed events at different times, this is the
+        /// earliest of the two.
+        time: MonotonicTime,
+    },
+}
+
+impl fmt::Display for DeterminismError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::JournalDisabled => write!(fmt, "the event journal is not enabled"),
+            Self::Divergence { slice, time } => write!(
+                fmt,
+                "the run diverges from the reference run at time slice {} ({})",
+                slice, time
+            ),
+        }
+    }
+}
+
+impl Error for DeterminismError {}
@@ -0,0 +1,107 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use crate::time::MonotonicTime;
+
+/// Journal of the events and queries processed by models.
+///
+/// A complete journal retains all entries, as required by the
+/// [`DeterminismChecker`](crate::simulation::DeterminismChecker). Otherwise,
+/// the journal acts as a ring buffer that only retains the entries exposed by
+/// the event log.
+pub(crate) struct EventJournal {
+    entries: Mutex<VecDeque<EventLogEntry>>,
+    /// Maximum number of retained entries, or `None` if the journal is
+    /// complete.
+    capacity: Option<usize>,
+    /// Number of most recent entries exposed by the event log.
+    log_capacity: usize,
+}
+
+impl EventJournal {
+    /// Creates a journal retaining all entries if `is_complete` is true, or
+    /// only the `log_capacity` most recent entries otherwise.
+    pub(crate) fn new(is_complete: bool, log_capacity: usize) -> Self {
+        let capacity = (!is_complete).then_some(log_capacity);
+
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity.unwrap_or(0))),
+            capacity,
+            log_capacity,
+        }
+    }
+
+    /// Checks whether the journal retains all entries.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.capacity.is_none()
+    }
+
+    /// Appends an entry for an event or query processed by the specified
+    /// model, discarding the oldest entry if the journal is full.
+    pub(crate) fn record(&self, time: MonotonicTime, model_name: &str, event_type: &'static str) {
+        let mut entries = self.entries.lock().unwrap();
+        if Some(entries.len()) == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(EventLogEntry {
+            time,
+            model_name: model_name.to_owned(),
+            event_type,
+        });
+    }
+
+    /// Returns a copy of the entries exposed by the event log, from the oldest
+    /// to the most recent.
+    pub(crate) fn log_entries(&self) -> Vec<EventLogEntry> {
+        let entries = self.entries.lock().unwrap();
+        let start = entries.len().saturating_sub(self.log_capacity);
+
+        entries.range(start..).cloned().collect()
+    }
+
+    /// Returns the time and digest of each time slice recorded in the journal.
+    ///
+    /// Entries of a time slice are recorded in the order of their processing,
+    /// which for different models is subject to the whims of the executor.
+    /// They are therefore sorted by model name before being hashed, which
+    /// preserves the relative order of the entries of each model.
+    pub(crate) fn slice_digests(&self) -> Vec<(MonotonicTime, u64)> {
+        let mut entries: Vec<_> = self.entries.lock().unwrap().iter().cloned().collect();
+
+        let mut digests = Vec::new();
+        for slice in entries.chunk_by_mut(|a, b| a.time == b.time) {
+            slice.sort_by(|a, b| a.model_name.cmp(&b.model_name));
+
+            let mut hasher = DefaultHasher::new();
+            slice.hash(&mut hasher);
+            digests.push((slice[0].time, hasher.finish()));
+        }
+
+        digests
+    }
+}
+
+impl fmt::Debug for EventJournal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventJournal")
+            .field("capacity", &self.capacity)
+            .field("log_capacity", &self.log_capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An event or query recorded in the event log of a simulation.
+///
+/// See [`Simulation::event_log`](crate::simulation::Simulation::event_log).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EventLogEntry {
+    /// Simulation time at which the event or query was processed.
+    pub time: MonotonicTime,
+    /// Fully qualified name of the model that processed the event or query.
+    pub model_name: String,
+    /// Type of the event or query as returned by [`std::any::type_name`].
+    pub event_type: &'static str,
+}
@@ -1,4 +1,6 @@
+use std::any::Any;
 use std::fmt;
+use std::sync::Arc;
 
 use crate::channel::{Receiver, Sender};
 use crate::model::Model;
@@ -8,7 +10,8 @@ use crate::model::Model;
 /// A mailbox is an entity associated to a model instance that collects all
 /// messages sent to that model. The size of its internal buffer can be
 /// optionally specified at construction time using
-/// [`with_capacity`](Mailbox::with_capacity).
+/// [`with_capacity`](Mailbox::with_capacity), together with the behavior of
+/// senders when the buffer is full using [`with_policy`](Mailbox::with_policy).
 pub struct Mailbox<M: Model>(pub(crate) Receiver<M>);
 
 impl<M: Model> Mailbox<M> {
@@ -30,6 +33,19 @@ impl<M: Model> Mailbox<M> {
         Self(Receiver::new(capacity))
     }
 
+    /// Creates a new mailbox with the specified capacity and overflow policy.
+    ///
+    /// See [`OverflowPolicy`] for the behavior of senders when the mailbox is
+    /// full.
+    ///
+    /// # Panic
+    ///
+    /// The constructor will panic if the requested capacity is 0 or is greater
+    /// than `usize::MAX/2 + 1`.
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self(Receiver::with_overflow_policy(capacity, policy))
+    }
+
     /// Returns a handle to this mailbox.
     pub fn address(&self) -> Address<M> {
         Address(self.0.sender())
@@ -50,6 +66,31 @@ impl<M: Model> fmt::Debug for Mailbox<M> {
     }
 }
 
+/// Behavior of a mailbox when a message is sent while it is full.
+///
+/// Discarding messages makes it possible to break mailbox saturation deadlocks
+/// by design for models for which only the latest messages matter, such as
+/// sensor models. Note that a discarded query is never answered, so a
+/// simulation step that sends a query which ends up being discarded fails
+/// with an [`ExecutionError::NoRecipient`] error; non-blocking policies are
+/// therefore best reserved for mailboxes that only receive events.
+///
+/// [`ExecutionError::NoRecipient`]: crate::simulation::ExecutionError::NoRecipient
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OverflowPolicy {
+    /// The sender waits until the model has processed enough messages for the
+    /// new message to fit in the mailbox.
+    ///
+    /// This is the behavior of mailboxes created with [`Mailbox::new`] or
+    /// [`Mailbox::with_capacity`].
+    Block,
+    /// The oldest message in the mailbox is discarded to make room for the new
+    /// message.
+    DropOldest,
+    /// The new message is discarded.
+    DropNewest,
+}
+
 /// Handle to a model mailbox.
 ///
 /// An address always points to the same mailbox. Unlike a [`Mailbox`], however,
@@ -61,6 +102,20 @@ impl<M: Model> fmt::Debug for Mailbox<M> {
 /// `Address::clone` or `Mailbox::address` as appropriate.
 pub struct Address<M: Model>(pub(crate) Sender<M>);
 
+impl<M: Model> Address<M> {
+    /// Reports whether the mailbox of this address can still receive
+    /// messages.
+    ///
+    /// This returns `false` if the mailbox was dropped, for instance because
+    /// it was never added to the simulation bench, or if its model was
+    /// removed with
+    /// [`Simulation::remove_model`](crate::simulation::Simulation::remove_model).
+    /// Events sent to such an address are lost.
+    pub fn is_connected(&self) -> bool {
+        !self.0.is_closed()
+    }
+}
+
 impl<M: Model> Clone for Address<M> {
     fn clone(&self) -> Self {
         Self(self.0.clone())
@@ -94,3 +149,57 @@ impl<M: Model> fmt::Debug for Address<M> {
             .finish_non_exhaustive()
     }
 }
+
+/// A type-erased handle to a model mailbox.
+///
+/// An `AnyAddress` can be obtained from the [`Address`] of a model of any type,
+/// which makes it possible to refer to several models of different types in a
+/// single collection.
+#[derive(Clone)]
+pub struct AnyAddress {
+    channel_id: usize,
+    // Keeps the mailbox alive so that its channel ID cannot be reused.
+    address: Arc<dyn Any + Send + Sync>,
+}
+
+impl AnyAddress {
+    /// Returns the channel ID of the mailbox.
+    pub(crate) fn channel_id(&self) -> usize {
+        self.channel_id
+    }
+
+    /// Returns the typed address if the mailbox belongs to a model of type
+    /// `M`.
+    pub(crate) fn downcast<M: Model>(&self) -> Option<Address<M>> {
+        self.address.downcast_ref::<Address<M>>().cloned()
+    }
+}
+
+impl<M: Model> From<Address<M>> for AnyAddress {
+    fn from(address: Address<M>) -> Self {
+        Self {
+            channel_id: address.0.channel_id(),
+            address: Arc::new(address),
+        }
+    }
+}
+
+impl<M: Model> From<&Address<M>> for AnyAddress {
+    fn from(address: &Address<M>) -> Self {
+        address.clone().into()
+    }
+}
+
+impl<M: Model> From<&Mailbox<M>> for AnyAddress {
+    fn from(mailbox: &Mailbox<M>) -> Self {
+        mailbox.address().into()
+    }
+}
+
+impl fmt::Debug for AnyAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyAddress")
+            .field("mailbox_id", &self.channel_id.to_string())
+            .finish_non_exhaustive()
+    }
+}
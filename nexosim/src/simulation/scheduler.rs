@@ -16,7 +16,9 @@ use crate::channel::Sender;
 use crate::executor::Executor;
 use crate::model::Model;
 use crate::ports::InputFn;
-use crate::simulation::Address;
+#[cfg(feature = "server")]
+use crate::registry::{ActionKeyRegistry, EventKey};
+use crate::simulation::{Address, CURRENT_MODEL_ID};
 use crate::time::{AtomicTimeReader, Deadline, MonotonicTime};
 use crate::util::priority_queue::PriorityQueue;
 
@@ -58,6 +60,42 @@ impl Scheduler {
         self.0.time()
     }
 
+    /// Returns a scheduler handle that schedules actions with the specified
+    /// ordering rank.
+    ///
+    /// Within a given simulation time, all actions with a lower rank are
+    /// guaranteed to have been processed to completion, including all events
+    /// and queries they triggered, before any action with a higher rank is
+    /// processed. This makes it possible, for instance, to guarantee that the
+    /// events of one event source are always processed by an input before
+    /// those of another source.
+    ///
+    /// Actions scheduled by other scheduler handles have a rank of 0. See also
+    /// [`SimInit::set_scheduling_rank`](crate::simulation::SimInit::set_scheduling_rank)
+    /// to set the rank of actions scheduled by models.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use nexosim::ports::EventSource;
+    /// use nexosim::simulation::{Scheduler, SchedulingError};
+    ///
+    /// fn schedule_in_order(
+    ///     scheduler: &Scheduler,
+    ///     first: &EventSource<u32>,
+    ///     second: &EventSource<u32>,
+    /// ) -> Result<(), SchedulingError> {
+    ///     let deadline = Duration::from_secs(1);
+    ///     scheduler.with_rank(1).schedule(deadline, second.event(2))?;
+    ///     scheduler.with_rank(0).schedule(deadline, first.event(1))
+    /// }
+    /// ```
+    pub fn with_rank(&self, rank: i32) -> Self {
+        Self(self.0.clone().with_rank(rank))
+    }
+
     /// Schedules an action at a future time.
     ///
     /// An error is returned if the specified time is not in the future of the
@@ -71,10 +109,47 @@ impl Scheduler {
             .schedule_from(deadline, action, GLOBAL_SCHEDULER_ORIGIN_ID)
     }
 
+    /// Schedules an action at a future time or, if the specified time lies in
+    /// the past by no more than the specified tolerance, at the current
+    /// simulation time.
+    ///
+    /// Actions scheduled for the current simulation time are processed by the
+    /// next simulation step before simulation time advances. The effective
+    /// scheduling time is returned.
+    #[cfg(feature = "server")]
+    pub(crate) fn schedule_with_tolerance(
+        &self,
+        time: MonotonicTime,
+        tolerance: Duration,
+        action: Action,
+    ) -> Result<MonotonicTime, SchedulingError> {
+        self.0
+            .schedule_with_tolerance_from(time, tolerance, action, GLOBAL_SCHEDULER_ORIGIN_ID)
+    }
+
+    /// Schedules several actions at once, either all or none of them.
+    ///
+    /// Each action is scheduled at its specified time or, if a tolerance is
+    /// specified and the time lies in the past by no more than this
+    /// tolerance, at the current simulation time. If any of the actions cannot
+    /// be scheduled, an error is returned and no action is scheduled.
+    /// Otherwise, the effective scheduling times are returned in the order of
+    /// the actions.
+    #[cfg(feature = "server")]
+    pub(crate) fn schedule_all(
+        &self,
+        actions: Vec<(MonotonicTime, Action)>,
+        tolerance: Option<Duration>,
+    ) -> Result<Vec<MonotonicTime>, SchedulingError> {
+        self.0
+            .schedule_all_from(actions, tolerance, GLOBAL_SCHEDULER_ORIGIN_ID)
+    }
+
     /// Schedules an event at a future time.
     ///
     /// An error is returned if the specified time is not in the future of the
-    /// current simulation time.
+    /// current simulation time or if the mailbox of the target model was
+    /// dropped.
     ///
     /// Events scheduled for the same time and targeting the same model are
     /// guaranteed to be processed according to the scheduling order.
@@ -88,7 +163,7 @@ impl Scheduler {
     where
         M: Model,
         F: for<'a> InputFn<'a, M, T, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
         S: Send + 'static,
     {
         self.0
@@ -98,7 +173,8 @@ impl Scheduler {
     /// Schedules a cancellable event at a future time and returns an event key.
     ///
     /// An error is returned if the specified time is not in the future of the
-    /// current simulation time.
+    /// current simulation time or if the mailbox of the target model was
+    /// dropped.
     ///
     /// Events scheduled for the same time and targeting the same model are
     /// guaranteed to be processed according to the scheduling order.
@@ -112,7 +188,7 @@ impl Scheduler {
     where
         M: Model,
         F: for<'a> InputFn<'a, M, T, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
         S: Send + 'static,
     {
         self.0
@@ -122,7 +198,8 @@ impl Scheduler {
     /// Schedules a periodically recurring event at a future time.
     ///
     /// An error is returned if the specified time is not in the future of the
-    /// current simulation time or if the specified period is null.
+    /// current simulation time, if the specified period is null or if the
+    /// mailbox of the target model was dropped.
     ///
     /// Events scheduled for the same time and targeting the same model are
     /// guaranteed to be processed according to the scheduling order.
@@ -154,7 +231,8 @@ impl Scheduler {
     /// and returns an event key.
     ///
     /// An error is returned if the specified time is not in the future of the
-    /// current simulation time or if the specified period is null.
+    /// current simulation time, if the specified period is null or if the
+    /// mailbox of the target model was dropped.
     ///
     /// Events scheduled for the same time and targeting the same model are
     /// guaranteed to be processed according to the scheduling order.
@@ -235,6 +313,8 @@ impl ActionKey {
     }
 
     /// Cancels the associated action.
+    ///
+    /// If the action is periodic, all its future occurrences are cancelled.
     pub fn cancel(self) {
         self.is_cancelled.store(true, Ordering::Relaxed);
     }
@@ -245,6 +325,37 @@ impl ActionKey {
             is_cancelled: self.is_cancelled,
         }
     }
+
+    /// Makes this key available to remote clients and returns its serializable
+    /// identifier.
+    ///
+    /// A clone of the key is stored in the provided registry. The returned
+    /// `EventKey` can be passed to the `CancelEvent` remote procedure call,
+    /// which will then extract the key from the registry, or converted back
+    /// in-process with [`ActionKey::from_event_key`].
+    ///
+    /// The key does not expire: unless it is extracted by `CancelEvent`, it
+    /// remains in the registry until removed with
+    /// [`ActionKeyRegistry::remove`].
+    #[cfg(feature = "server")]
+    pub fn to_event_key(&self, registry: &ActionKeyRegistry) -> EventKey {
+        registry.register(self)
+    }
+
+    /// Retrieves the action key associated to a serializable identifier.
+    ///
+    /// The identifier may have been returned either by the `ScheduleEvent`
+    /// remote procedure call or by [`ActionKey::to_event_key`]. The returned
+    /// key is a clone of the registered key, which therefore remains available
+    /// to remote clients.
+    ///
+    /// Returns `None` if the identifier does not designate a key of the
+    /// registry, which may in particular be the case if the key was already
+    /// used by a remote client or if it has expired.
+    #[cfg(feature = "server")]
+    pub fn from_event_key(event_key: EventKey, registry: &ActionKeyRegistry) -> Option<Self> {
+        registry.get(event_key)
+    }
 }
 
 impl PartialEq for ActionKey {
@@ -268,7 +379,8 @@ impl Hash for ActionKey {
     }
 }
 
-/// Error returned when the scheduled time or the repetition period are invalid.
+/// Error returned when an action cannot be scheduled.
+#[non_exhaustive]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SchedulingError {
     /// The scheduled time does not lie in the future of the current simulation
@@ -276,6 +388,13 @@ pub enum SchedulingError {
     InvalidScheduledTime,
     /// The repetition period is zero.
     NullRepetitionPeriod,
+    /// The scheduled time lies beyond the latest representable
+    /// [`MonotonicTime`].
+    TimeOverflow,
+    /// The mailbox of the target model was dropped, for instance because it
+    /// was never added to the simulation bench or because the model was
+    /// removed.
+    NoRecipient,
 }
 
 impl fmt::Display for SchedulingError {
@@ -286,6 +405,11 @@ impl fmt::Display for SchedulingError {
                 "the scheduled time should be in the future of the current simulation time"
             ),
             Self::NullRepetitionPeriod => write!(fmt, "the repetition period cannot be zero"),
+            Self::TimeOverflow => write!(
+                fmt,
+                "the scheduled time exceeds the latest representable timestamp"
+            ),
+            Self::NoRecipient => write!(fmt, "the target model does not exist"),
         }
     }
 }
@@ -302,12 +426,29 @@ impl Error for SchedulingError {}
 /// [`Simulation::process`](crate::simulation::Simulation::process).
 pub struct Action {
     inner: Box<dyn ActionInner>,
+    target_id: Option<usize>,
 }
 
 impl Action {
     /// Creates a new `Action` from an `ActionInner`.
     pub(crate) fn new<S: ActionInner>(s: S) -> Self {
-        Self { inner: Box::new(s) }
+        Self {
+            inner: Box::new(s),
+            target_id: None,
+        }
+    }
+
+    /// Sets the channel ID of the mailbox targeted by the action.
+    pub(crate) fn with_target(mut self, target_id: usize) -> Self {
+        self.target_id = Some(target_id);
+
+        self
+    }
+
+    /// Returns the channel ID of the mailbox targeted by the action, if the
+    /// action is known to target a single mailbox.
+    pub(crate) fn target_id(&self) -> Option<usize> {
+        self.target_id
     }
 
     /// Reports whether the action was cancelled.
@@ -315,12 +456,23 @@ impl Action {
         self.inner.is_cancelled()
     }
 
+    /// Returns the cancellation key of the action, if any.
+    pub(crate) fn key(&self) -> Option<&ActionKey> {
+        self.inner.key()
+    }
+
     /// If this is a periodic action, returns a boxed clone of this action and
     /// its repetition period; otherwise returns `None`.
     pub(crate) fn next(&self) -> Option<(Action, Duration)> {
-        self.inner
-            .next()
-            .map(|(inner, period)| (Self { inner }, period))
+        self.inner.next().map(|(inner, period)| {
+            (
+                Self {
+                    inner,
+                    target_id: self.target_id,
+                },
+                period,
+            )
+        })
     }
 
     /// Returns a boxed future that performs the action.
@@ -351,7 +503,12 @@ impl fmt::Debug for Action {
 /// scheduler). The preservation of this ordering is implemented by the event
 /// loop, which aggregate events with the same origin into single sequential
 /// futures, thus ensuring that they are not executed concurrently.
-pub(crate) type SchedulerQueue = PriorityQueue<(MonotonicTime, usize), Action>;
+///
+/// The ordering rank, which sits between the time and the origin ID, is used by
+/// the event loop to split the processing of a time slice into successive
+/// phases: all actions with a given rank are run to completion before actions
+/// with a higher rank are processed.
+pub(crate) type SchedulerQueue = PriorityQueue<(MonotonicTime, i32, usize), Action>;
 
 /// Internal implementation of the global scheduler.
 #[derive(Clone)]
@@ -359,6 +516,8 @@ pub(crate) struct GlobalScheduler {
     scheduler_queue: Arc<Mutex<SchedulerQueue>>,
     time: AtomicTimeReader,
     is_halted: Arc<AtomicBool>,
+    init_monitor: Option<Arc<InitSchedulingMonitor>>,
+    rank: i32,
 }
 
 impl GlobalScheduler {
@@ -371,9 +530,26 @@ impl GlobalScheduler {
             scheduler_queue,
             time,
             is_halted,
+            init_monitor: None,
+            rank: 0,
         }
     }
 
+    /// Sets the ordering rank of all actions scheduled with this scheduler.
+    pub(crate) fn with_rank(mut self, rank: i32) -> Self {
+        self.rank = rank;
+
+        self
+    }
+
+    /// Attaches a monitor that records attempts to schedule actions in the
+    /// past during simulation initialization.
+    pub(crate) fn with_init_monitor(mut self, init_monitor: Arc<InitSchedulingMonitor>) -> Self {
+        self.init_monitor = Some(init_monitor);
+
+        self
+    }
+
     /// Returns the current simulation time.
     pub(crate) fn time(&self) -> MonotonicTime {
         // We use `read` rather than `try_read` because the scheduler can be
@@ -401,16 +577,68 @@ impl GlobalScheduler {
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
 
         let now = self.time();
-        let time = deadline.into_time(now);
+        let time = deadline
+            .checked_into_time(now)
+            .ok_or(SchedulingError::TimeOverflow)?;
         if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
+            return Err(self.invalid_scheduled_time());
         }
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, self.rank, origin_id), action);
 
         Ok(())
     }
 
+    /// Schedules an action identified by its origin at a future time or, if
+    /// the specified time lies in the past by no more than the specified
+    /// tolerance, at the current simulation time.
+    #[cfg(feature = "server")]
+    pub(crate) fn schedule_with_tolerance_from(
+        &self,
+        time: MonotonicTime,
+        tolerance: Duration,
+        action: Action,
+        origin_id: usize,
+    ) -> Result<MonotonicTime, SchedulingError> {
+        // The scheduler queue must always be locked when reading the time (see
+        // `schedule_from`).
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let time = tolerated_time(time, self.time(), Some(tolerance))
+            .ok_or_else(|| self.invalid_scheduled_time())?;
+
+        scheduler_queue.insert((time, self.rank, origin_id), action);
+
+        Ok(time)
+    }
+
+    /// Schedules several actions identified by their origin, either all or
+    /// none of them.
+    #[cfg(feature = "server")]
+    pub(crate) fn schedule_all_from(
+        &self,
+        actions: Vec<(MonotonicTime, Action)>,
+        tolerance: Option<Duration>,
+        origin_id: usize,
+    ) -> Result<Vec<MonotonicTime>, SchedulingError> {
+        // The scheduler queue must always be locked when reading the time (see
+        // `schedule_from`). Holding the lock while checking all times also
+        // ensures that simulation time cannot advance before all actions are
+        // inserted.
+        let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
+        let now = self.time();
+        let times = actions
+            .iter()
+            .map(|(time, _)| tolerated_time(*time, now, tolerance))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| self.invalid_scheduled_time())?;
+
+        for (&time, (_, action)) in times.iter().zip(actions) {
+            scheduler_queue.insert((time, self.rank, origin_id), action);
+        }
+
+        Ok(times)
+    }
+
     /// Schedules an event identified by its origin at a future time.
     pub(crate) fn schedule_event_from<M, F, T, S>(
         &self,
@@ -423,22 +651,29 @@ impl GlobalScheduler {
     where
         M: Model,
         F: for<'a> InputFn<'a, M, T, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
         S: Send + 'static,
     {
         let sender = address.into().0;
-        let action = Action::new(OnceAction::new(process_event(func, arg, sender)));
+        if sender.is_closed() {
+            return Err(SchedulingError::NoRecipient);
+        }
+        let target_id = sender.channel_id();
+        let action =
+            Action::new(OnceAction::new(process_event(func, arg, sender))).with_target(target_id);
 
         // The scheduler queue must always be locked when reading the time (see
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
+        let time = deadline
+            .checked_into_time(now)
+            .ok_or(SchedulingError::TimeOverflow)?;
         if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
+            return Err(self.invalid_scheduled_time());
         }
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, self.rank, origin_id), action);
 
         Ok(())
     }
@@ -456,26 +691,33 @@ impl GlobalScheduler {
     where
         M: Model,
         F: for<'a> InputFn<'a, M, T, S>,
-        T: Send + Clone + 'static,
+        T: Send + 'static,
         S: Send + 'static,
     {
         let event_key = ActionKey::new();
         let sender = address.into().0;
+        if sender.is_closed() {
+            return Err(SchedulingError::NoRecipient);
+        }
+        let target_id = sender.channel_id();
         let action = Action::new(KeyedOnceAction::new(
             |ek| send_keyed_event(ek, func, arg, sender),
             event_key.clone(),
-        ));
+        ))
+        .with_target(target_id);
 
         // The scheduler queue must always be locked when reading the time (see
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
+        let time = deadline
+            .checked_into_time(now)
+            .ok_or(SchedulingError::TimeOverflow)?;
         if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
+            return Err(self.invalid_scheduled_time());
         }
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, self.rank, origin_id), action);
 
         Ok(event_key)
     }
@@ -501,21 +743,28 @@ impl GlobalScheduler {
             return Err(SchedulingError::NullRepetitionPeriod);
         }
         let sender = address.into().0;
+        if sender.is_closed() {
+            return Err(SchedulingError::NoRecipient);
+        }
+        let target_id = sender.channel_id();
         let action = Action::new(PeriodicAction::new(
             || process_event(func, arg, sender),
             period,
-        ));
+        ))
+        .with_target(target_id);
 
         // The scheduler queue must always be locked when reading the time (see
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
+        let time = deadline
+            .checked_into_time(now)
+            .ok_or(SchedulingError::TimeOverflow)?;
         if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
+            return Err(self.invalid_scheduled_time());
         }
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, self.rank, origin_id), action);
 
         Ok(())
     }
@@ -542,22 +791,29 @@ impl GlobalScheduler {
         }
         let event_key = ActionKey::new();
         let sender = address.into().0;
+        if sender.is_closed() {
+            return Err(SchedulingError::NoRecipient);
+        }
+        let target_id = sender.channel_id();
         let action = Action::new(KeyedPeriodicAction::new(
             |ek| send_keyed_event(ek, func, arg, sender),
             period,
             event_key.clone(),
-        ));
+        ))
+        .with_target(target_id);
 
         // The scheduler queue must always be locked when reading the time (see
         // `schedule_from`).
         let mut scheduler_queue = self.scheduler_queue.lock().unwrap();
         let now = self.time();
-        let time = deadline.into_time(now);
+        let time = deadline
+            .checked_into_time(now)
+            .ok_or(SchedulingError::TimeOverflow)?;
         if now >= time {
-            return Err(SchedulingError::InvalidScheduledTime);
+            return Err(self.invalid_scheduled_time());
         }
 
-        scheduler_queue.insert((time, origin_id), action);
+        scheduler_queue.insert((time, self.rank, origin_id), action);
 
         Ok(event_key)
     }
@@ -566,6 +822,72 @@ impl GlobalScheduler {
     pub(crate) fn halt(&mut self) {
         self.is_halted.store(true, Ordering::Relaxed);
     }
+
+    /// Returns the error for an action scheduled at or before the current
+    /// time, reporting it to the initialization monitor if any.
+    fn invalid_scheduled_time(&self) -> SchedulingError {
+        if let Some(init_monitor) = &self.init_monitor {
+            init_monitor.report();
+        }
+
+        SchedulingError::InvalidScheduledTime
+    }
+}
+
+/// Returns the time at which an action can be scheduled given the current
+/// simulation time, or `None` if the requested time is in the past by more than
+/// the tolerance, if any.
+///
+/// A time in the past but within the tolerance is moved to the current time.
+#[cfg(feature = "server")]
+fn tolerated_time(
+    time: MonotonicTime,
+    now: MonotonicTime,
+    tolerance: Option<Duration>,
+) -> Option<MonotonicTime> {
+    if time > now {
+        return Some(time);
+    }
+
+    let tolerance = tolerance?;
+    let is_within_tolerance = match now.checked_sub(tolerance) {
+        Some(earliest) => time >= earliest,
+        None => true,
+    };
+
+    is_within_tolerance.then_some(now)
+}
+
+/// A monitor recording the first model that attempts to schedule an action at
+/// or before the simulation start time while models are being initialized.
+#[derive(Debug, Default)]
+pub(crate) struct InitSchedulingMonitor {
+    is_active: AtomicBool,
+    offending_model: Mutex<Option<usize>>,
+}
+
+impl InitSchedulingMonitor {
+    /// Enables or disables the recording of scheduling attempts.
+    pub(crate) fn set_active(&self, is_active: bool) {
+        self.is_active.store(is_active, Ordering::Relaxed);
+    }
+
+    /// Records the model being currently polled, unless a model was already
+    /// recorded or the monitor is inactive.
+    fn report(&self) {
+        if !self.is_active.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut offending_model = self.offending_model.lock().unwrap();
+        if offending_model.is_none() {
+            *offending_model = CURRENT_MODEL_ID.get().get();
+        }
+    }
+
+    /// Returns the index of the recorded model, if any.
+    pub(crate) fn offending_model(&self) -> Option<usize> {
+        *self.offending_model.lock().unwrap()
+    }
 }
 
 impl fmt::Debug for GlobalScheduler {
@@ -581,6 +903,9 @@ pub(crate) trait ActionInner: Send + 'static {
     /// Reports whether the action was cancelled.
     fn is_cancelled(&self) -> bool;
 
+    /// Returns the cancellation key of the action, if any.
+    fn key(&self) -> Option<&ActionKey>;
+
     /// If this is a periodic action, returns a boxed clone of this action and
     /// its repetition period; otherwise returns `None`.
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)>;
@@ -637,6 +962,9 @@ where
     fn is_cancelled(&self) -> bool {
         false
     }
+    fn key(&self) -> Option<&ActionKey> {
+        None
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         None
     }
@@ -681,6 +1009,9 @@ where
     fn is_cancelled(&self) -> bool {
         false
     }
+    fn key(&self) -> Option<&ActionKey> {
+        None
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         let event = Box::new(Self::new(self.gen.clone(), self.period));
 
@@ -726,6 +1057,9 @@ where
     fn is_cancelled(&self) -> bool {
         self.event_key.is_cancelled()
     }
+    fn key(&self) -> Option<&ActionKey> {
+        Some(&self.event_key)
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         None
     }
@@ -775,6 +1109,9 @@ where
     fn is_cancelled(&self) -> bool {
         self.event_key.is_cancelled()
     }
+    fn key(&self) -> Option<&ActionKey> {
+        Some(&self.event_key)
+    }
     fn next(&self) -> Option<(Box<dyn ActionInner>, Duration)> {
         let event = Box::new(Self::new(
             self.gen.clone(),
@@ -805,6 +1142,7 @@ where
                   scheduler,
                   recycle_box: RecycleBox<()>|
                   -> RecycleBox<dyn Future<Output = ()> + Send + '_> {
+                scheduler.record_event_type(&arg);
                 let fut = func.call(model, arg, scheduler);
 
                 coerce_box!(RecycleBox::recycle(recycle_box, fut))
@@ -822,7 +1160,7 @@ pub(crate) async fn send_keyed_event<M, F, T, S>(
 ) where
     M: Model,
     F: for<'a> InputFn<'a, M, T, S>,
-    T: Send + Clone + 'static,
+    T: Send + 'static,
 {
     let _ = sender
         .send(
@@ -833,6 +1171,7 @@ pub(crate) async fn send_keyed_event<M, F, T, S>(
                 let fut = async move {
                     // Only perform the call if the event wasn't cancelled.
                     if !event_key.is_cancelled() {
+                        scheduler.record_event_type(&arg);
                         func.call(model, arg, scheduler).await;
                     }
                 };
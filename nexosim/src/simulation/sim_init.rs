@@ -1,18 +1,23 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::atomic::AtomicBool;
+#[cfg(feature = "tracing")]
+use std::sync::RwLock;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crate::channel::ChannelObserver;
 use crate::executor::{Executor, SimulationContext};
-use crate::model::ProtoModel;
+use crate::model::{Model, ProtoModel};
 use crate::time::{AtomicTime, Clock, MonotonicTime, NoClock, SyncStatus, TearableAtomicTime};
 use crate::util::priority_queue::PriorityQueue;
+use crate::util::semaphore::Semaphore;
 use crate::util::sync_cell::SyncCell;
 
 use super::{
-    add_model, ExecutionError, GlobalScheduler, Mailbox, Scheduler, SchedulerQueue, Signal,
-    Simulation,
+    add_model, check_connections, Address, AnyAddress, ExecutionError, GlobalScheduler,
+    InitSchedulingMonitor, Instrumentation, Mailbox, ModelId, PendingModel, Scheduler,
+    SchedulerQueue, Signal, Simulation,
 };
 
 /// Builder for a multi-threaded, discrete-event simulation.
@@ -25,8 +30,17 @@ pub struct SimInit {
     clock_tolerance: Option<Duration>,
     timeout: Duration,
     observers: Vec<(String, Box<dyn ChannelObserver>)>,
+    addresses: Vec<(String, AnyAddress)>,
     abort_signal: Signal,
-    model_names: Vec<String>,
+    pending_models: Vec<PendingModel>,
+    queue_length_history_capacity: usize,
+    init_monitor: Option<Arc<InitSchedulingMonitor>>,
+    scheduling_ranks: HashMap<usize, i32>,
+    event_type_counting: bool,
+    event_journal: bool,
+    event_log_capacity: usize,
+    #[cfg(feature = "tracing")]
+    time_slice_span: Arc<RwLock<tracing::Span>>,
 }
 
 impl SimInit {
@@ -49,9 +63,12 @@ impl SimInit {
             num_threads.clamp(1, usize::BITS as usize)
         };
         let time = SyncCell::new(TearableAtomicTime::new(MonotonicTime::EPOCH));
+        #[cfg(feature = "tracing")]
+        let time_slice_span = Arc::new(RwLock::new(tracing::Span::none()));
         let simulation_context = SimulationContext {
-            #[cfg(feature = "tracing")]
             time_reader: time.reader(),
+            #[cfg(feature = "tracing")]
+            time_slice_span: time_slice_span.clone(),
         };
 
         let abort_signal = Signal::new();
@@ -70,11 +87,72 @@ impl SimInit {
             clock_tolerance: None,
             timeout: Duration::ZERO,
             observers: Vec::new(),
+            addresses: Vec::new(),
             abort_signal,
-            model_names: Vec::new(),
+            pending_models: Vec::new(),
+            queue_length_history_capacity: 0,
+            init_monitor: None,
+            scheduling_ranks: HashMap::new(),
+            event_type_counting: false,
+            event_journal: false,
+            event_log_capacity: 0,
+            #[cfg(feature = "tracing")]
+            time_slice_span,
         }
     }
 
+    /// Creates a builder for a simulation meant for performance regression
+    /// testing.
+    ///
+    /// The full scheduling and broadcasting machinery is exercised, but since
+    /// the simulation runs in the reproducible order of
+    /// [`SimInit::deterministic`], the wall clock time of a run is free from
+    /// the variability caused by thread scheduling and work stealing.
+    ///
+    /// Note that the measured performance is not representative of the
+    /// throughput of a multi-threaded simulation, which benefits from
+    /// parallelism but also bears synchronization costs.
+    pub fn with_benchmark_executor() -> Self {
+        Self::deterministic()
+    }
+
+    /// Creates a builder for a simulation with a reproducible execution order.
+    ///
+    /// The simulation runs on a single-threaded executor, so the order in
+    /// which models process their messages only depends on the bench and on
+    /// the scheduled actions. Within a time slice, actions are spawned by
+    /// increasing rank, then by origin (the global scheduler first, followed
+    /// by models in the order in which they were added), then in the order in
+    /// which they were scheduled. A given bench fed with the same inputs
+    /// therefore behaves identically from one run to another, down to the
+    /// relative order of events processed by distinct models.
+    ///
+    /// This is equivalent to `SimInit::with_num_threads(1)`.
+    pub fn deterministic() -> Self {
+        Self::with_num_threads(1)
+    }
+
+    /// Returns the number of threads on which the simulation will run.
+    ///
+    /// This is the number of threads requested at construction, after it was
+    /// constrained to the supported range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nexosim::simulation::SimInit;
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// let bench = SimInit::with_num_threads(0);
+    /// assert_eq!(bench.num_threads(), 1);
+    ///
+    /// let (simu, _scheduler) = bench.init(MonotonicTime::EPOCH).unwrap();
+    /// assert_eq!(simu.num_threads(), 1);
+    /// ```
+    pub fn num_threads(&self) -> usize {
+        self.executor.num_threads()
+    }
+
     /// Adds a model and its mailbox to the simulation bench.
     ///
     /// The `name` argument needs not be unique. The use of the dot character in
@@ -93,22 +171,254 @@ impl SimInit {
         };
         self.observers
             .push((name.clone(), Box::new(mailbox.0.observer())));
-        let scheduler = GlobalScheduler::new(
-            self.scheduler_queue.clone(),
-            self.time.reader(),
-            self.is_halted.clone(),
-        );
+        self.addresses
+            .push((name.clone(), mailbox.address().into()));
+
+        add_model(model, mailbox, name, &mut self.pending_models);
+
+        self
+    }
+
+    /// Adds a model and its mailbox to the simulation bench and returns the
+    /// address of the model.
+    ///
+    /// This is equivalent to [`SimInit::add_model`], but it saves the caller
+    /// from retrieving the address from the mailbox before the mailbox is
+    /// moved into the bench.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// struct MyModel {}
+    /// impl MyModel {
+    ///     fn input(&mut self, _value: u32) {}
+    /// }
+    /// impl Model for MyModel {}
+    ///
+    /// let (bench, address) =
+    ///     SimInit::new().add_model_with_address(MyModel {}, Mailbox::new(), "model");
+    /// let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+    ///
+    /// simu.process_event(MyModel::input, 42, &address).unwrap();
+    /// ```
+    pub fn add_model_with_address<P: ProtoModel>(
+        self,
+        model: P,
+        mailbox: Mailbox<P::Model>,
+        name: impl Into<String>,
+    ) -> (Self, Address<P::Model>) {
+        let address = mailbox.address();
 
-        add_model(
-            model,
-            mailbox,
-            name,
-            scheduler,
-            &self.executor,
-            &self.abort_signal,
-            &mut self.model_names,
+        (self.add_model(model, mailbox, name), address)
+    }
+
+    /// Adds a collection of models and their mailboxes to the simulation
+    /// bench.
+    ///
+    /// This is equivalent to calling [`SimInit::add_model`] for each model in
+    /// iteration order, but storage for the models is reserved upfront based
+    /// on the size hint of the iterator, which makes it cheaper to assemble
+    /// benches with a large number of models.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// struct Sensor {}
+    /// impl Model for Sensor {}
+    ///
+    /// let sensors = (0..100).map(|i| (Sensor {}, Mailbox::new(), format!("sensor_{}", i)));
+    /// let bench = SimInit::new().add_models(sensors);
+    /// let simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+    /// assert_eq!(simu.mailbox_occupancy().len(), 100);
+    /// ```
+    pub fn add_models<P, I>(mut self, models: I) -> Self
+    where
+        P: ProtoModel,
+        I: IntoIterator<Item = (P, Mailbox<P::Model>, String)>,
+    {
+        let models = models.into_iter();
+        let (additional, _) = models.size_hint();
+        self.observers.reserve(additional);
+        self.addresses.reserve(additional);
+        self.pending_models.reserve(additional);
+
+        for (model, mailbox, name) in models {
+            self = self.add_model(model, mailbox, name);
+        }
+
+        self
+    }
+
+    /// Adds a model and its mailbox to the simulation bench, capping the
+    /// number of messages that the model and its submodels may process
+    /// concurrently.
+    ///
+    /// Each model processes its own messages sequentially, so the cap is only
+    /// meaningful for hierarchical models that fan work out to several
+    /// submodels: the model and all submodels added from its
+    /// [`ProtoModel::build`] method share `max_concurrent` permits, and a
+    /// message received by any of them is processed only once a permit is
+    /// available, even if idle executor threads could process it right away.
+    /// A permit is held for the whole processing of the message, including
+    /// while awaiting sends to other models and replies to queries. Models
+    /// outside the group are not affected.
+    ///
+    /// Since a model that waits for a reply from another model of the same
+    /// group holds a permit in the meantime, such queries must not be
+    /// performed with a cap lower than the length of the resulting chain of
+    /// requests, or a deadlock will result.
+    ///
+    /// See [`SimInit::add_model`] for the requirements on `name`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `max_concurrent` is 0.
+    pub fn add_model_with_concurrency<P: ProtoModel>(
+        mut self,
+        model: P,
+        mailbox: Mailbox<P::Model>,
+        name: impl Into<String>,
+        max_concurrent: usize,
+    ) -> Self {
+        assert!(
+            max_concurrent > 0,
+            "the maximum number of concurrent messages cannot be 0"
         );
 
+        let first_model = self.pending_models.len();
+        self = self.add_model(model, mailbox, name);
+
+        let limiter = Arc::new(Semaphore::new(max_concurrent));
+        for pending_model in &mut self.pending_models[first_model..] {
+            pending_model.set_limiter(limiter.clone());
+        }
+
+        self
+    }
+
+    /// Returns the address of the model with the specified name and type.
+    ///
+    /// The name is the one provided when the model was added to the bench;
+    /// submodels added from a [`ProtoModel::build`] method cannot be looked up.
+    /// Since names need not be unique, the address of the first model added
+    /// with this name and type is returned. `None` is returned if no such
+    /// model exists.
+    ///
+    /// This makes it possible to connect event and query sources to models
+    /// by name once all models have been added, without keeping track of
+    /// their mailboxes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::ports::EventSource;
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// struct Heater {}
+    /// impl Heater {
+    ///     async fn power(&mut self, _on: bool) {}
+    /// }
+    /// impl Model for Heater {}
+    ///
+    /// let bench = SimInit::new()
+    ///     .add_model(Heater {}, Mailbox::new(), "heater_1")
+    ///     .add_model(Heater {}, Mailbox::new(), "heater_2");
+    ///
+    /// let mut power = EventSource::new();
+    /// power.connect(Heater::power, bench.address_of::<Heater>("heater_2").unwrap());
+    /// assert!(bench.address_of::<Heater>("heater_3").is_none());
+    ///
+    /// let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+    /// simu.process(power.event(true)).unwrap();
+    /// ```
+    pub fn address_of<M: Model>(&self, name: &str) -> Option<Address<M>> {
+        self.addresses
+            .iter()
+            .filter(|(model_name, _)| model_name == name)
+            .find_map(|(_, address)| address.downcast())
+    }
+
+    /// Returns an iterator over the names and addresses of all models of type
+    /// `M` in the order in which they were added to the bench.
+    ///
+    /// This is typically used to fan out an event or query source to all
+    /// models of a given type. As with [`SimInit::address_of`], submodels
+    /// added from a [`ProtoModel::build`] method are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nexosim::model::Model;
+    /// use nexosim::ports::EventSource;
+    /// use nexosim::simulation::{Mailbox, SimInit};
+    /// use nexosim::time::MonotonicTime;
+    ///
+    /// struct Heater {}
+    /// impl Heater {
+    ///     async fn power(&mut self, _on: bool) {}
+    /// }
+    /// impl Model for Heater {}
+    ///
+    /// let bench = (0..4).fold(SimInit::new(), |bench, i| {
+    ///     bench.add_model(Heater {}, Mailbox::new(), format!("heater_{}", i))
+    /// });
+    ///
+    /// let mut power = EventSource::new();
+    /// for (_name, address) in bench.addresses::<Heater>() {
+    ///     power.connect(Heater::power, address);
+    /// }
+    ///
+    /// let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+    /// simu.process(power.event(true)).unwrap();
+    /// ```
+    pub fn addresses<M: Model>(&self) -> impl Iterator<Item = (&str, Address<M>)> + '_ {
+        self.addresses
+            .iter()
+            .filter_map(|(name, address)| Some((name.as_str(), address.downcast()?)))
+    }
+
+    /// Merges the models of another simulation bench into this one.
+    ///
+    /// This makes it possible to assemble independently-built sub-benches into
+    /// a single simulation running on the executor and scheduler of `self`.
+    /// Connections between models of both benches should be established
+    /// beforehand using the [`Address`](crate::simulation::Address)es of the
+    /// target models.
+    ///
+    /// The clock, clock tolerance, timeout and number of threads of `other`
+    /// are discarded in favor of those of `self`. Instrumentation settings
+    /// are combined: a check or recorder enabled on either bench is enabled on
+    /// the merged bench, and the largest of the queue length history and event
+    /// log capacities is retained.
+    ///
+    /// As with [`add_model`](SimInit::add_model), model names need not be
+    /// unique: if both benches contain models with the same name,
+    /// [`address_of`](SimInit::address_of) returns the address of the model
+    /// of `self`.
+    pub fn merge(mut self, other: SimInit) -> Self {
+        self.observers.extend(other.observers);
+        self.addresses.extend(other.addresses);
+        self.pending_models.extend(other.pending_models);
+        self.scheduling_ranks.extend(other.scheduling_ranks);
+
+        self.queue_length_history_capacity = self
+            .queue_length_history_capacity
+            .max(other.queue_length_history_capacity);
+        self.init_monitor = self.init_monitor.or(other.init_monitor);
+        self.event_type_counting |= other.event_type_counting;
+        self.event_journal |= other.event_journal;
+        self.event_log_capacity = self.event_log_capacity.max(other.event_log_capacity);
+
         self
     }
 
@@ -150,6 +460,97 @@ impl SimInit {
         self
     }
 
+    /// Enables the recording of the scheduler queue length at each simulation
+    /// step, keeping at most the specified number of most recent records.
+    ///
+    /// A null capacity disables the recording, which is the default behavior.
+    ///
+    /// See also [`Simulation::queue_length_history`].
+    pub fn set_queue_length_history(mut self, capacity: usize) -> Self {
+        self.queue_length_history_capacity = capacity;
+
+        self
+    }
+
+    /// Makes [`SimInit::init`] fail if a model attempts to schedule an action
+    /// at or before the simulation start time during its initialization.
+    ///
+    /// When enabled, such attempts are reported as an
+    /// [`ExecutionError::InitScheduledInPast`] error, which takes precedence
+    /// over any other error returned by the initialization. Otherwise, the
+    /// model only receives a
+    /// [`SchedulingError::InvalidScheduledTime`](crate::simulation::SchedulingError::InvalidScheduledTime)
+    /// error, which is the default behavior.
+    pub fn set_init_scheduling_check(mut self, enabled: bool) -> Self {
+        self.init_monitor = enabled.then(Default::default);
+
+        self
+    }
+
+    /// Enables the counting of the events and queries processed by models,
+    /// broken down by event type.
+    ///
+    /// Counting is disabled by default, in which case it has no overhead.
+    ///
+    /// See also [`Simulation::event_type_counts`].
+    pub fn set_event_type_counting(mut self, enabled: bool) -> Self {
+        self.event_type_counting = enabled;
+
+        self
+    }
+
+    /// Enables the journaling of the events and queries processed by models.
+    ///
+    /// The journal records the simulation time, the model name and the event
+    /// type of each processed event or query. Journaling is disabled by
+    /// default; since it stores an entry for every processed message, it is
+    /// only meant for testing purposes.
+    ///
+    /// See also [`DeterminismChecker`](crate::simulation::DeterminismChecker).
+    pub fn set_event_journal(mut self, enabled: bool) -> Self {
+        self.event_journal = enabled;
+
+        self
+    }
+
+    /// Enables the logging of the events and queries processed by models,
+    /// keeping at most the specified number of most recent entries.
+    ///
+    /// The log is a view of the most recent entries of the event journal.
+    /// Unless journaling is enabled with [`SimInit::set_event_journal`], the
+    /// journal only retains these entries, so the log can be kept enabled
+    /// during long runs. A null capacity disables the log, which is the default
+    /// behavior; a disabled log has no overhead.
+    ///
+    /// See also [`Simulation::event_log`].
+    pub fn set_event_log(mut self, capacity: usize) -> Self {
+        self.event_log_capacity = capacity;
+
+        self
+    }
+
+    /// Sets the ordering rank of all actions scheduled by the model with the
+    /// specified address.
+    ///
+    /// Within a given simulation time, all actions with a lower rank are
+    /// guaranteed to have been processed to completion, including all events
+    /// and queries they triggered, before any action with a higher rank is
+    /// processed. Ranks can thus be used to make sure that, when several models
+    /// send events to the same input as a result of self-scheduled actions,
+    /// the events of one model are always processed before those of another.
+    ///
+    /// Models have a rank of 0 by default. See also [`Scheduler::with_rank`].
+    pub fn set_scheduling_rank<M: Model>(
+        mut self,
+        address: impl Into<Address<M>>,
+        rank: i32,
+    ) -> Self {
+        self.scheduling_ranks
+            .insert(address.into().0.channel_id(), rank);
+
+        self
+    }
+
     /// Builds a simulation initialized at the specified simulation time,
     /// executing the [`Model::init`](crate::model::Model::init) method on all
     /// model initializers.
@@ -160,6 +561,8 @@ impl SimInit {
         mut self,
         start_time: MonotonicTime,
     ) -> Result<(Simulation, Scheduler), ExecutionError> {
+        check_connections(&self.pending_models)?;
+
         self.time.write(start_time);
         if let SyncStatus::OutOfSync(lag) = self.clock.synchronize(start_time) {
             if let Some(tolerance) = &self.clock_tolerance {
@@ -169,6 +572,35 @@ impl SimInit {
             }
         }
 
+        let instrumentation = Instrumentation::new(
+            self.event_type_counting,
+            self.event_journal,
+            self.event_log_capacity,
+        );
+        let mut models = Vec::with_capacity(self.pending_models.len());
+        for model in self.pending_models {
+            let mut model_scheduler = GlobalScheduler::new(
+                self.scheduler_queue.clone(),
+                self.time.reader(),
+                self.is_halted.clone(),
+            );
+            if let Some(init_monitor) = &self.init_monitor {
+                model_scheduler = model_scheduler.with_init_monitor(init_monitor.clone());
+            }
+            if let Some(&rank) = self.scheduling_ranks.get(&model.channel_id()) {
+                model_scheduler = model_scheduler.with_rank(rank);
+            }
+            let model_id = ModelId::new(models.len());
+            let model = model.spawn(
+                model_id,
+                model_scheduler,
+                instrumentation.clone(),
+                &self.executor,
+                &self.abort_signal,
+            );
+            models.push(model);
+        }
+
         let scheduler = Scheduler::new(
             self.scheduler_queue.clone(),
             self.time.reader(),
@@ -182,10 +614,27 @@ impl SimInit {
             self.clock_tolerance,
             self.timeout,
             self.observers,
-            self.model_names,
+            models,
             self.is_halted,
+            self.queue_length_history_capacity,
+            instrumentation,
+            self.abort_signal,
+            #[cfg(feature = "tracing")]
+            self.time_slice_span,
         );
-        simulation.run()?;
+        if let Some(init_monitor) = &self.init_monitor {
+            init_monitor.set_active(true);
+        }
+        let result = simulation.run();
+        if let Some(init_monitor) = self.init_monitor {
+            init_monitor.set_active(false);
+            if let Some(model_id) = init_monitor.offending_model() {
+                return Err(ExecutionError::InitScheduledInPast {
+                    model: simulation.models[&model_id].name.clone(),
+                });
+            }
+        }
+        result?;
 
         Ok((simulation, scheduler))
     }
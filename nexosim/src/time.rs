@@ -4,7 +4,8 @@
 //!
 //! * [`MonotonicTime`]: a monotonic timestamp based on the [TAI] time standard,
 //! * [`Clock`]: a trait for types that can synchronize a simulation,
-//!   implemented for instance by [`SystemClock`] and [`AutoSystemClock`].
+//!   implemented for instance by [`SystemClock`], [`AutoSystemClock`],
+//!   [`ScaledSystemClock`], [`TraceClock`] and [`JitterClock`].
 //!
 //! [TAI]: https://en.wikipedia.org/wiki/International_Atomic_Time
 //!
@@ -50,7 +51,10 @@ mod monotonic_time;
 
 pub use tai_time::MonotonicTime;
 
-pub use clock::{AutoSystemClock, Clock, NoClock, SyncStatus, SystemClock};
+pub use clock::{
+    AutoSystemClock, Clock, JitterClock, NoClock, ScaledSystemClock, SyncStatus, SystemClock,
+    TraceClock,
+};
 pub(crate) use monotonic_time::TearableAtomicTime;
 
 pub(crate) type AtomicTime = crate::util::sync_cell::SyncCell<TearableAtomicTime>;
@@ -64,6 +68,16 @@ pub trait Deadline {
     /// Make this deadline into an absolute timestamp, using the provided
     /// current time as a reference.
     fn into_time(self, now: MonotonicTime) -> MonotonicTime;
+
+    /// Make this deadline into an absolute timestamp, using the provided
+    /// current time as a reference, or return `None` if the timestamp would
+    /// lie beyond [`MonotonicTime::MAX`].
+    fn checked_into_time(self, now: MonotonicTime) -> Option<MonotonicTime>
+    where
+        Self: Sized,
+    {
+        Some(self.into_time(now))
+    }
 }
 
 impl Deadline for std::time::Duration {
@@ -71,6 +85,11 @@ impl Deadline for std::time::Duration {
     fn into_time(self, now: MonotonicTime) -> MonotonicTime {
         now + self
     }
+
+    #[inline(always)]
+    fn checked_into_time(self, now: MonotonicTime) -> Option<MonotonicTime> {
+        now.checked_add(self)
+    }
 }
 
 impl Deadline for MonotonicTime {
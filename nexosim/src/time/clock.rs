@@ -3,6 +3,7 @@ use std::time::{Duration, Instant, SystemTime};
 use tai_time::MonotonicClock;
 
 use crate::time::MonotonicTime;
+use crate::util::rng::Rng;
 
 /// A type that can be used to synchronize a simulation.
 ///
@@ -14,18 +15,90 @@ use crate::time::MonotonicTime;
 pub trait Clock: Send {
     /// Blocks until the deadline.
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus;
+
+    /// Returns the ratio between the rates of simulation time and wall clock
+    /// time.
+    ///
+    /// The default implementation returns 1.0.
+    fn time_scale(&self) -> f64 {
+        1.0
+    }
+
+    /// Sets the ratio between the rates of simulation time and wall clock
+    /// time.
+    ///
+    /// Clocks that support this method should apply the new scale from the
+    /// current instant onwards, without any discontinuity in the mapping
+    /// between simulation time and wall clock time. Clocks that do not support
+    /// time scaling, or that are not paced by the wall clock, should ignore
+    /// this call.
+    ///
+    /// The default implementation does nothing.
+    fn set_time_scale(&mut self, _scale: f64) {}
+
+    /// Pauses the clock.
+    ///
+    /// Clocks that support this method should exclude the wall clock time
+    /// elapsed until the next call to [`resume`](Clock::resume) from the
+    /// mapping between simulation time and wall clock time, so that the
+    /// simulation neither catches up with a burst of events nor processes any
+    /// event ahead of time once resumed. A call to
+    /// [`synchronize`](Clock::synchronize) while the clock is paused should
+    /// implicitly resume it.
+    ///
+    /// The default implementation does nothing.
+    fn pause(&mut self) {}
+
+    /// Resumes a paused clock.
+    ///
+    /// This call should be ignored if the clock is not paused.
+    ///
+    /// The default implementation does nothing.
+    fn resume(&mut self) {}
 }
 
 impl<C: Clock + ?Sized> Clock for &mut C {
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
         (**self).synchronize(deadline)
     }
+
+    fn time_scale(&self) -> f64 {
+        (**self).time_scale()
+    }
+
+    fn set_time_scale(&mut self, scale: f64) {
+        (**self).set_time_scale(scale)
+    }
+
+    fn pause(&mut self) {
+        (**self).pause()
+    }
+
+    fn resume(&mut self) {
+        (**self).resume()
+    }
 }
 
 impl<C: Clock + ?Sized> Clock for Box<C> {
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
         (**self).synchronize(deadline)
     }
+
+    fn time_scale(&self) -> f64 {
+        (**self).time_scale()
+    }
+
+    fn set_time_scale(&mut self, scale: f64) {
+        (**self).set_time_scale(scale)
+    }
+
+    fn pause(&mut self) {
+        (**self).pause()
+    }
+
+    fn resume(&mut self) {
+        (**self).resume()
+    }
 }
 
 /// The current synchronization status of a clock.
@@ -65,7 +138,11 @@ impl Clock for NoClock {
 /// This clock accepts an arbitrary reference time and remains synchronized with
 /// the system's monotonic clock.
 #[derive(Copy, Clone, Debug)]
-pub struct SystemClock(MonotonicClock);
+pub struct SystemClock {
+    clock: MonotonicClock,
+    /// Simulation time at which the clock was paused, if paused.
+    paused_at: Option<MonotonicTime>,
+}
 
 impl SystemClock {
     /// Constructs a `SystemClock` with an offset between simulation clock and
@@ -94,10 +171,10 @@ impl SystemClock {
     ///     .init(t0);
     /// ```
     pub fn from_instant(simulation_ref: MonotonicTime, wall_clock_ref: Instant) -> Self {
-        Self(MonotonicClock::init_from_instant(
-            simulation_ref,
-            wall_clock_ref,
-        ))
+        Self {
+            clock: MonotonicClock::init_from_instant(simulation_ref, wall_clock_ref),
+            paused_at: None,
+        }
     }
 
     /// Constructs a `SystemClock` with an offset between simulation clock and
@@ -138,10 +215,10 @@ impl SystemClock {
     ///     .init(t0);
     /// ```
     pub fn from_system_time(simulation_ref: MonotonicTime, wall_clock_ref: SystemTime) -> Self {
-        Self(MonotonicClock::init_from_system_time(
-            simulation_ref,
-            wall_clock_ref,
-        ))
+        Self {
+            clock: MonotonicClock::init_from_system_time(simulation_ref, wall_clock_ref),
+            paused_at: None,
+        }
     }
 }
 
@@ -149,7 +226,9 @@ impl Clock for SystemClock {
     /// Blocks until the system time corresponds to the specified simulation
     /// time.
     fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
-        let now = self.0.now();
+        self.resume();
+
+        let now = self.clock.now();
         if now <= deadline {
             spin_sleep::sleep(deadline.duration_since(now));
 
@@ -158,6 +237,20 @@ impl Clock for SystemClock {
 
         SyncStatus::OutOfSync(now.duration_since(deadline))
     }
+
+    /// Freezes the simulation time of the clock.
+    fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(self.clock.now());
+        }
+    }
+
+    /// Restarts the clock from the simulation time at which it was paused.
+    fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.clock = MonotonicClock::init_from_instant(paused_at, Instant::now());
+        }
+    }
 }
 
 /// An automatically initialized real-time [`Clock`] based on the system's
@@ -194,6 +287,369 @@ impl Clock for AutoSystemClock {
             Some(clock) => clock.synchronize(deadline),
         }
     }
+
+    /// Pauses the clock if it is already running.
+    fn pause(&mut self) {
+        if let Some(clock) = &mut self.inner {
+            clock.pause();
+        }
+    }
+
+    fn resume(&mut self) {
+        if let Some(clock) = &mut self.inner {
+            clock.resume();
+        }
+    }
+}
+
+/// A real-time [`Clock`] based on the system's monotonic clock and running at
+/// an adjustable multiple of the wall clock rate.
+///
+/// The time scale is the ratio between the rates of simulation time and wall
+/// clock time: a scale of 2.0 makes the simulation run twice as fast as the
+/// wall clock while a scale of 0.25 makes it run in slow motion. It can be
+/// changed while the simulation is running with
+/// [`Simulation::set_time_scale`](crate::simulation::Simulation::set_time_scale).
+///
+/// Unless the reference time is specified with
+/// [`ScaledSystemClock::from_instant`], the first call to
+/// [`synchronize`](Clock::synchronize) never blocks and implicitly defines the
+/// reference time, as with [`AutoSystemClock`].
+///
+/// Synchronization lags reported by this clock are expressed in simulation
+/// time.
+#[derive(Copy, Clone, Debug)]
+pub struct ScaledSystemClock {
+    reference: Option<(MonotonicTime, Instant)>,
+    scale: f64,
+    /// Simulation time at which the clock was paused, if paused.
+    paused_at: Option<MonotonicTime>,
+}
+
+impl ScaledSystemClock {
+    /// Constructs a `ScaledSystemClock` with the specified time scale that
+    /// starts running on its first invocation.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the time scale is not strictly positive and finite.
+    pub fn new(scale: f64) -> Self {
+        assert_valid_scale(scale);
+
+        Self {
+            reference: None,
+            scale,
+            paused_at: None,
+        }
+    }
+
+    /// Constructs a `ScaledSystemClock` with the specified time scale and an
+    /// offset between simulation clock and wall clock specified by a
+    /// simulation time matched to an [`Instant`] timestamp.
+    ///
+    /// The provided reference time may lie in the past or in the future.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the time scale is not strictly positive and finite.
+    pub fn from_instant(
+        simulation_ref: MonotonicTime,
+        wall_clock_ref: Instant,
+        scale: f64,
+    ) -> Self {
+        assert_valid_scale(scale);
+
+        Self {
+            reference: Some((simulation_ref, wall_clock_ref)),
+            scale,
+            paused_at: None,
+        }
+    }
+
+    /// Returns the simulation time corresponding to a wall clock instant.
+    fn simulation_time(
+        &self,
+        simulation_ref: MonotonicTime,
+        wall_clock_ref: Instant,
+        now: Instant,
+    ) -> MonotonicTime {
+        if now >= wall_clock_ref {
+            simulation_ref + now.duration_since(wall_clock_ref).mul_f64(self.scale)
+        } else {
+            simulation_ref - wall_clock_ref.duration_since(now).mul_f64(self.scale)
+        }
+    }
+}
+
+impl Clock for ScaledSystemClock {
+    /// Initializes the time reference and returns immediately on the first
+    /// call if no reference was specified, otherwise blocks until the scaled
+    /// system time corresponds to the specified simulation time.
+    fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
+        self.resume();
+
+        let (simulation_ref, wall_clock_ref) = match self.reference {
+            Some(reference) => reference,
+            None => {
+                self.reference = Some((deadline, Instant::now()));
+
+                return SyncStatus::Synchronized;
+            }
+        };
+
+        let now = self.simulation_time(simulation_ref, wall_clock_ref, Instant::now());
+        if now <= deadline {
+            spin_sleep::sleep(deadline.duration_since(now).div_f64(self.scale));
+
+            return SyncStatus::Synchronized;
+        }
+
+        SyncStatus::OutOfSync(now.duration_since(deadline))
+    }
+
+    fn time_scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Sets the time scale, rebasing the time reference on the current instant
+    /// so that the simulation time elapsed so far is not affected.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the time scale is not strictly positive and finite.
+    fn set_time_scale(&mut self, scale: f64) {
+        assert_valid_scale(scale);
+
+        if self.paused_at.is_none() {
+            if let Some((simulation_ref, wall_clock_ref)) = self.reference {
+                let now = Instant::now();
+                let simulation_now = self.simulation_time(simulation_ref, wall_clock_ref, now);
+                self.reference = Some((simulation_now, now));
+            }
+        }
+        self.scale = scale;
+    }
+
+    /// Freezes the simulation time of the clock if it is already running.
+    fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            if let Some((simulation_ref, wall_clock_ref)) = self.reference {
+                self.paused_at =
+                    Some(self.simulation_time(simulation_ref, wall_clock_ref, Instant::now()));
+            }
+        }
+    }
+
+    /// Restarts the clock from the simulation time at which it was paused.
+    fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.reference = Some((paused_at, Instant::now()));
+        }
+    }
+}
+
+/// A [`Clock`] that replays a recorded trace of timestamps.
+///
+/// Each call to [`synchronize`](Clock::synchronize) consumes the next
+/// timestamp of the trace, irrespective of the simulation deadline. The first
+/// call never blocks and matches the first timestamp to the current wall
+/// clock time. Each subsequent call blocks until the wall clock time elapsed
+/// since the first call equals the offset of its timestamp from the first
+/// timestamp. The simulation is thus paced by the same intervals as the
+/// recorded trace, which makes it possible, for instance, to reproduce the
+/// timing of events observed on a production system.
+///
+/// Once the trace is exhausted, synchronization is ignored as with
+/// [`NoClock`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use nexosim::simulation::SimInit;
+/// use nexosim::time::{MonotonicTime, TraceClock};
+///
+/// let t0 = MonotonicTime::EPOCH;
+///
+/// // The first simulation step takes 10ms and the second 30ms.
+/// let trace = [0, 10, 40].map(|ms| t0 + Duration::from_millis(ms));
+///
+/// let simu = SimInit::new()
+/// //  .add_model(...)
+/// //  .add_model(...)
+///     .set_clock(TraceClock::new(trace))
+///     .init(t0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TraceClock {
+    trace: std::vec::IntoIter<MonotonicTime>,
+    reference: Option<(MonotonicTime, Instant)>,
+    /// Wall clock time at which the clock was paused, if paused.
+    paused_at: Option<Instant>,
+}
+
+impl TraceClock {
+    /// Constructs a `TraceClock` from a sequence of timestamps.
+    ///
+    /// Timestamps are expected to be sorted in chronological order; a
+    /// timestamp that precedes the first one is treated as if it were equal to
+    /// the first one.
+    pub fn new(trace: impl IntoIterator<Item = MonotonicTime>) -> Self {
+        Self {
+            trace: trace.into_iter().collect::<Vec<_>>().into_iter(),
+            reference: None,
+            paused_at: None,
+        }
+    }
+}
+
+impl Clock for TraceClock {
+    /// Initializes the time reference and returns immediately on the first
+    /// call, otherwise blocks until the wall clock time corresponds to the next
+    /// timestamp of the trace, if any.
+    fn synchronize(&mut self, _: MonotonicTime) -> SyncStatus {
+        self.resume();
+
+        let timestamp = match self.trace.next() {
+            Some(timestamp) => timestamp,
+            None => return SyncStatus::Synchronized,
+        };
+
+        let (trace_ref, wall_clock_ref) = match self.reference {
+            Some(reference) => reference,
+            None => {
+                self.reference = Some((timestamp, Instant::now()));
+
+                return SyncStatus::Synchronized;
+            }
+        };
+
+        let target = wall_clock_ref
+            + timestamp
+                .checked_duration_since(trace_ref)
+                .unwrap_or_default();
+        let now = Instant::now();
+        if now <= target {
+            spin_sleep::sleep(target.duration_since(now));
+
+            return SyncStatus::Synchronized;
+        }
+
+        SyncStatus::OutOfSync(now.duration_since(target))
+    }
+
+    /// Stops the replay of the trace if it has already started.
+    fn pause(&mut self) {
+        if self.reference.is_some() && self.paused_at.is_none() {
+            self.paused_at = Some(Instant::now());
+        }
+    }
+
+    /// Resumes the replay of the trace, excluding the paused duration.
+    fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            if let Some((_, wall_clock_ref)) = &mut self.reference {
+                *wall_clock_ref += paused_at.elapsed();
+            }
+        }
+    }
+}
+
+/// A [`Clock`] adaptor that delays each synchronization of another clock by a
+/// pseudo-random amount of wall clock time.
+///
+/// Each call to [`synchronize`](Clock::synchronize) first synchronizes the
+/// wrapped clock and then blocks for an additional duration drawn uniformly
+/// in `[0, max_jitter]`. The sequence of delays is fully determined by the
+/// seed, so that a run exhibiting a timing-related issue can be reproduced.
+///
+/// The jitter only affects the wall clock pacing of the simulation: events
+/// are still processed in the order of their simulation time, and the
+/// synchronization status reported is that of the wrapped clock. Note that a
+/// delay exceeding the wall clock time until the next deadline may cause the
+/// wrapped clock to report a loss of synchronization on the next call.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use nexosim::simulation::SimInit;
+/// use nexosim::time::{AutoSystemClock, JitterClock, MonotonicTime};
+///
+/// let clock = JitterClock::new(AutoSystemClock::new(), 42, Duration::from_millis(5));
+///
+/// let simu = SimInit::new()
+/// //  .add_model(...)
+/// //  .add_model(...)
+///     .set_clock(clock)
+///     .init(MonotonicTime::EPOCH);
+/// ```
+#[derive(Clone, Debug)]
+pub struct JitterClock<C: Clock> {
+    clock: C,
+    rng: Rng,
+    max_jitter_nanos: u64,
+}
+
+impl<C: Clock> JitterClock<C> {
+    /// Constructs a `JitterClock` wrapping the specified clock, with delays
+    /// generated from the specified seed and bounded by `max_jitter`.
+    pub fn new(clock: C, seed: u64, max_jitter: Duration) -> Self {
+        Self {
+            clock,
+            rng: Rng::new(seed),
+            max_jitter_nanos: max_jitter.as_nanos().try_into().unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Draws the next delay.
+    fn next_jitter(&mut self) -> Duration {
+        Duration::from_nanos(
+            self.rng
+                .gen_bounded(self.max_jitter_nanos.saturating_add(1)),
+        )
+    }
+}
+
+impl<C: Clock> Clock for JitterClock<C> {
+    /// Synchronizes the wrapped clock and then blocks for a pseudo-random
+    /// duration.
+    fn synchronize(&mut self, deadline: MonotonicTime) -> SyncStatus {
+        let status = self.clock.synchronize(deadline);
+
+        let jitter = self.next_jitter();
+        if !jitter.is_zero() {
+            spin_sleep::sleep(jitter);
+        }
+
+        status
+    }
+
+    fn time_scale(&self) -> f64 {
+        self.clock.time_scale()
+    }
+
+    fn set_time_scale(&mut self, scale: f64) {
+        self.clock.set_time_scale(scale);
+    }
+
+    fn pause(&mut self) {
+        self.clock.pause();
+    }
+
+    fn resume(&mut self) {
+        self.clock.resume();
+    }
+}
+
+/// Panics if the time scale is not strictly positive and finite.
+fn assert_valid_scale(scale: f64) {
+    assert!(
+        scale.is_finite() && scale > 0.0,
+        "the time scale must be strictly positive and finite"
+    );
 }
 
 #[cfg(test)]
@@ -220,4 +676,142 @@ mod test {
             elapsed,
         );
     }
+
+    #[test]
+    fn smoke_scaled_system_clock() {
+        let t0 = MonotonicTime::EPOCH;
+        const TOLERANCE: f64 = 0.0005; // [s]
+
+        let now = Instant::now();
+        let mut clock = ScaledSystemClock::from_instant(t0, now, 2.0);
+        let t1 = t0 + Duration::from_millis(400);
+        assert_eq!(clock.synchronize(t1), SyncStatus::Synchronized);
+        let elapsed = now.elapsed().as_secs_f64();
+        let dt = 0.2;
+
+        assert!(
+            (dt - elapsed) <= TOLERANCE,
+            "Expected t = {:.6}s +/- {:.6}s, measured t = {:.6}s",
+            dt,
+            TOLERANCE,
+            elapsed,
+        );
+
+        // Halve the rate: 100ms of simulation time now last 200ms.
+        clock.set_time_scale(0.5);
+        let now = Instant::now();
+        let t2 = t1 + Duration::from_millis(100);
+        assert_eq!(clock.synchronize(t2), SyncStatus::Synchronized);
+        let elapsed = now.elapsed().as_secs_f64();
+        let dt = 0.2;
+
+        assert!(
+            (dt - elapsed) <= TOLERANCE,
+            "Expected t = {:.6}s +/- {:.6}s, measured t = {:.6}s",
+            dt,
+            TOLERANCE,
+            elapsed,
+        );
+    }
+
+    #[test]
+    fn pause_system_clock() {
+        let t0 = MonotonicTime::EPOCH;
+        const TOLERANCE: f64 = 0.0005; // [s]
+
+        let mut clock = SystemClock::from_instant(t0, Instant::now());
+        clock.pause();
+        std::thread::sleep(Duration::from_millis(100));
+        clock.resume();
+
+        // The paused duration must not count as elapsed simulation time.
+        let now = Instant::now();
+        let t1 = t0 + Duration::from_millis(100);
+        assert_eq!(clock.synchronize(t1), SyncStatus::Synchronized);
+        let elapsed = now.elapsed().as_secs_f64();
+        let dt = 0.1;
+
+        assert!(
+            (dt - elapsed) <= TOLERANCE,
+            "Expected t = {:.6}s +/- {:.6}s, measured t = {:.6}s",
+            dt,
+            TOLERANCE,
+            elapsed,
+        );
+    }
+
+    #[test]
+    fn smoke_trace_clock() {
+        let t0 = MonotonicTime::EPOCH;
+        const TOLERANCE: f64 = 0.0005; // [s]
+
+        let trace = [0, 100, 300].map(|ms| t0 + Duration::from_millis(ms));
+        let mut clock = TraceClock::new(trace);
+
+        // The simulation deadlines are irrelevant.
+        let now = Instant::now();
+        assert_eq!(clock.synchronize(t0), SyncStatus::Synchronized);
+        assert_eq!(clock.synchronize(t0), SyncStatus::Synchronized);
+        assert_eq!(clock.synchronize(t0), SyncStatus::Synchronized);
+        let elapsed = now.elapsed().as_secs_f64();
+        let dt = 0.3;
+
+        assert!(
+            (dt - elapsed) <= TOLERANCE,
+            "Expected t = {:.6}s +/- {:.6}s, measured t = {:.6}s",
+            dt,
+            TOLERANCE,
+            elapsed,
+        );
+
+        // The trace is exhausted.
+        let now = Instant::now();
+        assert_eq!(clock.synchronize(t0), SyncStatus::Synchronized);
+        assert!(now.elapsed().as_secs_f64() <= TOLERANCE);
+    }
+
+    #[test]
+    fn jitter_clock_reproducible() {
+        const MAX_JITTER: Duration = Duration::from_millis(10);
+
+        let mut clock1 = JitterClock::new(NoClock::new(), 1234, MAX_JITTER);
+        let mut clock2 = JitterClock::new(NoClock::new(), 1234, MAX_JITTER);
+        let mut clock3 = JitterClock::new(NoClock::new(), 5678, MAX_JITTER);
+
+        let jitters1: Vec<_> = (0..100).map(|_| clock1.next_jitter()).collect();
+        let jitters2: Vec<_> = (0..100).map(|_| clock2.next_jitter()).collect();
+        let jitters3: Vec<_> = (0..100).map(|_| clock3.next_jitter()).collect();
+
+        assert!(jitters1.iter().all(|jitter| *jitter <= MAX_JITTER));
+        assert_eq!(jitters1, jitters2);
+        assert_ne!(jitters1, jitters3);
+
+        // A null jitter bound disables the delays.
+        let mut clock = JitterClock::new(NoClock::new(), 1234, Duration::ZERO);
+        assert!((0..100).all(|_| clock.next_jitter().is_zero()));
+    }
+
+    #[test]
+    fn jitter_clock_delays() {
+        const MAX_JITTER: Duration = Duration::from_millis(5);
+        const TOLERANCE: f64 = 0.0005; // [s]
+
+        let t0 = MonotonicTime::EPOCH;
+
+        // A twin clock with the same seed predicts the delays.
+        let mut clock = JitterClock::new(NoClock::new(), 42, MAX_JITTER);
+        let mut twin = JitterClock::new(NoClock::new(), 42, MAX_JITTER);
+
+        for _ in 0..20 {
+            let jitter = twin.next_jitter();
+            assert!(jitter <= MAX_JITTER);
+
+            let now = Instant::now();
+            assert_eq!(clock.synchronize(t0), SyncStatus::Synchronized);
+            let elapsed = now.elapsed();
+
+            assert!(elapsed >= jitter);
+            assert!(elapsed.as_secs_f64() - jitter.as_secs_f64() <= TOLERANCE);
+        }
+    }
 }
@@ -20,6 +20,15 @@
 //! [`SimulationTime`] timer compatible with
 //! [`tracing_subscriber::fmt`][mod@tracing_subscriber::fmt].
 //!
+//! Additionally, the processing of each time slice by
+//! [`Simulation::step`](crate::simulation::Simulation::step) and related
+//! methods is wrapped in a `time_slice` span with verbosity level
+//! [`Level::DEBUG`](tracing::Level::DEBUG), which records the simulation time
+//! of the slice in a `time` field and the number of actions processed within
+//! the slice in an `actions` field. Whenever this span is enabled, the `model`
+//! spans of the models that process messages within the time slice are nested
+//! within it, which makes it possible to break down the wall clock duration of
+//! each simulated instant by model using for instance flame graphs.
 //!
 //! # Configuration
 //!
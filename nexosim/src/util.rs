@@ -3,6 +3,7 @@ pub(crate) mod cached_rw_lock;
 pub(crate) mod indexed_priority_queue;
 pub(crate) mod priority_queue;
 pub(crate) mod rng;
+pub(crate) mod semaphore;
 pub(crate) mod seq_futures;
 pub(crate) mod slot;
 pub(crate) mod sync_cell;
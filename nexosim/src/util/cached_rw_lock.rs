@@ -40,6 +40,17 @@ impl<T: Clone> CachedRwLock<T> {
         &self.value
     }
 
+    /// Acquires a read lock on the shared data without synchronizing the local
+    /// cache.
+    pub(crate) fn read_shared(&self) -> LockResult<CachedRwLockReadGuard<'_, T>> {
+        match self.shared.value.lock() {
+            LockResult::Ok(shared) => LockResult::Ok(CachedRwLockReadGuard { guard: shared }),
+            LockResult::Err(poison) => LockResult::Err(PoisonError::new(CachedRwLockReadGuard {
+                guard: poison.into_inner(),
+            })),
+        }
+    }
+
     /// Synchronizes the local cache if it is behind the shared data and gives
     /// access to it.
     #[allow(dead_code)]
@@ -98,6 +109,21 @@ struct Shared<T> {
     value: Mutex<T>,
 }
 
+/// Read guard.
+///
+/// The lock is released when the guard is dropped.
+pub(crate) struct CachedRwLockReadGuard<'a, T: Clone> {
+    guard: MutexGuard<'a, T>,
+}
+
+impl<T: Clone> Deref for CachedRwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
 /// Write guard.
 ///
 /// The lock is released when the guard is dropped.
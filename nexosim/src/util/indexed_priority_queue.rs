@@ -190,6 +190,27 @@ impl<K: Copy + Ord, V> IndexedPriorityQueue<K, V> {
         Some(&item.key.key)
     }
 
+    /// Returns a reference to the key-value pair associated to the provided
+    /// insertion key if it is still in the queue, leaving it in the queue.
+    ///
+    /// Using an insertion key returned from another `PriorityQueue` is a logic
+    /// error and could result in an arbitrary key-value pair being returned.
+    ///
+    /// This operation has *O*(1) non-amortized theoretical complexity.
+    pub(crate) fn get(&self, insert_key: InsertKey) -> Option<(&K, &V)> {
+        match self.slab.get(insert_key.slab_idx) {
+            Some(Node::HeapNode(node)) => {
+                let item = &self.heap[node.heap_idx];
+                if item.key.epoch != insert_key.epoch {
+                    return None;
+                }
+
+                Some((&item.key.key, &node.value))
+            }
+            _ => None,
+        }
+    }
+
     /// Removes the key-value pair associated to the provided insertion key if
     /// it is still in the queue and returns it.
     ///
@@ -584,6 +605,25 @@ mod tests {
         check(operations.into_iter());
     }
 
+    #[test]
+    fn indexed_priority_queue_get() {
+        let mut queue = IndexedPriorityQueue::new();
+
+        queue.insert(5, 'a');
+        let key_b = queue.insert(2, 'b');
+        let key_c = queue.insert(7, 'c');
+
+        assert_eq!(queue.get(key_b), Some((&2, &'b')));
+        assert_eq!(queue.get(key_c), Some((&7, &'c')));
+        assert_eq!(queue.pull(), Some((2, 'b')));
+        assert_eq!(queue.get(key_b), None);
+
+        // Reuse the slab slot of the pulled item.
+        queue.insert(2, 'd');
+        assert_eq!(queue.get(key_b), None);
+        assert_eq!(queue.get(key_c), Some((&7, &'c')));
+    }
+
     #[test]
     fn indexed_priority_queue_fuzz() {
         use std::cell::Cell;
@@ -107,6 +107,26 @@ impl<K: Copy + Ord, V> PriorityQueue<K, V> {
 
         Some((key, value))
     }
+
+    /// Returns the number of key-value pairs in the queue.
+    pub(crate) fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Removes all key-value pairs from the queue.
+    pub(crate) fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Retains only the key-value pairs for which the predicate returns
+    /// `true`.
+    ///
+    /// The insertion order of the retained equal keys is preserved.
+    ///
+    /// This operation has *O*(N) non-amortized theoretical complexity.
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&K, &V) -> bool) {
+        self.heap.retain(|item| f(&item.key, &item.value));
+    }
 }
 
 #[cfg(all(test, not(nexosim_loom)))]
@@ -140,4 +160,25 @@ mod tests {
         assert_eq!(q.peek(), Some((&5, &'e')));
         assert_eq!(q.pull(), Some((5, 'e')));
     }
+
+    #[test]
+    fn priority_retain() {
+        let mut q = PriorityQueue::new();
+
+        q.insert(3, 'c');
+        q.insert(2, 'y');
+        q.insert(1, 'a');
+        q.insert(2, 'z');
+        q.insert(2, 'x');
+        q.insert(1, 'b');
+
+        q.retain(|_, v| *v != 'z' && *v != 'a');
+
+        assert_eq!(q.len(), 4);
+        assert_eq!(q.pull(), Some((1, 'b')));
+        assert_eq!(q.pull(), Some((2, 'y')));
+        assert_eq!(q.pull(), Some((2, 'x')));
+        assert_eq!(q.pull(), Some((3, 'c')));
+        assert_eq!(q.pull(), None);
+    }
 }
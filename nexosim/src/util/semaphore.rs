@@ -0,0 +1,89 @@
+//! Asynchronous counting semaphore.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_event::Event;
+
+/// An asynchronous counting semaphore.
+pub(crate) struct Semaphore {
+    /// Number of available permits.
+    permits: AtomicUsize,
+    /// Signalling primitive used to notify tasks awaiting a permit.
+    signal: Event,
+}
+
+impl Semaphore {
+    /// Creates a semaphore with the specified number of permits.
+    pub(crate) fn new(permits: usize) -> Self {
+        Self {
+            permits: AtomicUsize::new(permits),
+            signal: Event::new(),
+        }
+    }
+
+    /// Acquires a permit, if necessary waiting until one becomes available.
+    pub(crate) async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.signal.wait_until(|| self.try_acquire()).await
+    }
+
+    /// Acquires a permit if one is immediately available.
+    pub(crate) fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let mut permits = self.permits.load(Ordering::Relaxed);
+        loop {
+            if permits == 0 {
+                return None;
+            }
+
+            match self.permits.compare_exchange_weak(
+                permits,
+                permits - 1,
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(SemaphorePermit { semaphore: self }),
+                Err(current) => permits = current,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.permits.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// A permit returned to the semaphore when dropped.
+#[derive(Debug)]
+pub(crate) struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.permits.fetch_add(1, Ordering::SeqCst);
+        self.semaphore.signal.notify_one();
+    }
+}
+
+#[cfg(all(test, not(nexosim_loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn semaphore_try_acquire() {
+        let semaphore = Semaphore::new(2);
+
+        let permit1 = semaphore.try_acquire();
+        let permit2 = semaphore.try_acquire();
+        assert!(permit1.is_some());
+        assert!(permit2.is_some());
+        assert!(semaphore.try_acquire().is_none());
+
+        drop(permit1);
+        assert!(semaphore.try_acquire().is_some());
+    }
+}
@@ -0,0 +1,58 @@
+//! Blocking reads from an event buffer written by a real-time simulation.
+
+use std::thread;
+use std::time::Duration;
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::{AutoSystemClock, MonotonicTime};
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u32>,
+}
+impl TestModel {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for TestModel {}
+
+fn event_buffer_next_timeout(num_threads: usize) {
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .set_clock(AutoSystemClock::new())
+        .init(MonotonicTime::EPOCH)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_millis(100), TestModel::input, 42, &addr)
+        .unwrap();
+
+    let simulation = thread::spawn(move || simu.step_until(Duration::from_millis(200)));
+
+    assert_eq!(output.next_timeout(Duration::from_secs(10)), Some(42));
+    assert_eq!(output.next_timeout(Duration::from_millis(10)), None);
+
+    simulation.join().unwrap().unwrap();
+}
+
+#[test]
+fn event_buffer_next_timeout_st() {
+    event_buffer_next_timeout(1);
+}
+
+#[test]
+fn event_buffer_next_timeout_mt() {
+    event_buffer_next_timeout(MT_NUM_THREADS);
+}
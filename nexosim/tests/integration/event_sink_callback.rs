@@ -0,0 +1,66 @@
+//! Push-based consumption of event sinks.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use nexosim::model::{Context, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<(MonotonicTime, u32)>,
+}
+impl TestModel {
+    async fn input(&mut self, value: u32, cx: &mut Context<Self>) {
+        self.output.send((cx.time(), value)).await;
+        self.output.send((cx.time(), value + 1)).await;
+    }
+}
+impl Model for TestModel {}
+
+fn sink_callback(num_threads: usize) {
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = events.clone();
+    simu.add_sink_callback(output, move |event| {
+        events_clone.lock().unwrap().push(event)
+    });
+
+    let t1 = t0 + Duration::from_secs(1);
+    scheduler
+        .schedule_event(t1, TestModel::input, 1, &addr)
+        .unwrap();
+
+    simu.process_event(TestModel::input, 10, &addr).unwrap();
+    assert_eq!(*events.lock().unwrap(), vec![(t0, 10), (t0, 11)]);
+    events.lock().unwrap().clear();
+
+    simu.step().unwrap();
+    assert_eq!(*events.lock().unwrap(), vec![(t1, 1), (t1, 2)]);
+}
+
+#[test]
+fn sink_callback_st() {
+    sink_callback(1);
+}
+
+#[test]
+fn sink_callback_mt() {
+    sink_callback(MT_NUM_THREADS);
+}
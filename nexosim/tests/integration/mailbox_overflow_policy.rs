@@ -0,0 +1,83 @@
+//! Discarding of messages sent to a full mailbox.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, OverflowPolicy, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+const MAILBOX_SIZE: usize = 2;
+
+#[derive(Default)]
+struct TestModel {
+    loopback: Output<u32>,
+    output: Output<u32>,
+}
+impl TestModel {
+    async fn burst(&mut self, count: u32) {
+        for value in 1..=count {
+            self.loopback.send(value).await;
+        }
+    }
+    async fn forward(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for TestModel {}
+
+/// Sends 5 messages in loopback to a mailbox that can only hold 2 and returns
+/// the values of the messages that were received.
+fn overflow(num_threads: usize, policy: OverflowPolicy) -> Vec<u32> {
+    let mut model = TestModel::default();
+    let mbox = Mailbox::with_policy(MAILBOX_SIZE, policy);
+    let addr = mbox.address();
+
+    // The loopback messages cannot be processed until the burst is over.
+    model.loopback.connect(TestModel::forward, &addr);
+    let mut received = EventBuffer::new();
+    model.output.connect_sink(&received);
+
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::burst, 5, &addr).unwrap();
+
+    received.by_ref().collect()
+}
+
+fn overflow_drop_oldest(num_threads: usize) {
+    assert_eq!(
+        overflow(num_threads, OverflowPolicy::DropOldest),
+        vec![4, 5]
+    );
+}
+
+fn overflow_drop_newest(num_threads: usize) {
+    assert_eq!(
+        overflow(num_threads, OverflowPolicy::DropNewest),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn overflow_drop_oldest_st() {
+    overflow_drop_oldest(1);
+}
+
+#[test]
+fn overflow_drop_oldest_mt() {
+    overflow_drop_oldest(MT_NUM_THREADS);
+}
+
+#[test]
+fn overflow_drop_newest_st() {
+    overflow_drop_newest(1);
+}
+
+#[test]
+fn overflow_drop_newest_mt() {
+    overflow_drop_newest(MT_NUM_THREADS);
+}
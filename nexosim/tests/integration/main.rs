@@ -1,13 +1,35 @@
 // Integration tests follow the organization suggested by Matklad:
 // https://matklad.github.io/2021/02/27/delete-cargo-integration-tests.html
 
+#[cfg(not(miri))]
+mod event_buffer_timeout;
+mod event_sink_callback;
+mod mailbox_overflow_policy;
+mod model_backpressure;
+#[cfg(not(miri))]
+mod model_concurrency;
 mod model_scheduling;
+mod model_validation;
+mod output_tap;
+#[cfg(feature = "server")]
+mod registry_records;
+#[cfg(feature = "server")]
+mod registry_serialized_events;
 #[cfg(not(miri))]
 mod simulation_clock_sync;
 mod simulation_deadlock;
+mod simulation_determinism;
+mod simulation_dynamic_models;
+mod simulation_event_log;
+mod simulation_event_type_counts;
+mod simulation_merge;
 mod simulation_message_loss;
 mod simulation_no_recipient;
 mod simulation_panic;
+mod simulation_query_broadcast;
+mod simulation_queue_length_history;
+mod simulation_reset;
 mod simulation_scheduling;
+mod simulation_shutdown;
 #[cfg(not(miri))]
 mod simulation_timeout;
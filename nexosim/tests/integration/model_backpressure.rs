@@ -0,0 +1,84 @@
+//! Back-pressure metrics of output port connections.
+
+use std::time::Duration;
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct Producer {
+    output: Output<u32>,
+}
+impl Producer {
+    async fn burst(&mut self, count: u32) {
+        for i in 0..count {
+            self.output.send(i).await;
+        }
+    }
+}
+impl Model for Producer {}
+
+#[derive(Default)]
+struct Consumer {
+    output: Output<u32>,
+}
+impl Consumer {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for Consumer {}
+
+/// Sends a burst of events to a consumer with the specified mailbox capacity
+/// and returns the back-pressure metrics of the connection.
+fn burst(num_threads: usize, mailbox_capacity: usize, count: u32) -> (u64, Duration) {
+    let mut producer = Producer::default();
+    let mut consumer = Consumer::default();
+    let producer_mbox = Mailbox::new();
+    let producer_addr = producer_mbox.address();
+    let consumer_mbox = Mailbox::with_capacity(mailbox_capacity);
+
+    let metrics = producer
+        .output
+        .connect_with_backpressure_metrics(Consumer::input, &consumer_mbox);
+    let mut sink = EventBuffer::new();
+    consumer.output.connect_sink(&sink);
+
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(producer, producer_mbox, "producer")
+        .add_model(consumer, consumer_mbox, "consumer")
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    simu.process_event(Producer::burst, count, producer_addr)
+        .unwrap();
+    assert_eq!(
+        sink.by_ref().collect::<Vec<_>>(),
+        (0..count).collect::<Vec<_>>()
+    );
+
+    (metrics.stall_count(), metrics.total_stall_time())
+}
+
+#[test]
+fn backpressure_metrics_no_stall_st() {
+    assert_eq!(burst(1, 16, 8), (0, Duration::ZERO));
+}
+
+#[test]
+fn backpressure_metrics_no_stall_mt() {
+    assert_eq!(burst(MT_NUM_THREADS, 16, 8), (0, Duration::ZERO));
+}
+
+#[test]
+fn backpressure_metrics_stall_st() {
+    // The single-threaded executor only processes the consumer once the
+    // producer stalls on the full mailbox.
+    let (stall_count, _) = burst(1, 1, 8);
+    assert!(stall_count > 0);
+}
@@ -0,0 +1,163 @@
+//! Concurrency cap of hierarchical models.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use nexosim::model::{BuildContext, Model, ProtoModel};
+use nexosim::ports::{Output, Requestor};
+use nexosim::simulation::{DeadlockInfo, ExecutionError, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+const NUM_WORKERS: usize = 4;
+
+/// Tracker of the current and maximum number of busy workers.
+#[derive(Default)]
+struct Load {
+    current: AtomicUsize,
+    max: AtomicUsize,
+}
+
+struct WorkerModel {
+    load: Arc<Load>,
+}
+impl WorkerModel {
+    fn work(&mut self) {
+        let current = self.load.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.load.max.fetch_max(current, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(20));
+        self.load.current.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+impl Model for WorkerModel {}
+
+struct DispatcherModel {
+    to_workers: Output<()>,
+}
+impl DispatcherModel {
+    async fn dispatch(&mut self) {
+        self.to_workers.send(()).await;
+    }
+}
+impl Model for DispatcherModel {}
+
+struct ProtoDispatcherModel {
+    load: Arc<Load>,
+}
+impl ProtoModel for ProtoDispatcherModel {
+    type Model = DispatcherModel;
+
+    fn build(self, cx: &mut BuildContext<Self>) -> DispatcherModel {
+        let mut to_workers = Output::default();
+        for i in 0..NUM_WORKERS {
+            let worker_mbox = Mailbox::new();
+            to_workers.connect(WorkerModel::work, &worker_mbox);
+            cx.add_submodel(
+                WorkerModel {
+                    load: self.load.clone(),
+                },
+                worker_mbox,
+                format!("worker{}", i),
+            );
+        }
+
+        DispatcherModel { to_workers }
+    }
+}
+
+fn model_concurrency_cap(num_threads: usize, max_concurrent: usize) -> usize {
+    let load = Arc::new(Load::default());
+    let dispatcher = ProtoDispatcherModel { load: load.clone() };
+    let dispatcher_mbox = Mailbox::new();
+    let dispatcher_addr = dispatcher_mbox.address();
+
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model_with_concurrency(dispatcher, dispatcher_mbox, "dispatcher", max_concurrent)
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    simu.process_event(DispatcherModel::dispatch, (), &dispatcher_addr)
+        .unwrap();
+
+    load.max.load(Ordering::SeqCst)
+}
+
+struct ReplierModel {}
+impl ReplierModel {
+    async fn reply(&mut self) -> u32 {
+        42
+    }
+}
+impl Model for ReplierModel {}
+
+struct RequesterModel {
+    to_replier: Requestor<(), u32>,
+}
+impl RequesterModel {
+    async fn request(&mut self) {
+        let _ = self.to_replier.send(()).await.next();
+    }
+}
+impl Model for RequesterModel {}
+
+struct ProtoRequesterModel {}
+impl ProtoModel for ProtoRequesterModel {
+    type Model = RequesterModel;
+
+    fn build(self, cx: &mut BuildContext<Self>) -> RequesterModel {
+        let replier_mbox = Mailbox::new();
+        let mut to_replier = Requestor::default();
+        to_replier.connect(ReplierModel::reply, &replier_mbox);
+        cx.add_submodel(ReplierModel {}, replier_mbox, "replier");
+
+        RequesterModel { to_replier }
+    }
+}
+
+fn model_concurrency_deadlock(num_threads: usize) {
+    let requester_mbox = Mailbox::new();
+    let requester_addr = requester_mbox.address();
+
+    // The query to the submodel cannot obtain the only permit, which is held
+    // by the requester while it awaits the reply.
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model_with_concurrency(ProtoRequesterModel {}, requester_mbox, "requester", 1)
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    match simu.process_event(RequesterModel::request, (), &requester_addr) {
+        Err(ExecutionError::Deadlock(deadlock_info)) => assert_eq!(
+            deadlock_info,
+            vec![DeadlockInfo {
+                model: "requester.replier".to_string(),
+                mailbox_size: 1,
+            }]
+        ),
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn model_concurrency_cap_st() {
+    assert_eq!(model_concurrency_cap(1, 1), 1);
+}
+
+#[test]
+fn model_concurrency_cap_mt() {
+    assert_eq!(model_concurrency_cap(MT_NUM_THREADS, 1), 1);
+    assert!(model_concurrency_cap(MT_NUM_THREADS, 2) <= 2);
+}
+
+#[test]
+fn model_concurrency_deadlock_st() {
+    model_concurrency_deadlock(1);
+}
+
+#[test]
+fn model_concurrency_deadlock_mt() {
+    model_concurrency_deadlock(MT_NUM_THREADS);
+}
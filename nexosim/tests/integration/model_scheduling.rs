@@ -2,9 +2,9 @@
 
 use std::time::Duration;
 
-use nexosim::model::{Context, Model};
+use nexosim::model::{Context, InitializedModel, Model};
 use nexosim::ports::{EventBuffer, Output};
-use nexosim::simulation::{ActionKey, Mailbox, SimInit};
+use nexosim::simulation::{ActionKey, Address, ExecutionError, Mailbox, SimInit};
 use nexosim::time::MonotonicTime;
 
 const MT_NUM_THREADS: usize = 4;
@@ -243,6 +243,87 @@ fn model_cancel_periodic_event(num_threads: usize) {
     assert!(output.next().is_none());
 }
 
+fn model_schedule_event_on_other_model(num_threads: usize) {
+    #[derive(Default)]
+    struct Receiver {
+        output: Output<i32>,
+    }
+    impl Receiver {
+        async fn input(&mut self, value: i32) {
+            self.output.send(value).await;
+        }
+    }
+    impl Model for Receiver {}
+
+    struct Dispatcher {
+        receiver: Address<Receiver>,
+    }
+    impl Dispatcher {
+        fn trigger(&mut self, _: (), cx: &mut Context<Self>) {
+            cx.schedule_event_on(Duration::from_secs(1), Receiver::input, 1, &self.receiver)
+                .unwrap();
+            let key = cx
+                .schedule_keyed_event_on(Duration::from_secs(1), Receiver::input, 2, &self.receiver)
+                .unwrap();
+            cx.schedule_event_on(Duration::from_secs(1), Receiver::input, 3, &self.receiver)
+                .unwrap();
+            key.cancel();
+        }
+    }
+    impl Model for Dispatcher {}
+
+    let mut receiver = Receiver::default();
+    let receiver_mbox = Mailbox::new();
+    let dispatcher_mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    receiver.output.connect_sink(&output);
+    let dispatcher = Dispatcher {
+        receiver: receiver_mbox.address(),
+    };
+    let addr = dispatcher_mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(receiver, receiver_mbox, "receiver")
+        .add_model(dispatcher, dispatcher_mbox, "dispatcher")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    simu.process_event(Dispatcher::trigger, (), addr).unwrap();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(3));
+    assert!(output.next().is_none());
+}
+
+fn model_init_schedule_in_past(num_threads: usize) {
+    struct TestModel;
+    impl TestModel {
+        async fn action(&mut self) {}
+    }
+    impl Model for TestModel {
+        async fn init(self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+            let _ = cx.schedule_event(Duration::ZERO, Self::action, ());
+
+            self.into()
+        }
+    }
+
+    let t0 = MonotonicTime::EPOCH;
+    let result = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel, Mailbox::new(), "test_model")
+        .set_init_scheduling_check(true)
+        .init(t0);
+
+    match result {
+        Err(ExecutionError::InitScheduledInPast { model }) => assert_eq!(model, "test_model"),
+        _ => panic!("init-time scheduling in the past not detected"),
+    }
+}
+
 #[test]
 fn model_schedule_event_st() {
     model_schedule_event(1);
@@ -292,3 +373,23 @@ fn model_cancel_periodic_event_st() {
 fn model_cancel_periodic_event_mt() {
     model_cancel_periodic_event(MT_NUM_THREADS);
 }
+
+#[test]
+fn model_schedule_event_on_other_model_st() {
+    model_schedule_event_on_other_model(1);
+}
+
+#[test]
+fn model_schedule_event_on_other_model_mt() {
+    model_schedule_event_on_other_model(MT_NUM_THREADS);
+}
+
+#[test]
+fn model_init_schedule_in_past_st() {
+    model_init_schedule_in_past(1);
+}
+
+#[test]
+fn model_init_schedule_in_past_mt() {
+    model_init_schedule_in_past(MT_NUM_THREADS);
+}
@@ -0,0 +1,98 @@
+//! Validation of model prototypes.
+
+use nexosim::model::{BuildContext, ConnectionWarning, Model, ProtoModel};
+use nexosim::ports::Output;
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct TestModel {
+    output: Output<u32>,
+}
+impl TestModel {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for TestModel {}
+
+#[derive(Default)]
+struct ProtoTestModel {
+    output: Output<u32>,
+}
+impl ProtoModel for ProtoTestModel {
+    type Model = TestModel;
+
+    fn validate(&self) -> Result<(), Vec<ConnectionWarning>> {
+        if self.output.is_connected() {
+            Ok(())
+        } else {
+            Err(vec![ConnectionWarning::UnconnectedOutput("output".into())])
+        }
+    }
+
+    fn build(self, _: &mut BuildContext<Self>) -> TestModel {
+        TestModel {
+            output: self.output,
+        }
+    }
+}
+
+fn unconnected_ports(num_threads: usize) {
+    let mut connected_model = ProtoTestModel::default();
+    let unconnected_mbox = Mailbox::new();
+    connected_model
+        .output
+        .connect(TestModel::input, &unconnected_mbox);
+
+    let res = SimInit::with_num_threads(num_threads)
+        .add_model(connected_model, Mailbox::new(), "connected")
+        .add_model(ProtoTestModel::default(), unconnected_mbox, "unconnected")
+        .init(MonotonicTime::EPOCH);
+
+    match res {
+        Err(ExecutionError::UnconnectedPorts(warnings)) => assert_eq!(
+            warnings,
+            vec![(
+                "unconnected".to_string(),
+                ConnectionWarning::UnconnectedOutput("output".into())
+            )]
+        ),
+        _ => panic!("unconnected port not reported"),
+    }
+}
+
+fn unconnected_ports_added_at_runtime(num_threads: usize) {
+    let (mut simu, _) = SimInit::with_num_threads(num_threads)
+        .init(MonotonicTime::EPOCH)
+        .unwrap();
+
+    assert!(matches!(
+        simu.add_model(ProtoTestModel::default(), Mailbox::new(), "unconnected"),
+        Err(ExecutionError::UnconnectedPorts(_))
+    ));
+
+    // The rejected model was not added.
+    assert!(simu.mailbox_occupancy().is_empty());
+}
+
+#[test]
+fn unconnected_ports_st() {
+    unconnected_ports(1);
+}
+
+#[test]
+fn unconnected_ports_mt() {
+    unconnected_ports(MT_NUM_THREADS);
+}
+
+#[test]
+fn unconnected_ports_added_at_runtime_st() {
+    unconnected_ports_added_at_runtime(1);
+}
+
+#[test]
+fn unconnected_ports_added_at_runtime_mt() {
+    unconnected_ports_added_at_runtime(MT_NUM_THREADS);
+}
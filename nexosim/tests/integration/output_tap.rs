@@ -0,0 +1,79 @@
+//! Tapping of output ports.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct Forwarder {
+    output: Output<u32>,
+}
+impl Forwarder {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for Forwarder {}
+
+#[derive(Default)]
+struct Doubler {
+    output: Output<u32>,
+}
+impl Doubler {
+    async fn input(&mut self, value: u32) {
+        self.output.send(2 * value).await;
+    }
+}
+impl Model for Doubler {}
+
+fn output_tap(num_threads: usize) {
+    let mut forwarder = Forwarder::default();
+    let mut doubler = Doubler::default();
+    let forwarder_mbox = Mailbox::new();
+    let doubler_mbox = Mailbox::new();
+    let forwarder_addr = forwarder_mbox.address();
+
+    forwarder
+        .output
+        .connect(Doubler::input, doubler_mbox.address());
+    let mut doubled = EventBuffer::new();
+    doubler.output.connect_sink(&doubled);
+
+    // Keep a clone of the output to tap it once the bench is assembled.
+    let mut forwarder_output = forwarder.output.clone();
+
+    let (mut simu, _) = SimInit::with_num_threads(num_threads)
+        .add_model(forwarder, forwarder_mbox, "forwarder")
+        .add_model(doubler, doubler_mbox, "doubler")
+        .init(MonotonicTime::EPOCH)
+        .unwrap();
+
+    let mut tapped = EventBuffer::new();
+    let tap_id = forwarder_output.tap(&tapped);
+
+    simu.process_event(Forwarder::input, 3, &forwarder_addr)
+        .unwrap();
+    assert_eq!(tapped.next(), Some(3));
+    assert_eq!(doubled.next(), Some(6));
+
+    assert!(forwarder_output.untap(tap_id));
+    assert!(!forwarder_output.untap(tap_id));
+
+    simu.process_event(Forwarder::input, 5, &forwarder_addr)
+        .unwrap();
+    assert!(tapped.next().is_none());
+    assert_eq!(doubled.next(), Some(10));
+}
+
+#[test]
+fn output_tap_st() {
+    output_tap(1);
+}
+
+#[test]
+fn output_tap_mt() {
+    output_tap(MT_NUM_THREADS);
+}
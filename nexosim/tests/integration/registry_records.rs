@@ -0,0 +1,86 @@
+//! Scheduling of timestamped records with a registered event source.
+
+use std::time::Duration;
+
+use nexosim::model::{Context, Model};
+use nexosim::ports::{EventBuffer, EventSource, Output};
+use nexosim::registry::{EndpointRegistry, RecordSchedulingError};
+use nexosim::simulation::{Mailbox, SchedulingError, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<(MonotonicTime, u32)>,
+}
+impl TestModel {
+    async fn input(&mut self, value: u32, cx: &mut Context<Self>) {
+        self.output.send((cx.time(), value)).await;
+    }
+}
+impl Model for TestModel {}
+
+fn schedule_records(num_threads: usize) {
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let mut source = EventSource::new();
+    source.connect(TestModel::input, &mbox);
+    let mut registry = EndpointRegistry::new();
+    registry.add_event_source(source, "input").unwrap();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    let t1 = t0 + Duration::from_secs(1);
+    let t2 = t0 + Duration::from_secs(2);
+
+    assert_eq!(
+        registry.schedule_records("input", [(t2, 1u32), (t1, 2u32)], &scheduler),
+        Err(RecordSchedulingError::OutOfOrder { index: 1 })
+    );
+    assert_eq!(
+        registry.schedule_records("input", [(t0, 1u32)], &scheduler),
+        Err(RecordSchedulingError::Scheduling {
+            index: 0,
+            error: SchedulingError::InvalidScheduledTime
+        })
+    );
+    assert_eq!(
+        registry.schedule_records("input", [(t1, 1u64)], &scheduler),
+        Err(RecordSchedulingError::EventTypeMismatch)
+    );
+    assert_eq!(
+        registry.schedule_records("output", [(t1, 1u32)], &scheduler),
+        Err(RecordSchedulingError::SourceNotFound)
+    );
+
+    registry
+        .schedule_records("input", [(t1, 1u32), (t1, 2u32), (t2, 3u32)], &scheduler)
+        .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some((t1, 1)));
+    assert_eq!(output.next(), Some((t1, 2)));
+    assert!(output.next().is_none());
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some((t2, 3)));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn schedule_records_st() {
+    schedule_records(1);
+}
+
+#[test]
+fn schedule_records_mt() {
+    schedule_records(MT_NUM_THREADS);
+}
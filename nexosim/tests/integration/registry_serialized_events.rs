@@ -0,0 +1,169 @@
+//! Processing of serialized and typed events with a registered event source.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, EventSource, Output};
+use nexosim::registry::{Codec, EndpointRegistry, SerializedEventError};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u32>,
+}
+impl TestModel {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for TestModel {}
+
+fn process_serialized_event(num_threads: usize) {
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let mut source = EventSource::new();
+    source.connect(TestModel::input, &mbox);
+    let mut registry = EndpointRegistry::new();
+    registry.add_event_source(source, "input").unwrap();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    assert!(matches!(
+        registry.process_serialized_event("output", b"1", Codec::Json, &mut simu),
+        Err(SerializedEventError::SourceNotFound)
+    ));
+    assert!(matches!(
+        registry.process_serialized_event("input", b"\"one\"", Codec::Json, &mut simu),
+        Err(SerializedEventError::Decoding(_))
+    ));
+    assert!(output.next().is_none());
+
+    registry
+        .process_serialized_event("input", b"42", Codec::Json, &mut simu)
+        .unwrap();
+    assert_eq!(simu.time(), t0);
+    assert_eq!(output.next(), Some(42));
+    assert!(output.next().is_none());
+}
+
+fn process_typed_event(num_threads: usize) {
+    let mut model = TestModel::default();
+    let mbox = Mailbox::new();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let mut source = EventSource::new();
+    source.connect(TestModel::input, &mbox);
+    let mut registry = EndpointRegistry::new();
+    registry.add_event_source(source, "input").unwrap();
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    assert!(registry.event_source::<u32>("output").is_none());
+    assert!(registry.event_source::<u64>("input").is_none());
+
+    let source = registry.event_source::<u32>("input").unwrap();
+    simu.process(source.event(42)).unwrap();
+    assert_eq!(output.next(), Some(42));
+
+    // Typed and serialized events are broadcast by the same source.
+    registry
+        .process_serialized_event("input", b"43", Codec::Json, &mut simu)
+        .unwrap();
+    assert_eq!(output.next(), Some(43));
+    assert!(output.next().is_none());
+}
+
+fn process_multi_source_event(num_threads: usize) {
+    let mut registry = EndpointRegistry::new();
+    let mut bench = SimInit::with_num_threads(num_threads);
+    let mut outputs = Vec::new();
+    let mut sources = Vec::new();
+    for i in 0..3 {
+        let mut model = TestModel::default();
+        let mbox = Mailbox::new();
+
+        let output = EventBuffer::new();
+        model.output.connect_sink(&output);
+        outputs.push(output);
+
+        let mut source = EventSource::new();
+        source.connect(TestModel::input, &mbox);
+        sources.push(source);
+
+        bench = bench.add_model(model, mbox, format!("model{}", i));
+    }
+
+    registry.add_multi_event_source(sources, "input").unwrap();
+
+    // The name is already in use.
+    let mut source = EventSource::new();
+    source.connect(TestModel::input, &Mailbox::<TestModel>::new());
+    assert!(registry
+        .add_multi_event_source(vec![source], "input")
+        .is_err());
+
+    let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+
+    // The event is broadcast to the models connected to all sources.
+    registry
+        .process_serialized_event("input", b"42", Codec::Json, &mut simu)
+        .unwrap();
+    for output in &mut outputs {
+        assert_eq!(output.next(), Some(42));
+        assert!(output.next().is_none());
+    }
+
+    let source = registry.event_source::<u32>("input").unwrap();
+    simu.process(source.event(43)).unwrap();
+    for output in &mut outputs {
+        assert_eq!(output.next(), Some(43));
+        assert!(output.next().is_none());
+    }
+}
+
+#[test]
+fn process_serialized_event_st() {
+    process_serialized_event(1);
+}
+
+#[test]
+fn process_serialized_event_mt() {
+    process_serialized_event(MT_NUM_THREADS);
+}
+
+#[test]
+fn process_typed_event_st() {
+    process_typed_event(1);
+}
+
+#[test]
+fn process_typed_event_mt() {
+    process_typed_event(MT_NUM_THREADS);
+}
+
+#[test]
+fn process_multi_source_event_st() {
+    process_multi_source_event(1);
+}
+
+#[test]
+fn process_multi_source_event_mt() {
+    process_multi_source_event(MT_NUM_THREADS);
+}
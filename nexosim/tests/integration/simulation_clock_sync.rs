@@ -1,11 +1,11 @@
 //! Loss of synchronization during simulation step execution.
 
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use nexosim::model::Model;
 use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
-use nexosim::time::{AutoSystemClock, MonotonicTime};
+use nexosim::time::{AutoSystemClock, MonotonicTime, NoClock};
 
 const MT_NUM_THREADS: usize = 4;
 
@@ -96,6 +96,82 @@ fn clock_sync_with_tolerance(num_threads: usize) {
     }
 }
 
+fn clock_swap(num_threads: usize) {
+    const WARMUP: Duration = Duration::from_secs(3600);
+    const LIVE: Duration = Duration::from_millis(200);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, _scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel::default(), Mailbox::new(), "test")
+        .set_clock(NoClock::new())
+        .set_clock_tolerance(Duration::ZERO)
+        .init(t0)
+        .unwrap();
+
+    simu.step_until(WARMUP).unwrap();
+    assert_eq!(simu.time(), t0 + WARMUP);
+
+    // The new clock takes the current simulation time as its reference, so
+    // only the live phase is run in real time.
+    simu.set_clock(AutoSystemClock::new()).unwrap();
+    let start = Instant::now();
+    simu.step_until(LIVE).unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(simu.time(), t0 + WARMUP + LIVE);
+    assert!(elapsed >= LIVE);
+    assert!(elapsed < WARMUP);
+}
+
+fn run_for_walltime(num_threads: usize) {
+    const PERIOD: Duration = Duration::from_millis(100);
+    const WALLTIME: Duration = Duration::from_millis(350);
+
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel::default(), mbox, "test")
+        .set_clock(AutoSystemClock::new())
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_periodic_event(PERIOD, PERIOD, TestModel::block_for, Duration::ZERO, &addr)
+        .unwrap();
+
+    // The step at 300ms completes before the wall clock duration has elapsed,
+    // so the step at 400ms is processed as well.
+    let start = Instant::now();
+    let time = simu.run_for_walltime(WALLTIME).unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(time, t0 + 4 * PERIOD);
+    assert!(elapsed >= WALLTIME);
+
+    // Without a real-time clock, the simulation runs until no event remains.
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel::default(), mbox, "test")
+        .init(t0)
+        .unwrap();
+    for hours in 1..=3 {
+        scheduler
+            .schedule_event(
+                Duration::from_secs(hours * 3600),
+                TestModel::block_for,
+                Duration::ZERO,
+                &addr,
+            )
+            .unwrap();
+    }
+
+    let time = simu.run_for_walltime(WALLTIME).unwrap();
+    assert_eq!(time, t0 + Duration::from_secs(3 * 3600));
+}
+
 #[test]
 fn clock_sync_zero_tolerance_st() {
     clock_sync_zero_tolerance(1);
@@ -115,3 +191,23 @@ fn clock_sync_with_tolerance_st() {
 fn clock_sync_with_tolerance_mt() {
     clock_sync_with_tolerance(MT_NUM_THREADS);
 }
+
+#[test]
+fn clock_swap_st() {
+    clock_swap(1);
+}
+
+#[test]
+fn clock_swap_mt() {
+    clock_swap(MT_NUM_THREADS);
+}
+
+#[test]
+fn run_for_walltime_st() {
+    run_for_walltime(1);
+}
+
+#[test]
+fn run_for_walltime_mt() {
+    run_for_walltime(MT_NUM_THREADS);
+}
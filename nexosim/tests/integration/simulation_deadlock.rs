@@ -63,6 +63,11 @@ fn deadlock_on_mailbox_overflow(num_threads: usize) {
         }
         _ => panic!("deadlock not detected"),
     }
+
+    assert_eq!(
+        simu.mailbox_occupancy(),
+        vec![(MODEL_NAME.to_string(), MAILBOX_SIZE, MAILBOX_SIZE)]
+    );
 }
 
 /// Generates a deadlock with a query loopback.
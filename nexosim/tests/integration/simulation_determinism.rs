@@ -0,0 +1,155 @@
+//! Determinism checks based on the event journal.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use nexosim::model::{Context, InitializedModel, Model};
+use nexosim::ports::Output;
+use nexosim::simulation::{DeterminismChecker, DeterminismError, Mailbox, SimInit, Simulation};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u64>,
+}
+impl TestModel {
+    async fn forward(&mut self, value: u32) {
+        self.output.send(value as u64).await;
+    }
+    async fn sink(&mut self, _value: u64) {}
+}
+impl Model for TestModel {}
+
+/// Runs a bench where a source model broadcasts to several sink models,
+/// optionally with an additional event at the last time slice.
+fn run_bench(num_threads: usize, extra_event: bool) -> Simulation {
+    const NUM_SINKS: usize = 4;
+
+    let mut source = TestModel::default();
+    let source_mbox = Mailbox::new();
+    let source_addr = source_mbox.address();
+
+    let mut bench = SimInit::with_num_threads(num_threads).set_event_journal(true);
+    for i in 0..NUM_SINKS {
+        let sink_mbox = Mailbox::new();
+        source.output.connect(TestModel::sink, &sink_mbox);
+        bench = bench.add_model(TestModel::default(), sink_mbox, format!("sink{}", i));
+    }
+
+    let (mut simu, scheduler) = bench
+        .add_model(source, source_mbox, "source")
+        .init(MonotonicTime::EPOCH)
+        .unwrap();
+
+    for i in 1..=3 {
+        scheduler
+            .schedule_event(Duration::from_secs(i), TestModel::forward, 1, &source_addr)
+            .unwrap();
+    }
+    if extra_event {
+        scheduler
+            .schedule_event(Duration::from_secs(3), TestModel::forward, 2, &source_addr)
+            .unwrap();
+    }
+    simu.step_until(Duration::from_secs(3)).unwrap();
+
+    simu
+}
+
+fn determinism_check(num_threads: usize) {
+    let mut checker = DeterminismChecker::new();
+
+    checker.check(&run_bench(num_threads, false)).unwrap();
+    checker.check(&run_bench(num_threads, false)).unwrap();
+    checker.check(&run_bench(1, false)).unwrap();
+
+    assert_eq!(
+        checker.check(&run_bench(num_threads, true)),
+        Err(DeterminismError::Divergence {
+            slice: 2,
+            time: MonotonicTime::EPOCH + Duration::from_secs(3)
+        })
+    );
+}
+
+/// A model that logs its name at t=1s.
+struct LoggingModel {
+    name: usize,
+    log: Arc<Mutex<Vec<usize>>>,
+}
+impl LoggingModel {
+    fn log(&mut self) {
+        self.log.lock().unwrap().push(self.name);
+    }
+}
+impl Model for LoggingModel {
+    async fn init(self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+        cx.schedule_event(Duration::from_secs(1), Self::log, ())
+            .unwrap();
+
+        self.into()
+    }
+}
+
+/// Returns the order in which models log their name within a time slice.
+fn run_logging_bench() -> Vec<usize> {
+    const NUM_MODELS: usize = 8;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut bench = SimInit::deterministic();
+    for name in 0..NUM_MODELS {
+        let model = LoggingModel {
+            name,
+            log: log.clone(),
+        };
+        bench = bench.add_model(model, Mailbox::new(), name.to_string());
+    }
+    let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+    simu.step().unwrap();
+
+    let log = log.lock().unwrap().clone();
+    assert_eq!(log.len(), NUM_MODELS);
+
+    log
+}
+
+#[test]
+fn deterministic_cross_model_order() {
+    let reference = run_logging_bench();
+    for _ in 0..3 {
+        assert_eq!(run_logging_bench(), reference);
+    }
+}
+
+#[test]
+fn determinism_check_st() {
+    determinism_check(1);
+}
+
+#[test]
+fn determinism_check_mt() {
+    determinism_check(MT_NUM_THREADS);
+}
+
+#[test]
+fn determinism_check_journal_disabled() {
+    let (simu, _) = SimInit::new().init(MonotonicTime::EPOCH).unwrap();
+
+    assert_eq!(
+        DeterminismChecker::new().check(&simu),
+        Err(DeterminismError::JournalDisabled)
+    );
+
+    // A bounded event log does not make the journal complete.
+    let (simu, _) = SimInit::new()
+        .set_event_log(10)
+        .init(MonotonicTime::EPOCH)
+        .unwrap();
+
+    assert_eq!(
+        DeterminismChecker::new().check(&simu),
+        Err(DeterminismError::JournalDisabled)
+    );
+}
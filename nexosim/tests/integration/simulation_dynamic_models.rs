@@ -0,0 +1,173 @@
+//! Addition and removal of models in a running simulation.
+
+use std::time::Duration;
+
+use nexosim::model::{Context, InitializedModel, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<(&'static str, MonotonicTime)>,
+    label: &'static str,
+}
+impl TestModel {
+    async fn ping(&mut self, _: (), cx: &mut Context<Self>) {
+        self.output.send((self.label, cx.time())).await;
+    }
+}
+impl Model for TestModel {
+    async fn init(mut self, cx: &mut Context<Self>) -> InitializedModel<Self> {
+        self.output.send((self.label, cx.time())).await;
+
+        self.into()
+    }
+    async fn terminate(&mut self, cx: &mut Context<Self>) {
+        self.output.send(("terminated", cx.time())).await;
+    }
+}
+
+#[derive(Default)]
+struct Forwarder {
+    output: Output<()>,
+}
+impl Forwarder {
+    async fn forward(&mut self) {
+        self.output.send(()).await;
+    }
+}
+impl Model for Forwarder {}
+
+fn add_model_after_init(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut output = EventBuffer::new();
+
+    let mut model1 = TestModel {
+        label: "model1",
+        ..Default::default()
+    };
+    model1.output.connect_sink(&output);
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .init(t0)
+        .unwrap();
+
+    assert_eq!(output.next(), Some(("model1", t0)));
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), TestModel::ping, (), &addr1)
+        .unwrap();
+    simu.step().unwrap();
+    let t1 = t0 + Duration::from_secs(1);
+    assert_eq!(output.next(), Some(("model1", t1)));
+
+    let mut model2 = TestModel {
+        label: "model2",
+        ..Default::default()
+    };
+    model2.output.connect_sink(&output);
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    // The new model is initialized at the current simulation time.
+    simu.add_model(model2, mbox2, "model2").unwrap();
+    assert_eq!(output.next(), Some(("model2", t1)));
+    assert!(output.next().is_none());
+
+    // The new model can be sent events.
+    simu.process_event(TestModel::ping, (), &addr2).unwrap();
+    assert_eq!(output.next(), Some(("model2", t1)));
+
+    scheduler
+        .schedule_event(Duration::from_secs(2), TestModel::ping, (), &addr2)
+        .unwrap();
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some(("model2", t1 + Duration::from_secs(2))));
+    assert!(output.next().is_none());
+}
+
+#[test]
+fn add_model_after_init_st() {
+    add_model_after_init(1);
+}
+
+#[test]
+fn add_model_after_init_mt() {
+    add_model_after_init(MT_NUM_THREADS);
+}
+
+fn remove_model(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut output = EventBuffer::new();
+
+    let mut model = TestModel {
+        label: "model",
+        ..Default::default()
+    };
+    model.output.connect_sink(&output);
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut forwarder = Forwarder::default();
+    forwarder.output.connect(TestModel::ping, &addr);
+    let forwarder_mbox = Mailbox::new();
+    let forwarder_addr = forwarder_mbox.address();
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .add_model(forwarder, forwarder_mbox, "forwarder")
+        .init(t0)
+        .unwrap();
+
+    assert_eq!(output.next(), Some(("model", t0)));
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), TestModel::ping, (), &addr)
+        .unwrap();
+    scheduler
+        .schedule_periodic_event(
+            Duration::from_secs(2),
+            Duration::from_secs(1),
+            TestModel::ping,
+            (),
+            &addr,
+        )
+        .unwrap();
+
+    // The model is terminated before its removal.
+    simu.remove_model(&addr).unwrap();
+    assert_eq!(output.next(), Some(("terminated", t0)));
+
+    // Events scheduled for the removed model, including periodic ones, are
+    // cancelled.
+    assert_eq!(simu.step().unwrap(), None);
+    assert_eq!(simu.time(), t0);
+    simu.process_event(TestModel::ping, (), &addr).unwrap();
+    assert!(output.next().is_none());
+
+    // Events sent from other models are reported as having no recipient.
+    match simu.process_event(Forwarder::forward, (), &forwarder_addr) {
+        Err(ExecutionError::NoRecipient { model }) => {
+            assert_eq!(model, Some(String::from("forwarder")));
+        }
+        _ => panic!("missing recipient not detected"),
+    }
+}
+
+#[test]
+fn remove_model_st() {
+    remove_model(1);
+}
+
+#[test]
+fn remove_model_mt() {
+    remove_model(MT_NUM_THREADS);
+}
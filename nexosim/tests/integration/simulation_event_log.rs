@@ -0,0 +1,107 @@
+//! Bounded logging of processed events.
+
+use std::time::Duration;
+
+use nexosim::model::Model;
+use nexosim::ports::Output;
+use nexosim::simulation::{EventLogEntry, Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u64>,
+}
+impl TestModel {
+    async fn forward(&mut self, value: u32) {
+        self.output.send(value as u64).await;
+    }
+    async fn sink(&mut self, _value: u64) {}
+}
+impl Model for TestModel {}
+
+fn event_log(num_threads: usize, event_journal: bool) {
+    let mut model1 = TestModel::default();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let model2 = TestModel::default();
+    let mbox2 = Mailbox::new();
+
+    model1.output.connect(TestModel::sink, &mbox2);
+
+    let t0 = MonotonicTime::EPOCH;
+    let t1 = t0 + Duration::from_secs(1);
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .set_event_log(3)
+        .set_event_journal(event_journal)
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(t1, TestModel::forward, 1, &addr1)
+        .unwrap();
+    simu.process_event(TestModel::forward, 2, &addr1).unwrap();
+    simu.step().unwrap();
+
+    // The oldest of the 4 processed events was discarded.
+    let entry = |time, model_name: &str, event_type| EventLogEntry {
+        time,
+        model_name: model_name.to_string(),
+        event_type,
+    };
+    assert_eq!(
+        simu.event_log(),
+        vec![
+            entry(t0, "model2", std::any::type_name::<u64>()),
+            entry(t1, "model1", std::any::type_name::<u32>()),
+            entry(t1, "model2", std::any::type_name::<u64>()),
+        ]
+    );
+}
+
+fn event_log_disabled(num_threads: usize) {
+    let model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let (mut simu, _) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .init(MonotonicTime::EPOCH)
+        .unwrap();
+
+    simu.process_event(TestModel::forward, 1, &addr).unwrap();
+    assert!(simu.event_log().is_empty());
+}
+
+#[test]
+fn event_log_st() {
+    event_log(1, false);
+}
+
+#[test]
+fn event_log_mt() {
+    event_log(MT_NUM_THREADS, false);
+}
+
+#[test]
+fn event_log_with_journal_st() {
+    event_log(1, true);
+}
+
+#[test]
+fn event_log_with_journal_mt() {
+    event_log(MT_NUM_THREADS, true);
+}
+
+#[test]
+fn event_log_disabled_st() {
+    event_log_disabled(1);
+}
+
+#[test]
+fn event_log_disabled_mt() {
+    event_log_disabled(MT_NUM_THREADS);
+}
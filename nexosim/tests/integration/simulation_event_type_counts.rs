@@ -0,0 +1,96 @@
+//! Counting of processed events by event type.
+
+use std::time::Duration;
+
+use nexosim::model::Model;
+use nexosim::ports::Output;
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {
+    output: Output<u64>,
+}
+impl TestModel {
+    async fn forward(&mut self, value: u32) {
+        self.output.send(value as u64).await;
+    }
+    async fn sink(&mut self, _value: u64) {}
+    async fn get(&mut self, _: ()) -> bool {
+        true
+    }
+}
+impl Model for TestModel {}
+
+fn event_type_counts(num_threads: usize) {
+    let mut model1 = TestModel::default();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let model2 = TestModel::default();
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    model1.output.connect(TestModel::sink, &mbox2);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .set_event_type_counting(true)
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), TestModel::forward, 1, &addr1)
+        .unwrap();
+    simu.process_event(TestModel::forward, 2, &addr1).unwrap();
+    simu.process_query(TestModel::get, (), &addr2).unwrap();
+    simu.step().unwrap();
+
+    let counts = simu.event_type_counts();
+    assert_eq!(counts.get("u32"), Some(&2));
+    assert_eq!(counts.get("u64"), Some(&2));
+    assert_eq!(counts.get("()"), Some(&1));
+    assert_eq!(counts.len(), 3);
+
+    simu.reset_event_type_counts();
+    assert!(simu.event_type_counts().is_empty());
+}
+
+fn event_type_counts_disabled(num_threads: usize) {
+    let model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .init(MonotonicTime::EPOCH)
+        .unwrap()
+        .0;
+
+    simu.process_event(TestModel::sink, 1, &addr).unwrap();
+
+    assert!(simu.event_type_counts().is_empty());
+}
+
+#[test]
+fn event_type_counts_st() {
+    event_type_counts(1);
+}
+
+#[test]
+fn event_type_counts_mt() {
+    event_type_counts(MT_NUM_THREADS);
+}
+
+#[test]
+fn event_type_counts_disabled_st() {
+    event_type_counts_disabled(1);
+}
+
+#[test]
+fn event_type_counts_disabled_mt() {
+    event_type_counts_disabled(MT_NUM_THREADS);
+}
@@ -0,0 +1,185 @@
+//! Merging of independently-built simulation benches.
+
+use nexosim::model::Model;
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct PassThroughModel {
+    output: Output<u32>,
+}
+impl PassThroughModel {
+    async fn input(&mut self, value: u32) {
+        self.output.send(value).await;
+    }
+}
+impl Model for PassThroughModel {}
+
+fn merge_benches(num_threads: usize) {
+    let mut model1 = PassThroughModel::default();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+
+    let mut model2 = PassThroughModel::default();
+    let mbox2 = Mailbox::new();
+
+    // Cross-bench connection established before the merge.
+    model1.output.connect(PassThroughModel::input, &mbox2);
+    let mut output = EventBuffer::new();
+    model2.output.connect_sink(&output);
+
+    let bench1 = SimInit::with_num_threads(num_threads).add_model(model1, mbox1, "model1");
+    let bench2 = SimInit::with_num_threads(num_threads).add_model(model2, mbox2, "model2");
+
+    let mut simu = bench1.merge(bench2).init(MonotonicTime::EPOCH).unwrap().0;
+
+    simu.process_event(PassThroughModel::input, 42, &addr1)
+        .unwrap();
+    assert_eq!(output.next(), Some(42));
+    assert_eq!(output.next(), None);
+}
+
+fn merge_benches_duplicate_name(num_threads: usize) {
+    let mut model1 = PassThroughModel::default();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let mut model2 = PassThroughModel::default();
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    let mut output = EventBuffer::new();
+    model1.output.connect_sink(&output);
+    model2.output.connect_sink(&output);
+
+    // Model names need not be unique, either within or across benches.
+    let bench1 = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model")
+        .add_model(PassThroughModel::default(), Mailbox::new(), "");
+    let bench2 = SimInit::with_num_threads(num_threads)
+        .add_model(model2, mbox2, "model")
+        .add_model(PassThroughModel::default(), Mailbox::new(), "");
+    let bench = bench1.merge(bench2);
+
+    let names: Vec<_> = bench
+        .addresses::<PassThroughModel>()
+        .map(|(name, _)| name.to_string())
+        .collect();
+    assert_eq!(names, ["model", "", "model", ""]);
+
+    let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+
+    simu.process_event(PassThroughModel::input, 1, &addr1)
+        .unwrap();
+    simu.process_event(PassThroughModel::input, 2, &addr2)
+        .unwrap();
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(output.next(), Some(2));
+    assert_eq!(output.next(), None);
+}
+
+fn merge_benches_instrumentation(num_threads: usize) {
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    // Instrumentation enabled on either bench applies to the merged bench.
+    let bench1 = SimInit::with_num_threads(num_threads)
+        .add_model(PassThroughModel::default(), mbox1, "model1")
+        .set_event_log(1);
+    let bench2 = SimInit::with_num_threads(num_threads)
+        .add_model(PassThroughModel::default(), mbox2, "model2")
+        .set_event_type_counting(true)
+        .set_event_log(2);
+
+    let mut simu = bench1.merge(bench2).init(MonotonicTime::EPOCH).unwrap().0;
+
+    simu.process_event(PassThroughModel::input, 1, &addr1)
+        .unwrap();
+    simu.process_event(PassThroughModel::input, 2, &addr2)
+        .unwrap();
+
+    assert_eq!(
+        simu.event_type_counts().get(std::any::type_name::<u32>()),
+        Some(&2)
+    );
+    let names: Vec<_> = simu
+        .event_log()
+        .into_iter()
+        .map(|entry| entry.model_name)
+        .collect();
+    assert_eq!(names, ["model1", "model2"]);
+}
+
+fn merge_benches_address_lookup(num_threads: usize) {
+    let mut model1 = PassThroughModel::default();
+    let mut output = EventBuffer::new();
+    model1.output.connect_sink(&output);
+
+    let bench1 = SimInit::with_num_threads(num_threads).add_model(model1, Mailbox::new(), "model1");
+    let bench2 = SimInit::with_num_threads(num_threads).add_model(
+        PassThroughModel::default(),
+        Mailbox::new(),
+        "model2",
+    );
+    let bench = bench1.merge(bench2);
+
+    let addr1 = bench.address_of::<PassThroughModel>("model1").unwrap();
+    assert!(bench.address_of::<PassThroughModel>("model2").is_some());
+    assert!(bench.address_of::<PassThroughModel>("model3").is_none());
+    let names: Vec<_> = bench
+        .addresses::<PassThroughModel>()
+        .map(|(name, _)| name.to_string())
+        .collect();
+    assert_eq!(names, ["model1", "model2"]);
+
+    let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+
+    simu.process_event(PassThroughModel::input, 42, &addr1)
+        .unwrap();
+    assert_eq!(output.next(), Some(42));
+    assert_eq!(output.next(), None);
+}
+
+#[test]
+fn merge_benches_st() {
+    merge_benches(1);
+}
+
+#[test]
+fn merge_benches_mt() {
+    merge_benches(MT_NUM_THREADS);
+}
+
+#[test]
+fn merge_benches_duplicate_name_st() {
+    merge_benches_duplicate_name(1);
+}
+
+#[test]
+fn merge_benches_duplicate_name_mt() {
+    merge_benches_duplicate_name(MT_NUM_THREADS);
+}
+
+#[test]
+fn merge_benches_instrumentation_st() {
+    merge_benches_instrumentation(1);
+}
+
+#[test]
+fn merge_benches_instrumentation_mt() {
+    merge_benches_instrumentation(MT_NUM_THREADS);
+}
+
+#[test]
+fn merge_benches_address_lookup_st() {
+    merge_benches_address_lookup(1);
+}
+
+#[test]
+fn merge_benches_address_lookup_mt() {
+    merge_benches_address_lookup(MT_NUM_THREADS);
+}
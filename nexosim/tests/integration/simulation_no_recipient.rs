@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use nexosim::model::Model;
 use nexosim::ports::{EventSource, Output, QuerySource, Requestor};
-use nexosim::simulation::{ExecutionError, Mailbox, SimInit};
+use nexosim::simulation::{ExecutionError, Mailbox, QueryError, SchedulingError, SimInit};
 use nexosim::time::MonotonicTime;
 
 const MT_NUM_THREADS: usize = 4;
@@ -21,6 +21,7 @@ impl TestModel {
     async fn activate_requestor(&mut self) {
         let _ = self.requestor.send(()).await;
     }
+    async fn ping(&mut self) {}
 }
 impl Model for TestModel {}
 
@@ -128,6 +129,91 @@ fn no_replier_from_scheduler(num_threads: usize) {
     }
 }
 
+/// Process a query targeting a dead mailbox.
+fn no_replier_from_simulation(num_threads: usize) {
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let bad_mbox = Mailbox::<TestModel>::new();
+    let bad_addr = bad_mbox.address();
+
+    drop(bad_mbox);
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel::default(), mbox, "testmodel")
+        .init(t0)
+        .unwrap()
+        .0;
+
+    match simu.process_query(TestModel::ping, (), &bad_addr) {
+        Err(ExecutionError::BadQuery(QueryError::NoRecipient)) => {}
+        _ => panic!("missing recipient not detected"),
+    }
+
+    // The error is not fatal.
+    simu.process_query(TestModel::ping, (), &addr).unwrap();
+}
+
+/// Schedule events targeting dead mailboxes.
+fn no_input_from_schedule_event(num_threads: usize) {
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let bad_mbox = Mailbox::<TestModel>::new();
+    let bad_addr = bad_mbox.address();
+
+    drop(bad_mbox);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel::default(), mbox, "testmodel")
+        .init(t0)
+        .unwrap();
+
+    let deadline = Duration::from_secs(1);
+    assert_eq!(
+        scheduler.schedule_event(deadline, TestModel::ping, (), &bad_addr),
+        Err(SchedulingError::NoRecipient)
+    );
+    assert_eq!(
+        scheduler
+            .schedule_keyed_periodic_event(deadline, deadline, TestModel::ping, (), &bad_addr)
+            .map(|_| ()),
+        Err(SchedulingError::NoRecipient)
+    );
+
+    // The mailbox of a removed model is dropped as well.
+    scheduler
+        .schedule_event(deadline, TestModel::ping, (), &addr)
+        .unwrap();
+    simu.remove_model(&addr).unwrap();
+    assert_eq!(
+        scheduler.schedule_event(deadline, TestModel::ping, (), &addr),
+        Err(SchedulingError::NoRecipient)
+    );
+}
+
+/// Check the liveness of model addresses.
+fn address_liveness(num_threads: usize) {
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let bad_mbox = Mailbox::<TestModel>::new();
+    let bad_addr = bad_mbox.address();
+
+    drop(bad_mbox);
+    assert!(!bad_addr.is_connected());
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(TestModel::default(), mbox, "testmodel")
+        .init(t0)
+        .unwrap()
+        .0;
+    assert!(addr.is_connected());
+
+    simu.remove_model(&addr).unwrap();
+    assert!(!addr.is_connected());
+}
+
 #[test]
 fn no_input_from_model_st() {
     no_input_from_model(1);
@@ -167,3 +253,33 @@ fn no_replier_from_scheduler_st() {
 fn no_replier_from_scheduler_mt() {
     no_replier_from_scheduler(MT_NUM_THREADS);
 }
+
+#[test]
+fn no_replier_from_simulation_st() {
+    no_replier_from_simulation(1);
+}
+
+#[test]
+fn no_replier_from_simulation_mt() {
+    no_replier_from_simulation(MT_NUM_THREADS);
+}
+
+#[test]
+fn no_input_from_schedule_event_st() {
+    no_input_from_schedule_event(1);
+}
+
+#[test]
+fn no_input_from_schedule_event_mt() {
+    no_input_from_schedule_event(MT_NUM_THREADS);
+}
+
+#[test]
+fn address_liveness_st() {
+    address_liveness(1);
+}
+
+#[test]
+fn address_liveness_mt() {
+    address_liveness(MT_NUM_THREADS);
+}
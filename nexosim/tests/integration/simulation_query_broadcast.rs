@@ -0,0 +1,87 @@
+//! Query broadcast from a `Simulation` instance.
+
+use nexosim::model::Model;
+use nexosim::ports::QuerySource;
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct TestModel {
+    factor: u32,
+}
+impl TestModel {
+    async fn multiply(&mut self, arg: u32) -> u32 {
+        arg * self.factor
+    }
+}
+impl Model for TestModel {}
+
+fn broadcast_query(num_threads: usize) {
+    let mut source = QuerySource::new();
+    let mut bench = SimInit::with_num_threads(num_threads);
+    for factor in 1..=3 {
+        let mbox = Mailbox::new();
+        source.connect(TestModel::multiply, &mbox);
+        bench = bench.add_model(TestModel { factor }, mbox, format!("model{}", factor));
+    }
+
+    let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+
+    let mut replies = simu.broadcast_query(&source, 5).unwrap();
+    replies.sort();
+    assert_eq!(replies, vec![5, 10, 15]);
+
+    // A source with no connected replier yields no reply.
+    let disconnected = QuerySource::<u32, u32>::new();
+    assert!(simu.broadcast_query(&disconnected, 5).unwrap().is_empty());
+}
+
+fn query_reply_names(num_threads: usize) {
+    let mut source = QuerySource::new();
+    let mut bench = SimInit::with_num_threads(num_threads);
+    for factor in 1..=3 {
+        let mbox = Mailbox::new();
+        source.connect(TestModel::multiply, &mbox);
+        bench = bench.add_model(TestModel { factor }, mbox, format!("model{}", factor));
+    }
+
+    let mut simu = bench.init(MonotonicTime::EPOCH).unwrap().0;
+
+    let (action, mut reply_receiver) = source.query(5);
+    simu.process(action).unwrap();
+
+    let mut replies: Vec<_> = reply_receiver.take_with_names().unwrap().collect();
+    replies.sort();
+    assert_eq!(
+        replies,
+        vec![
+            ("model1".to_string(), 5),
+            ("model2".to_string(), 10),
+            ("model3".to_string(), 15)
+        ]
+    );
+
+    // The replies were already taken.
+    assert!(reply_receiver.take().is_none());
+}
+
+#[test]
+fn broadcast_query_st() {
+    broadcast_query(1);
+}
+
+#[test]
+fn broadcast_query_mt() {
+    broadcast_query(MT_NUM_THREADS);
+}
+
+#[test]
+fn query_reply_names_st() {
+    query_reply_names(1);
+}
+
+#[test]
+fn query_reply_names_mt() {
+    query_reply_names(MT_NUM_THREADS);
+}
@@ -0,0 +1,98 @@
+//! Recording of the scheduler queue length history.
+
+use std::time::Duration;
+
+use nexosim::model::Model;
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+#[derive(Default)]
+struct TestModel {}
+impl TestModel {
+    async fn sink(&mut self, _: ()) {}
+}
+impl Model for TestModel {}
+
+fn queue_length_history(num_threads: usize) {
+    let model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .set_queue_length_history(3)
+        .init(t0)
+        .unwrap();
+
+    for secs in 1..=5 {
+        scheduler
+            .schedule_event(Duration::from_secs(secs), TestModel::sink, (), &addr)
+            .unwrap();
+    }
+
+    assert!(simu.queue_length_history().is_empty());
+
+    simu.step().unwrap();
+    simu.step().unwrap();
+    assert_eq!(
+        simu.queue_length_history(),
+        &[
+            (t0 + Duration::from_secs(1), 4),
+            (t0 + Duration::from_secs(2), 3)
+        ]
+    );
+
+    // Only the 3 most recent records are kept.
+    simu.step().unwrap();
+    simu.step().unwrap();
+    simu.step().unwrap();
+    assert_eq!(
+        simu.queue_length_history(),
+        &[
+            (t0 + Duration::from_secs(3), 2),
+            (t0 + Duration::from_secs(4), 1),
+            (t0 + Duration::from_secs(5), 0)
+        ]
+    );
+}
+
+fn queue_length_history_disabled(num_threads: usize) {
+    let model = TestModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "model")
+        .init(MonotonicTime::EPOCH)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), TestModel::sink, (), &addr)
+        .unwrap();
+    simu.step().unwrap();
+
+    assert!(simu.queue_length_history().is_empty());
+}
+
+#[test]
+fn queue_length_history_st() {
+    queue_length_history(1);
+}
+
+#[test]
+fn queue_length_history_mt() {
+    queue_length_history(MT_NUM_THREADS);
+}
+
+#[test]
+fn queue_length_history_disabled_st() {
+    queue_length_history_disabled(1);
+}
+
+#[test]
+fn queue_length_history_disabled_mt() {
+    queue_length_history_disabled(MT_NUM_THREADS);
+}
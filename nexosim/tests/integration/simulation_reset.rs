@@ -0,0 +1,135 @@
+//! Episode reset and schedule clearing of a running simulation.
+
+use std::time::Duration;
+
+use nexosim::model::{Context, Model};
+use nexosim::ports::{EventBuffer, Output};
+use nexosim::simulation::{Mailbox, SimInit};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+// A model that outputs the running sum of its inputs.
+#[derive(Default)]
+struct SumModel {
+    output: Output<u64>,
+    sum: u64,
+}
+impl SumModel {
+    async fn input(&mut self, value: u64) {
+        self.sum += value;
+        self.output.send(self.sum).await;
+    }
+}
+impl Model for SumModel {
+    async fn reset(&mut self, _: &mut Context<Self>) {
+        self.sum = 0;
+    }
+}
+
+fn reset_episode(num_threads: usize) {
+    let mut model = SumModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "sum")
+        .init(t0)
+        .unwrap();
+
+    for episode in 0..2 {
+        scheduler
+            .schedule_event(Duration::from_secs(1), SumModel::input, 1, &addr)
+            .unwrap();
+        scheduler
+            .schedule_event(Duration::from_secs(2), SumModel::input, 2, &addr)
+            .unwrap();
+        // Only scheduled actions that were not yet processed are discarded.
+        scheduler
+            .schedule_event(Duration::from_secs(5), SumModel::input, 4, &addr)
+            .unwrap();
+
+        simu.step_until(Duration::from_secs(2)).unwrap();
+        assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+        assert_eq!(
+            output.by_ref().collect::<Vec<_>>(),
+            vec![1, 3],
+            "episode {episode}"
+        );
+
+        simu.reset(t0).unwrap();
+        assert_eq!(simu.time(), t0);
+    }
+
+    simu.step_until(Duration::from_secs(10)).unwrap();
+    assert!(output.next().is_none());
+}
+
+fn clear_schedule(num_threads: usize) {
+    let mut model = SumModel::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "sum")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), SumModel::input, 1, &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(2), SumModel::input, 2, &addr)
+        .unwrap();
+    scheduler
+        .schedule_periodic_event(
+            Duration::from_secs(3),
+            Duration::from_secs(1),
+            SumModel::input,
+            4,
+            &addr,
+        )
+        .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some(1));
+
+    assert_eq!(simu.clear_schedule(), 2);
+    assert_eq!(simu.clear_schedule(), 0);
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+
+    simu.step_until(Duration::from_secs(10)).unwrap();
+    assert!(output.next().is_none());
+
+    // The state of the model is preserved.
+    simu.process_event(SumModel::input, 8, &addr).unwrap();
+    assert_eq!(output.next(), Some(9));
+}
+
+#[test]
+fn reset_episode_st() {
+    reset_episode(1);
+}
+
+#[test]
+fn reset_episode_mt() {
+    reset_episode(MT_NUM_THREADS);
+}
+
+#[test]
+fn clear_schedule_st() {
+    clear_schedule(1);
+}
+
+#[test]
+fn clear_schedule_mt() {
+    clear_schedule(MT_NUM_THREADS);
+}
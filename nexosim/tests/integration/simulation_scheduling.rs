@@ -1,12 +1,15 @@
 //! Event scheduling from a `Simulation` instance.
 
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[cfg(not(miri))]
 use nexosim::model::Context;
 use nexosim::model::Model;
-use nexosim::ports::{EventBuffer, Output};
-use nexosim::simulation::{Address, Mailbox, Scheduler, SimInit, Simulation};
+use nexosim::ports::{EventBuffer, EventSource, Output, Timestamped};
+use nexosim::simulation::{
+    ActionKey, Address, ExecutionError, Mailbox, Scheduler, SchedulingError, SimInit, Simulation,
+};
 use nexosim::time::MonotonicTime;
 
 const MT_NUM_THREADS: usize = 4;
@@ -71,6 +74,8 @@ fn schedule_events(num_threads: usize) {
         )
         .unwrap();
 
+    let marker = simu.mark();
+
     // Move to the 1st event at t0+2s.
     simu.step().unwrap();
     assert_eq!(simu.time(), t0 + Duration::from_secs(2));
@@ -91,6 +96,9 @@ fn schedule_events(num_threads: usize) {
     assert_eq!(simu.time(), t0 + Duration::from_secs(6));
     assert!(output.next().is_some());
     assert!(output.next().is_none());
+
+    assert_eq!(simu.elapsed_since(marker), Some(Duration::from_secs(6)));
+    assert_eq!(simu.elapsed_since(t0 + Duration::from_secs(7)), None);
 }
 
 fn schedule_keyed_events(num_threads: usize) {
@@ -215,6 +223,628 @@ fn schedule_periodic_keyed_events(num_threads: usize) {
     }
 }
 
+fn schedule_ranked_events(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    // Two pass-through models feeding the same sink.
+    let mut model1 = PassThroughModel::new();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let mut model2 = PassThroughModel::new();
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    let mut output = EventBuffer::new();
+    model1.output.connect_sink(&output);
+    model2.output.connect_sink(&output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .init(t0)
+        .unwrap();
+
+    // Schedule events for the same time, in reverse order of their ranks.
+    scheduler
+        .with_rank(1)
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr1)
+        .unwrap();
+    scheduler
+        .with_rank(0)
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 2, &addr2)
+        .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert_eq!(output.next(), Some(2));
+    assert_eq!(output.next(), Some(1));
+    assert!(output.next().is_none());
+}
+
+fn schedule_merged_event_sources(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model1 = PassThroughModel::new();
+    let mbox1 = Mailbox::new();
+    let mut model2 = PassThroughModel::new();
+    let mbox2 = Mailbox::new();
+
+    let output = EventBuffer::new();
+    model1.output.map_connect_sink(|v| (1, *v), &output);
+    model2.output.map_connect_sink(|v| (2, *v), &output);
+
+    let mut source1 = EventSource::new();
+    source1.connect(PassThroughModel::input, &mbox1);
+    let mut source2 = EventSource::new();
+    source2.connect(PassThroughModel::input, &mbox2);
+    source1.merge(source2);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule(Duration::from_secs(1), source1.event(42))
+        .unwrap();
+
+    simu.step().unwrap();
+    let mut events: Vec<_> = output.collect();
+    events.sort();
+    assert_eq!(events, vec![(1, 42), (2, 42)]);
+}
+
+fn cancel_keyed_periodic_source_event(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model = PassThroughModel::new();
+    let mbox = Mailbox::new();
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let mut source = EventSource::new();
+    source.connect(PassThroughModel::input, &mbox);
+    let source = Arc::new(source);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    // Queue a periodic event at t0 + 1s + k*2s.
+    let (action, key) = source.keyed_periodic_event(Duration::from_secs(2), 7);
+    scheduler.schedule(Duration::from_secs(1), action).unwrap();
+
+    for k in 0..3 {
+        simu.step().unwrap();
+        assert_eq!(
+            simu.time(),
+            t0 + Duration::from_secs(1) + k * Duration::from_secs(2)
+        );
+        assert_eq!(output.next(), Some(7));
+        assert!(output.next().is_none());
+    }
+
+    // Cancelling the key stops the whole series.
+    key.cancel();
+    simu.step_until(Duration::from_secs(20)).unwrap();
+    assert!(output.next().is_none());
+}
+
+fn step_model_subset(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model1 = PassThroughModel::new();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let mut model2 = PassThroughModel::new();
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    let mut output = EventBuffer::new();
+    model1.output.map_connect_sink(|v| (1, *v), &output);
+    model2.output.map_connect_sink(|v| (2, *v), &output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .init(t0)
+        .unwrap();
+
+    let t1 = t0 + Duration::from_secs(1);
+    let t2 = t0 + Duration::from_secs(2);
+    let t3 = t0 + Duration::from_secs(3);
+    scheduler
+        .schedule_event(t1, PassThroughModel::input, 1, &addr1)
+        .unwrap();
+    scheduler
+        .schedule_event(t2, PassThroughModel::input, 2, &addr2)
+        .unwrap();
+    scheduler
+        .schedule_event(t3, PassThroughModel::input, 3, &addr1)
+        .unwrap();
+
+    // The event of the first model at t1 is held back.
+    simu.step_subset(&[(&addr2).into()]).unwrap();
+    assert_eq!(simu.time(), t2);
+    assert_eq!(output.next(), Some((2, 2)));
+    assert!(output.next().is_none());
+
+    // No event remains for the second model.
+    simu.step_subset(&[(&addr2).into()]).unwrap();
+    assert_eq!(simu.time(), t2);
+    assert!(output.next().is_none());
+
+    // The held-back event is processed without advancing time.
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t2);
+    assert_eq!(output.next(), Some((1, 1)));
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t3);
+    assert_eq!(output.next(), Some((1, 3)));
+    assert!(output.next().is_none());
+}
+
+fn step_until_current_time(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model1 = PassThroughModel::new();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let mut model2 = PassThroughModel::new();
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    let mut output = EventBuffer::new();
+    model1.output.map_connect_sink(|v| (1, *v), &output);
+    model2.output.map_connect_sink(|v| (2, *v), &output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .init(t0)
+        .unwrap();
+
+    // A deadline equal to the current time is a no-op.
+    simu.step_until(t0).unwrap();
+    assert_eq!(simu.time(), t0);
+
+    let t1 = t0 + Duration::from_secs(1);
+    let t2 = t0 + Duration::from_secs(2);
+    scheduler
+        .schedule_event(t1, PassThroughModel::input, 1, &addr1)
+        .unwrap();
+    scheduler
+        .schedule_event(t2, PassThroughModel::input, 2, &addr2)
+        .unwrap();
+
+    // The event of the first model at t1 is held back.
+    simu.step_subset(&[(&addr2).into()]).unwrap();
+    assert_eq!(output.next(), Some((2, 2)));
+    assert!(output.next().is_none());
+
+    // The held-back event is due at the current time.
+    simu.step_until(t2).unwrap();
+    assert_eq!(simu.time(), t2);
+    assert_eq!(output.next(), Some((1, 1)));
+    assert!(output.next().is_none());
+
+    assert!(matches!(
+        simu.step_until(t1),
+        Err(ExecutionError::InvalidDeadline(t)) if t == t1
+    ));
+}
+
+fn step_events(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model1 = PassThroughModel::new();
+    let mbox1 = Mailbox::new();
+    let addr1 = mbox1.address();
+    let mut model2 = PassThroughModel::new();
+    let mbox2 = Mailbox::new();
+    let addr2 = mbox2.address();
+
+    let mut output = EventBuffer::new();
+    model1.output.map_connect_sink(|v| (1, *v), &output);
+    model2.output.map_connect_sink(|v| (2, *v), &output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model1, mbox1, "model1")
+        .add_model(model2, mbox2, "model2")
+        .init(t0)
+        .unwrap();
+
+    let t1 = t0 + Duration::from_secs(1);
+    let t2 = t0 + Duration::from_secs(2);
+    scheduler
+        .schedule_event(t1, PassThroughModel::input, 1, &addr1)
+        .unwrap();
+    scheduler
+        .schedule_event(t1, PassThroughModel::input, 2, &addr2)
+        .unwrap();
+    let key = scheduler
+        .schedule_keyed_event(t1, PassThroughModel::input, 3, &addr2)
+        .unwrap();
+    scheduler
+        .schedule_event(t2, PassThroughModel::input, 4, &addr1)
+        .unwrap();
+    key.cancel();
+
+    // Only the first event of the time slice is processed.
+    assert_eq!(simu.step_events(1).unwrap(), 1);
+    assert_eq!(simu.time(), t1);
+    assert_eq!(output.next(), Some((1, 1)));
+    assert!(output.next().is_none());
+
+    // The cancelled event is not counted.
+    assert_eq!(simu.step_events(2).unwrap(), 2);
+    assert_eq!(simu.time(), t2);
+    assert_eq!(output.next(), Some((2, 2)));
+    assert_eq!(output.next(), Some((1, 4)));
+    assert!(output.next().is_none());
+
+    // No event remains scheduled.
+    assert_eq!(simu.step_events(5).unwrap(), 0);
+    assert_eq!(simu.time(), t2);
+}
+
+fn schedule_past_time_ceiling(num_threads: usize) {
+    let t0 = MonotonicTime::MAX - Duration::from_secs(2);
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    assert_eq!(
+        scheduler.schedule_event(Duration::MAX, PassThroughModel::input, 0, &addr),
+        Err(SchedulingError::TimeOverflow)
+    );
+    assert_eq!(
+        scheduler.schedule_event(Duration::from_secs(3), PassThroughModel::input, 0, &addr),
+        Err(SchedulingError::TimeOverflow)
+    );
+
+    // A periodic event whose next recurrence would overflow is processed
+    // only once.
+    scheduler
+        .schedule_periodic_event(
+            Duration::from_secs(1),
+            Duration::from_secs(2),
+            PassThroughModel::input,
+            1,
+            &addr,
+        )
+        .unwrap();
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert_eq!(output.next(), Some(1));
+
+    // Simulation time cannot be advanced past the ceiling either.
+    assert!(matches!(
+        simu.step_until(Duration::from_secs(2)),
+        Err(ExecutionError::InvalidDeadline(time)) if time == MonotonicTime::MAX
+    ));
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+
+    simu.step_until(MonotonicTime::MAX).unwrap();
+    assert!(output.next().is_none());
+}
+
+fn observe_fired_keys(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    let event_t1 = scheduler
+        .schedule_keyed_periodic_event(
+            Duration::from_secs(1),
+            Duration::from_secs(3),
+            PassThroughModel::input,
+            1,
+            &addr,
+        )
+        .unwrap();
+    let event_t2 = scheduler
+        .schedule_keyed_event(Duration::from_secs(2), PassThroughModel::input, 2, &addr)
+        .unwrap();
+
+    let fired = Arc::new(Mutex::new(Vec::new()));
+    for (key, id) in [(&event_t1, 1), (&event_t2, 2)] {
+        let fired = fired.clone();
+        simu.on_key_fired(key, move |time| fired.lock().unwrap().push((id, time)));
+    }
+    let dropped = Arc::new(());
+    let guard = dropped.clone();
+    simu.on_key_fired(&event_t2, move |_| drop(guard));
+
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(
+        *fired.lock().unwrap(),
+        vec![(1, t0 + Duration::from_secs(1))]
+    );
+
+    // The callbacks of a cancelled key are dropped without being invoked.
+    event_t2.cancel();
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(4));
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(fired.lock().unwrap().len(), 1);
+    assert_eq!(Arc::strong_count(&dropped), 1);
+}
+
+// A pass-through model which can also cancel a shared action key.
+#[derive(Default)]
+struct CancellingModel {
+    key: Arc<Mutex<Option<ActionKey>>>,
+    output: Output<u32>,
+}
+impl CancellingModel {
+    async fn input(&mut self, arg: u32) {
+        self.output.send(arg).await;
+    }
+    async fn cancel(&mut self) {
+        if let Some(key) = self.key.lock().unwrap().take() {
+            key.cancel();
+        }
+    }
+}
+impl Model for CancellingModel {}
+
+fn skip_callbacks_of_cancelled_keys(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model = CancellingModel::default();
+    let shared_key = model.key.clone();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    // The cancelling event is processed first and cancels the keyed event
+    // after both were pulled from the scheduler queue.
+    let t1 = t0 + Duration::from_secs(1);
+    scheduler
+        .schedule_event(t1, CancellingModel::cancel, (), &addr)
+        .unwrap();
+    let key = scheduler
+        .schedule_keyed_event(t1, CancellingModel::input, 1, &addr)
+        .unwrap();
+    *shared_key.lock().unwrap() = Some(key.clone());
+
+    let fired = Arc::new(Mutex::new(Vec::new()));
+    {
+        let fired = fired.clone();
+        simu.on_key_fired(&key, move |time| fired.lock().unwrap().push(time));
+    }
+
+    // A callback registered with an already cancelled key is dropped.
+    let t2 = t0 + Duration::from_secs(2);
+    let cancelled_key = scheduler
+        .schedule_keyed_event(t2, CancellingModel::input, 2, &addr)
+        .unwrap();
+    cancelled_key.clone().cancel();
+    let guard = Arc::new(());
+    {
+        let guard = guard.clone();
+        simu.on_key_fired(&cancelled_key, move |_| drop(guard));
+    }
+    assert_eq!(Arc::strong_count(&guard), 1);
+
+    simu.step().unwrap();
+    assert!(output.next().is_none());
+    assert!(fired.lock().unwrap().is_empty());
+}
+
+fn prewarm_before_step(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, (), &addr)
+        .unwrap();
+
+    // Pre-warming neither advances time nor processes scheduled events.
+    simu.prewarm().unwrap();
+    simu.prewarm().unwrap();
+    assert_eq!(simu.time(), t0);
+    assert!(output.next().is_none());
+
+    simu.step().unwrap();
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+    assert!(output.next().is_some());
+}
+
+fn step_reports_new_time(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    let key = scheduler
+        .schedule_keyed_event(Duration::from_secs(3), PassThroughModel::input, 2, &addr)
+        .unwrap();
+    key.cancel();
+
+    assert_eq!(simu.step().unwrap(), Some(t0 + Duration::from_secs(2)));
+    assert_eq!(output.next(), Some(1));
+
+    // Cancelled events do not count as scheduled events.
+    assert_eq!(simu.step().unwrap(), None);
+    assert_eq!(simu.time(), t0 + Duration::from_secs(2));
+    assert!(output.next().is_none());
+}
+
+fn peek_next_event_time(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    assert_eq!(simu.next_event_time(), None);
+    assert_eq!(simu.pending_event_count(), 0);
+
+    scheduler
+        .schedule_event(Duration::from_secs(3), PassThroughModel::input, 2, &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+
+    // Peeking leaves the queue and simulation time untouched.
+    assert_eq!(simu.next_event_time(), Some(t0 + Duration::from_secs(1)));
+    assert_eq!(simu.next_event_time(), Some(t0 + Duration::from_secs(1)));
+    assert_eq!(simu.pending_event_count(), 2);
+    assert_eq!(simu.time(), t0);
+
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some(1));
+    assert_eq!(simu.next_event_time(), Some(t0 + Duration::from_secs(3)));
+    assert_eq!(simu.pending_event_count(), 1);
+
+    simu.step().unwrap();
+    assert_eq!(output.next(), Some(2));
+    assert_eq!(simu.next_event_time(), None);
+    assert_eq!(simu.pending_event_count(), 0);
+}
+
+fn step_bounded_slices(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    for i in 1..=5 {
+        scheduler
+            .schedule_event(Duration::from_secs(i), PassThroughModel::input, i, &addr)
+            .unwrap();
+    }
+    // Events scheduled for the same time make up a single time slice.
+    scheduler
+        .schedule_event(Duration::from_secs(2), PassThroughModel::input, 20, &addr)
+        .unwrap();
+
+    assert_eq!(simu.step_bounded(0).unwrap(), None);
+    assert_eq!(simu.time(), t0);
+
+    assert_eq!(
+        simu.step_bounded(2).unwrap(),
+        Some(t0 + Duration::from_secs(2))
+    );
+    assert_eq!(output.by_ref().collect::<Vec<_>>(), vec![1, 2, 20]);
+
+    assert_eq!(
+        simu.step_bounded(10).unwrap(),
+        Some(t0 + Duration::from_secs(5))
+    );
+    assert_eq!(output.by_ref().collect::<Vec<_>>(), vec![3, 4, 5]);
+
+    assert_eq!(simu.step_bounded(10).unwrap(), None);
+    assert_eq!(simu.time(), t0 + Duration::from_secs(5));
+}
+
+fn post_events(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+    let (mut simu, scheduler, addr, mut output) = passthrough_bench(num_threads, t0);
+
+    simu.post_event(PassThroughModel::input, 1, &addr);
+    simu.post_event(PassThroughModel::input, 2, &addr);
+    assert!(output.next().is_none());
+
+    simu.process_posted_events().unwrap();
+    let mut events = output.by_ref().collect::<Vec<_>>();
+    events.sort();
+    assert_eq!(events, vec![1, 2]);
+
+    // Pending posted events are processed before time is advanced.
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 4, &addr)
+        .unwrap();
+    simu.post_event(PassThroughModel::input, 3, &addr);
+    simu.step().unwrap();
+    assert_eq!(output.by_ref().collect::<Vec<_>>(), vec![3, 4]);
+    assert_eq!(simu.time(), t0 + Duration::from_secs(1));
+}
+
+fn timestamped_sink(num_threads: usize) {
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model = PassThroughModel::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&Timestamped::new(&output));
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), PassThroughModel::input, 1, &addr)
+        .unwrap();
+    scheduler
+        .schedule_event(Duration::from_secs(3), PassThroughModel::input, 3, &addr)
+        .unwrap();
+    simu.step_unbounded().unwrap();
+
+    assert_eq!(
+        output.by_ref().collect::<Vec<_>>(),
+        vec![
+            (t0 + Duration::from_secs(1), 1),
+            (t0 + Duration::from_secs(3), 3)
+        ]
+    );
+}
+
+fn schedule_non_clone_events(num_threads: usize) {
+    // A payload that does not implement `Clone`.
+    struct Payload(u32);
+
+    #[derive(Default)]
+    struct Consumer {
+        output: Output<u32>,
+    }
+    impl Consumer {
+        async fn input(&mut self, payload: Payload) {
+            self.output.send(payload.0).await;
+        }
+    }
+    impl Model for Consumer {}
+
+    let t0 = MonotonicTime::EPOCH;
+
+    let mut model = Consumer::default();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let mut output = EventBuffer::new();
+    model.output.connect_sink(&output);
+
+    let (mut simu, scheduler) = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "")
+        .init(t0)
+        .unwrap();
+
+    scheduler
+        .schedule_event(Duration::from_secs(1), Consumer::input, Payload(1), &addr)
+        .unwrap();
+    let key = scheduler
+        .schedule_keyed_event(Duration::from_secs(2), Consumer::input, Payload(2), &addr)
+        .unwrap();
+    scheduler
+        .schedule_keyed_event(Duration::from_secs(3), Consumer::input, Payload(3), &addr)
+        .unwrap();
+    key.cancel();
+    simu.process_event(Consumer::input, Payload(0), &addr)
+        .unwrap();
+    simu.step_unbounded().unwrap();
+
+    assert_eq!(output.by_ref().collect::<Vec<_>>(), vec![0, 1, 3]);
+}
+
 #[test]
 fn schedule_events_st() {
     schedule_events(1);
@@ -255,6 +885,136 @@ fn schedule_periodic_keyed_events_mt() {
     schedule_periodic_keyed_events(MT_NUM_THREADS);
 }
 
+#[test]
+fn schedule_ranked_events_st() {
+    schedule_ranked_events(1);
+}
+
+#[test]
+fn schedule_ranked_events_mt() {
+    schedule_ranked_events(MT_NUM_THREADS);
+}
+
+#[test]
+fn schedule_merged_event_sources_st() {
+    schedule_merged_event_sources(1);
+}
+
+#[test]
+fn schedule_merged_event_sources_mt() {
+    schedule_merged_event_sources(MT_NUM_THREADS);
+}
+
+#[test]
+fn cancel_keyed_periodic_source_event_st() {
+    cancel_keyed_periodic_source_event(1);
+}
+
+#[test]
+fn cancel_keyed_periodic_source_event_mt() {
+    cancel_keyed_periodic_source_event(MT_NUM_THREADS);
+}
+
+#[test]
+fn step_model_subset_st() {
+    step_model_subset(1);
+}
+
+#[test]
+fn step_model_subset_mt() {
+    step_model_subset(MT_NUM_THREADS);
+}
+
+#[test]
+fn observe_fired_keys_st() {
+    observe_fired_keys(1);
+}
+
+#[test]
+fn observe_fired_keys_mt() {
+    observe_fired_keys(MT_NUM_THREADS);
+}
+
+#[test]
+fn skip_callbacks_of_cancelled_keys_st() {
+    skip_callbacks_of_cancelled_keys(1);
+}
+
+#[test]
+fn skip_callbacks_of_cancelled_keys_mt() {
+    skip_callbacks_of_cancelled_keys(MT_NUM_THREADS);
+}
+
+#[test]
+fn prewarm_before_step_st() {
+    prewarm_before_step(1);
+}
+
+#[test]
+fn prewarm_before_step_mt() {
+    prewarm_before_step(MT_NUM_THREADS);
+}
+
+#[test]
+fn step_reports_new_time_st() {
+    step_reports_new_time(1);
+}
+
+#[test]
+fn step_reports_new_time_mt() {
+    step_reports_new_time(MT_NUM_THREADS);
+}
+
+#[test]
+fn peek_next_event_time_st() {
+    peek_next_event_time(1);
+}
+
+#[test]
+fn peek_next_event_time_mt() {
+    peek_next_event_time(MT_NUM_THREADS);
+}
+
+#[test]
+fn step_bounded_slices_st() {
+    step_bounded_slices(1);
+}
+
+#[test]
+fn step_bounded_slices_mt() {
+    step_bounded_slices(MT_NUM_THREADS);
+}
+
+#[test]
+fn post_events_st() {
+    post_events(1);
+}
+
+#[test]
+fn post_events_mt() {
+    post_events(MT_NUM_THREADS);
+}
+
+#[test]
+fn timestamped_sink_st() {
+    timestamped_sink(1);
+}
+
+#[test]
+fn timestamped_sink_mt() {
+    timestamped_sink(MT_NUM_THREADS);
+}
+
+#[test]
+fn schedule_non_clone_events_st() {
+    schedule_non_clone_events(1);
+}
+
+#[test]
+fn schedule_non_clone_events_mt() {
+    schedule_non_clone_events(MT_NUM_THREADS);
+}
+
 #[cfg(not(miri))]
 use std::time::{Instant, SystemTime};
 
@@ -504,3 +1264,33 @@ fn auto_system_clock_st() {
 fn auto_system_clock_mt() {
     auto_system_clock(MT_NUM_THREADS);
 }
+
+#[test]
+fn step_until_current_time_st() {
+    step_until_current_time(1);
+}
+
+#[test]
+fn step_until_current_time_mt() {
+    step_until_current_time(MT_NUM_THREADS);
+}
+
+#[test]
+fn step_events_st() {
+    step_events(1);
+}
+
+#[test]
+fn step_events_mt() {
+    step_events(MT_NUM_THREADS);
+}
+
+#[test]
+fn schedule_past_time_ceiling_st() {
+    schedule_past_time_ceiling(1);
+}
+
+#[test]
+fn schedule_past_time_ceiling_mt() {
+    schedule_past_time_ceiling(MT_NUM_THREADS);
+}
@@ -0,0 +1,118 @@
+//! Termination of models upon simulation shutdown.
+
+use std::sync::{Arc, Mutex};
+
+use nexosim::model::{Context, Model};
+use nexosim::simulation::{ExecutionError, Mailbox, Scheduler, SimInit, Simulation};
+use nexosim::time::MonotonicTime;
+
+const MT_NUM_THREADS: usize = 4;
+
+struct TestModel {
+    id: usize,
+    log: Arc<Mutex<Vec<usize>>>,
+}
+impl TestModel {
+    async fn input(&mut self) {}
+}
+impl Model for TestModel {
+    async fn terminate(&mut self, _: &mut Context<Self>) {
+        self.log.lock().unwrap().push(self.id);
+    }
+}
+
+fn bench(num_threads: usize, log: &Arc<Mutex<Vec<usize>>>) -> (Simulation, Scheduler) {
+    let mut sim_init = SimInit::with_num_threads(num_threads);
+    for id in 0..4 {
+        let model = TestModel {
+            id,
+            log: log.clone(),
+        };
+        sim_init = sim_init.add_model(model, Mailbox::new(), id.to_string());
+    }
+
+    sim_init.init(MonotonicTime::EPOCH).unwrap()
+}
+
+fn shutdown_terminates_models(num_threads: usize) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let (mut simu, _) = bench(num_threads, &log);
+
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+    let model = TestModel {
+        id: 4,
+        log: log.clone(),
+    };
+    simu.add_model(model, mbox, "4").unwrap();
+    assert!(log.lock().unwrap().is_empty());
+
+    simu.shutdown().unwrap();
+    assert_eq!(*log.lock().unwrap(), vec![4, 3, 2, 1, 0]);
+
+    assert!(matches!(
+        simu.process_event(TestModel::input, (), &addr),
+        Err(ExecutionError::Terminated)
+    ));
+
+    // Models are not terminated again when the simulation is dropped.
+    drop(simu);
+    assert_eq!(log.lock().unwrap().len(), 5);
+}
+
+fn drop_terminates_models(num_threads: usize) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let (simu, _) = bench(num_threads, &log);
+
+    drop(simu);
+    assert_eq!(*log.lock().unwrap(), vec![3, 2, 1, 0]);
+}
+
+fn drop_terminates_halted_models(num_threads: usize) {
+    // The halt is observed by a step before the simulation is dropped.
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let (mut simu, mut scheduler) = bench(num_threads, &log);
+
+    scheduler.halt();
+    assert!(matches!(simu.step(), Err(ExecutionError::Halted)));
+    drop(simu);
+    assert_eq!(*log.lock().unwrap(), vec![3, 2, 1, 0]);
+
+    // The halt is still pending when the simulation is dropped.
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let (simu, mut scheduler) = bench(num_threads, &log);
+
+    scheduler.halt();
+    drop(simu);
+    assert_eq!(*log.lock().unwrap(), vec![3, 2, 1, 0]);
+}
+
+#[test]
+fn shutdown_terminates_models_st() {
+    shutdown_terminates_models(1);
+}
+
+#[test]
+fn shutdown_terminates_models_mt() {
+    shutdown_terminates_models(MT_NUM_THREADS);
+}
+
+#[test]
+fn drop_terminates_models_st() {
+    drop_terminates_models(1);
+}
+
+#[test]
+fn drop_terminates_models_mt() {
+    drop_terminates_models(MT_NUM_THREADS);
+}
+
+#[test]
+fn drop_terminates_halted_models_st() {
+    drop_terminates_halted_models(1);
+}
+
+#[test]
+fn drop_terminates_halted_models_mt() {
+    drop_terminates_halted_models(MT_NUM_THREADS);
+}
@@ -33,6 +33,12 @@ impl TestModel {
     async fn input(&mut self) {
         self.output.send(()).await;
     }
+
+    async fn probe(&mut self) -> bool {
+        self.output.send(()).await;
+
+        true
+    }
 }
 impl Drop for TestModel {
     fn drop(&mut self) {
@@ -55,6 +61,7 @@ fn timeout_untriggered(num_threads: usize) {
         .0;
 
     assert!(simu.process_event(TestModel::input, (), addr).is_ok());
+    assert!(simu.last_step_task_count() > 0);
 }
 
 fn timeout_triggered(num_threads: usize) {
@@ -83,6 +90,59 @@ fn timeout_triggered(num_threads: usize) {
     assert!(!model_is_alive.load(Ordering::Relaxed));
 }
 
+fn query_timeout_triggered(num_threads: usize) {
+    let (mut model, model_is_alive) = TestModel::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    // Make a loopback connection.
+    model.output.connect(TestModel::input, addr.clone());
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .init(t0)
+        .unwrap()
+        .0;
+    simu.set_query_timeout(Duration::from_millis(100));
+
+    assert!(matches!(
+        simu.process_query(TestModel::probe, (), addr),
+        Err(ExecutionError::Timeout)
+    ));
+
+    // Make sure the request to stop the simulation has succeeded.
+    thread::sleep(Duration::from_millis(10));
+    assert!(!model_is_alive.load(Ordering::Relaxed));
+}
+
+fn poll_budget_triggered(num_threads: usize) {
+    let (mut model, _model_is_alive) = TestModel::new();
+    let mbox = Mailbox::new();
+    let addr = mbox.address();
+
+    // Make a loopback connection.
+    model.output.connect(TestModel::input, addr.clone());
+
+    let t0 = MonotonicTime::EPOCH;
+    let mut simu = SimInit::with_num_threads(num_threads)
+        .add_model(model, mbox, "test")
+        .init(t0)
+        .unwrap()
+        .0;
+    simu.set_poll_budget(1000);
+
+    assert!(matches!(
+        simu.process_event(TestModel::input, (), addr.clone()),
+        Err(ExecutionError::PollBudgetExceeded)
+    ));
+    assert_eq!(simu.last_step_task_count(), 1000);
+    assert!(matches!(
+        simu.process_event(TestModel::input, (), addr),
+        Err(ExecutionError::Terminated)
+    ));
+}
+
 #[test]
 fn timeout_untriggered_st() {
     timeout_untriggered(1);
@@ -102,3 +162,18 @@ fn timeout_triggered_st() {
 fn timeout_triggered_mt() {
     timeout_triggered(MT_NUM_THREADS);
 }
+
+#[test]
+fn query_timeout_triggered_st() {
+    query_timeout_triggered(1);
+}
+
+#[test]
+fn query_timeout_triggered_mt() {
+    query_timeout_triggered(MT_NUM_THREADS);
+}
+
+#[test]
+fn poll_budget_triggered_st() {
+    poll_budget_triggered(1);
+}